@@ -0,0 +1,5 @@
+#![deny(warnings)]
+/// Provides foreign key conversion for String.
+///
+/// Example string `foo` becomes `foo_id`
+pub mod foreignkey;
@@ -0,0 +1,121 @@
+//! Typed responses of the crates.io web API.
+//!
+//! Package info from a sparse or local registry index is converted into the
+//! same types.
+
+use crate::{CloneError, Cloner};
+use serde::Deserialize;
+
+/// The package info returned by `/api/v1/crates/<name>`, from
+/// [`Cloner::crate_info`].
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct CrateResponse {
+    /// Information about the package.
+    #[serde(rename = "crate")]
+    pub krate: CrateData,
+    /// The published versions, in no particular order.
+    pub versions: Vec<CrateVersion>,
+}
+
+/// Information about a package, in a [`CrateResponse`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[non_exhaustive]
+pub struct CrateData {
+    /// The package name, with the capitalization it was published with.
+    pub name: String,
+    /// The repository URL from the package's `Cargo.toml`.
+    pub repository: Option<String>,
+    /// The homepage URL from the package's `Cargo.toml`.
+    pub homepage: Option<String>,
+    /// The description from the package's `Cargo.toml`.
+    pub description: Option<String>,
+    /// The documentation URL from the package's `Cargo.toml`.
+    #[serde(default)]
+    pub documentation: Option<String>,
+}
+
+/// A published version of a package, in a [`CrateResponse`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[non_exhaustive]
+pub struct CrateVersion {
+    /// The version number.
+    pub num: String,
+    /// The download path, relative to the registry URL.
+    pub dl_path: Option<String>,
+    /// The absolute download URL, for versions from an index, which has no
+    /// download paths.
+    #[serde(default)]
+    pub dl_url: Option<String>,
+    /// The SHA-256 checksum of the `.crate` file.
+    pub checksum: Option<String>,
+    /// Whether the version was yanked.
+    #[serde(default)]
+    pub yanked: bool,
+    /// When the version was published, as an RFC 3339 timestamp.
+    ///
+    /// This is `None` for an index, which has no publish dates.
+    pub created_at: Option<String>,
+    /// The SPDX license expression.
+    pub license: Option<String>,
+    /// Links to more information about the version.
+    #[serde(default)]
+    pub links: VersionLinks,
+    /// The dependencies, which an index includes with each version.
+    ///
+    /// This is `None` for the web API, where they are fetched from
+    /// [`VersionLinks::dependencies`].
+    #[serde(default)]
+    pub deps: Option<Vec<Dependency>>,
+}
+
+/// Links of a [`CrateVersion`], as paths relative to the registry URL.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[non_exhaustive]
+pub struct VersionLinks {
+    /// The dependencies of the version.
+    pub dependencies: Option<String>,
+}
+
+/// A dependency of a [`CrateVersion`].
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct Dependency {
+    /// The name of the dependency, which is the name it is renamed to in an
+    /// index.
+    #[serde(alias = "crate_id")]
+    pub name: String,
+    /// The name of the package, if the dependency is renamed in an index.
+    #[serde(default)]
+    pub package: Option<String>,
+    /// The version requirement.
+    pub req: String,
+    /// `normal`, `build`, or `dev`.
+    pub kind: Option<String>,
+    /// Whether the dependency is optional.
+    #[serde(default)]
+    pub optional: bool,
+}
+
+impl Dependency {
+    /// The name of the package depended on.
+    pub fn package_name(&self) -> &str {
+        self.package.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// The response of `/api/v1/crates/<name>/<version>/dependencies`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct DependenciesResponse {
+    pub(crate) dependencies: Vec<Dependency>,
+}
+
+impl Cloner {
+    /// Fetches the package info of a package from the registry.
+    ///
+    /// With a sparse or local registry, this is built from the index, which
+    /// has no repository, homepage, or publish dates.
+    pub fn crate_info(&self, name: &str) -> Result<CrateResponse, CloneError> {
+        Ok(self.get_pkg_info(name)?)
+    }
+}
@@ -0,0 +1,53 @@
+//! Keeping the downloaded `.crate` files, with or without extracting them.
+
+use crate::Cloner;
+use anyhow::Error;
+use std::path::PathBuf;
+
+impl Cloner {
+    /// Sets whether the downloaded `.crate` file is saved in the output
+    /// directory as `<name>-<version>.crate`, next to the extracted package.
+    ///
+    /// This is the exact file published to the registry, such as for
+    /// hashing or archival. Packages are always downloaded with the `crate`
+    /// method.
+    pub fn set_keep_archive(&mut self, value: bool) -> &mut Self {
+        self.keep_archive = value;
+        self
+    }
+
+    /// Sets whether `.crate` files are extracted. Defaults to `true`.
+    ///
+    /// Without extraction, only the `.crate` file is saved, as with
+    /// [`Cloner::set_keep_archive`], and [`ClonedPackage::path`] is the file.
+    ///
+    /// [`ClonedPackage::path`]: crate::ClonedPackage::path
+    pub fn set_extract(&mut self, value: bool) -> &mut Self {
+        self.extract = value;
+        self
+    }
+
+    /// Returns whether the `.crate` file is saved in the output directory.
+    pub(crate) fn saves_archive(&self) -> bool {
+        self.keep_archive || !self.extract
+    }
+
+    /// Returns the path the `.crate` file of a package is saved to, if it is,
+    /// which must not exist yet.
+    ///
+    /// It is named after the destination name, if one is set.
+    pub(crate) fn archive_dest(&self, name: &str, version: &str) -> Result<Option<PathBuf>, Error> {
+        if !self.saves_archive() {
+            return Ok(None);
+        }
+        let mut file = match &self.dest_name {
+            Some(dest) => PathBuf::from(dest),
+            None => self.package_dir(name, version),
+        }
+        .into_os_string();
+        file.push(".crate");
+        let dest = self.out_dir()?.join(file);
+        self.prepare_destination(&dest)?;
+        Ok(Some(dest))
+    }
+}
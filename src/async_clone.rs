@@ -0,0 +1,754 @@
+//! An async variant of the clone API, enabled with the `async` feature.
+
+use crate::download::{resume_status, DownloadRetry, Resume};
+use crate::error::{message_kind, Kind};
+use crate::http::{is_transient, is_transient_request, is_transient_status, retry_after};
+use crate::search::{closest_names, name_variants, CratesPage};
+use crate::sparse::{
+    check_index_status, index_file_url, json_found, sparse_config_url, sparse_pkg_info,
+};
+use crate::{
+    check_bitbucket_status, check_pkg_info_status, found_name, get_repo, not_found_error,
+    repo_to_detect, CloneError, CloneEvent, CloneMethodKind, ClonedPackage, Cloner, CrateResponse,
+    CrateStep, RepoMatch,
+};
+use anyhow::{Context, Error};
+use reqwest::header::RANGE;
+use serde_json::Value;
+use std::io::{self, Read, SeekFrom};
+use std::path::{Path, PathBuf};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+impl Cloner {
+    /// Clones a crate using the provided method, without blocking the async
+    /// runtime.
+    ///
+    /// This behaves the same as [`Cloner::clone`], except that requests,
+    /// including `.crate` downloads, use reqwest's async client. Work on
+    /// disk, such as extracting a `.crate` file, reading a local registry,
+    /// or rebuilding a package from docs.rs, and VCS commands are run on
+    /// tokio's blocking thread pool, so this must be called from within a
+    /// tokio runtime. A `.crate` file is extracted while it is downloaded,
+    /// and only moved into place once it matches its checksum.
+    ///
+    /// - `method_kind` - Method to fetch crate.
+    /// - `spec` - The name of the crate to clone
+    /// - `version` - The semantic version (semver) of the spec crate to clone
+    /// - `extra` - Additional arguments passed to clone command.
+    ///
+    pub async fn clone_async(
+        &self,
+        method_kind: CloneMethodKind,
+        spec: &str,
+        version: Option<&str>,
+        extra: &[&str],
+    ) -> Result<ClonedPackage, CloneError> {
+        Ok(self
+            .clone_async_inner(method_kind, spec, version, extra)
+            .await?)
+    }
+
+    async fn clone_async_inner(
+        &self,
+        method_kind: CloneMethodKind,
+        spec: &str,
+        version: Option<&str>,
+        extra: &[&str],
+    ) -> Result<ClonedPackage, Error> {
+        let (name, version_req) = self.parse_request(spec, version)?;
+        self.emit_resolving(name, version_req.as_deref());
+        let client = self.async_http_client()?;
+        let pkg_info = self.async_get_pkg_info(&client, name).await?;
+        let name = found_name(name, &pkg_info);
+        let methods = self.method_chain(method_kind, extra);
+        self.async_clone_methods(
+            &client,
+            name,
+            &methods,
+            version_req.as_deref(),
+            extra,
+            &pkg_info,
+        )
+        .await
+    }
+
+    /// Looks up package info like `Cloner::get_pkg_info`.
+    async fn async_get_pkg_info(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+    ) -> Result<CrateResponse, Error> {
+        match self.async_fetch_pkg_info(client, name).await {
+            Err(e) if message_kind(&e) == Some(Kind::NotFound) => {
+                let variant = self.async_find_name_variant(client, name).await;
+                match self.use_variant(name, variant) {
+                    Ok(pkg_info) => Ok(pkg_info),
+                    Err(variant) => {
+                        let results = self.async_search_similar(client, name).await;
+                        Err(not_found_error(e, closest_names(name, variant, results)))
+                    }
+                }
+            }
+            result => result,
+        }
+    }
+
+    async fn async_fetch_pkg_info(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+    ) -> Result<CrateResponse, Error> {
+        if let Some(dir) = &self.local_registry {
+            let this = Clone::clone(self);
+            let dir = dir.clone();
+            let name = name.to_string();
+            return blocking(move || this.get_local_pkg_info(&dir, &name)).await;
+        }
+        if let Some(index_url) = self.sparse_index_url() {
+            return self.async_sparse_pkg_info(client, index_url, name).await;
+        }
+        // Looking up the web API of a git index clones the index.
+        let this = Clone::clone(self);
+        blocking(move || this.resolve_git_index()).await?;
+        let url = self.pkg_info_url(name);
+        let response = self
+            .async_registry_get(client, &url)
+            .await
+            .context("Failed to fetch package info from crates.io.")?;
+        check_pkg_info_status(name, response.status())?;
+        response.json().await.context("Failed to convert to json.")
+    }
+
+    /// Grab package info from a sparse index, like
+    /// `Cloner::get_sparse_pkg_info`.
+    async fn async_sparse_pkg_info(
+        &self,
+        client: &reqwest::Client,
+        index_url: &str,
+        name: &str,
+    ) -> Result<CrateResponse, Error> {
+        let dl = self.async_sparse_dl(client, index_url).await?;
+        let url = index_file_url(index_url, name);
+        let response = self
+            .async_registry_get(client, &url)
+            .await
+            .context("Failed to fetch package info from the index.")?;
+        check_index_status(name, index_url, &url, response.status())?;
+        let text = response
+            .text()
+            .await
+            .context("Failed to read index entry.")?;
+        sparse_pkg_info(name, &text, &url, dl)
+    }
+
+    /// Returns the download URL template of a sparse index, which is shared
+    /// with `Cloner::sparse_dl`.
+    async fn async_sparse_dl(
+        &self,
+        client: &reqwest::Client,
+        index_url: &str,
+    ) -> Result<&str, Error> {
+        if let Some(dl) = self.sparse_dl.get() {
+            return Ok(dl);
+        }
+        let config_url = sparse_config_url(index_url);
+        let config = self
+            .async_fetch_json(client, &config_url)
+            .await
+            .context(format!("Failed to fetch registry config `{}`.", config_url))?;
+        self.set_sparse_config(&config_url, config)
+    }
+
+    /// Fetches a JSON document, returning `None` if it doesn't exist.
+    async fn async_fetch_json(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<Option<Value>, Error> {
+        let response = self.async_registry_get(client, url).await?;
+        if !json_found(url, response.status())? {
+            return Ok(None);
+        }
+        Ok(Some(
+            response
+                .json()
+                .await
+                .context("Failed to convert to json.")?,
+        ))
+    }
+
+    /// Looks up `name` with `-` and `_` swapped, like
+    /// `Cloner::find_name_variant`.
+    async fn async_find_name_variant(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+    ) -> Option<CrateResponse> {
+        for variant in name_variants(name) {
+            match self.async_fetch_pkg_info(client, &variant).await {
+                Ok(pkg_info) => return Some(pkg_info),
+                Err(e) => debug!("Variant `{}` not found: {:#}", variant, e),
+            }
+        }
+        None
+    }
+
+    /// Searches for names similar to a package that was not found, ignoring
+    /// errors, like `Cloner::similar_names`.
+    async fn async_search_similar(&self, client: &reqwest::Client, name: &str) -> Vec<String> {
+        let Some(url) = self.suggestion_search_url(name) else {
+            return Vec::new();
+        };
+        match self.async_crates_page(client, &url).await {
+            Ok(names) => names,
+            Err(e) => {
+                debug!("Failed to search for `{}`: {:#}", name, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Fetches one page of `/api/v1/crates`, returning the package names.
+    async fn async_crates_page(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<Vec<String>, Error> {
+        let response = self.async_registry_get(client, url).await?;
+        let code = response.status();
+        if !code.is_success() {
+            fail!(Http, "Failed to get `{}` from crates.io: `{}`", url, code);
+        }
+        let page: CratesPage = response
+            .json()
+            .await
+            .context("Failed to convert to json.")?;
+        Ok(page.names())
+    }
+
+    /// Clones a package with the first of `methods` that works, like
+    /// `Cloner::clone_methods`.
+    async fn async_clone_methods(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+        methods: &[CloneMethodKind],
+        version_req: Option<&str>,
+        extra: &[&str],
+        pkg_info: &CrateResponse,
+    ) -> Result<ClonedPackage, Error> {
+        let mut tried: Vec<String> = Vec::new();
+        let mut error = None;
+        for (i, method_kind) in methods.iter().enumerate() {
+            let mut method_name = method_kind.command().to_string();
+            let selected = self
+                .async_select_method(client, method_kind.clone(), name, version_req, pkg_info)
+                .await;
+            let result = match selected {
+                Ok(selected) => {
+                    method_name = selected.0.command().to_string();
+                    // Auto may have selected a method that already failed.
+                    if tried.contains(&method_name) {
+                        continue;
+                    }
+                    tried.push(method_name.clone());
+                    self.async_clone_with(client, name, selected, version_req, extra, pkg_info)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            let e = match result {
+                Ok(package) => return Ok(package),
+                Err(e) => e,
+            };
+            error = Some(self.fall_back(name, &methods[i + 1..], &tried, &method_name, e)?);
+        }
+        // UNWRAP: A method is only skipped after another one failed.
+        Err(error.unwrap())
+    }
+
+    /// Determines the method to use, like `Cloner::select_method`.
+    async fn async_select_method(
+        &self,
+        client: &reqwest::Client,
+        method_kind: CloneMethodKind,
+        name: &str,
+        version_req: Option<&str>,
+        pkg_info: &CrateResponse,
+    ) -> Result<(CloneMethodKind, String), Error> {
+        let method_kind = self.requested_method(method_kind)?;
+        let repo = get_repo(pkg_info);
+        let detected = match repo_to_detect(&method_kind, version_req, repo.as_deref()) {
+            Some(repo) => Some(self.async_detect_repo(client, repo).await),
+            None => None,
+        };
+        self.selected_method(method_kind, name, repo, detected)
+    }
+
+    /// Determines the method and URL to clone a repository with, like
+    /// `Cloner::detect_repo_inner`.
+    async fn async_detect_repo(
+        &self,
+        client: &reqwest::Client,
+        repo: &str,
+    ) -> Result<(CloneMethodKind, String), Error> {
+        match self.match_repo(repo)? {
+            RepoMatch::Found(method, url) => Ok((method, url)),
+            RepoMatch::GitHub(owner, name) => {
+                let moved = self.async_github_redirect(client, owner, name).await;
+                Ok(self.github_repo(owner, name, moved))
+            }
+            RepoMatch::Bitbucket(user, name) => {
+                let api_url = self.bitbucket_api_url(user, name);
+                let repo_info = self
+                    .async_http_get(client, &api_url)
+                    .await
+                    .context("Failed to fetch repo info from bitbucket.")?;
+                check_bitbucket_status(&api_url, repo_info.status())?;
+                let repo_info: Value = repo_info
+                    .json()
+                    .await
+                    .context("Failed to convert to bitbucket json.")?;
+                self.bitbucket_clone(&repo_info)
+            }
+        }
+    }
+
+    /// Returns the new `owner/name` of a GitHub repository that moved, like
+    /// `Cloner::github_redirect`.
+    async fn async_github_redirect(
+        &self,
+        client: &reqwest::Client,
+        owner: &str,
+        name: &str,
+    ) -> Option<String> {
+        let url = self.github_redirect_url(owner, name)?;
+        // Not retried, a repository that can't be reached is cloned as is.
+        let response = match client.head(&url).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                debug!("HEAD {} -> {}", url, response.status());
+                return None;
+            }
+            Err(e) => {
+                debug!("Failed to check `{}` for a redirect: {:#}", url, e);
+                return None;
+            }
+        };
+        self.github_moved(owner, name, response.url().as_str())
+    }
+
+    /// Clones a package with the method and repository `selected` by
+    /// `Cloner::async_select_method`.
+    async fn async_clone_with(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+        selected: (CloneMethodKind, String),
+        version_req: Option<&str>,
+        extra: &[&str],
+        pkg_info: &CrateResponse,
+    ) -> Result<ClonedPackage, Error> {
+        let (method, repo) = selected;
+        self.emit_method(name, &method, &repo);
+        let package = match method {
+            CloneMethodKind::Crate => {
+                let version_req = self.crate_request(name, version_req, extra)?;
+                self.async_clone_crate(client, name, version_req, pkg_info)
+                    .await?
+            }
+            _ => {
+                let this = Clone::clone(self);
+                let name = name.to_string();
+                let version_req = version_req.map(str::to_string);
+                let extra: Vec<String> = extra.iter().map(|s| s.to_string()).collect();
+                let pkg_info = pkg_info.clone();
+                blocking(move || {
+                    let extra: Vec<&str> = extra.iter().map(String::as_str).collect();
+                    this.clone_vcs(
+                        &name,
+                        &method,
+                        &repo,
+                        version_req.as_deref(),
+                        &extra,
+                        &pkg_info,
+                    )
+                })
+                .await?
+            }
+        };
+        let this = Clone::clone(self);
+        let pkg_info = pkg_info.clone();
+        blocking(move || this.finish_clone(package, Some(&pkg_info))).await
+    }
+
+    /// Downloads a crate, like `Cloner::clone_crate`.
+    async fn async_clone_crate(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+        version_req: Option<String>,
+        pkg_info: &CrateResponse,
+    ) -> Result<ClonedPackage, Error> {
+        let step = {
+            let this = Clone::clone(self);
+            let name = name.to_string();
+            let pkg_info = pkg_info.clone();
+            blocking(move || this.prepare_crate(&name, version_req.as_deref(), &pkg_info)).await?
+        };
+        let pending = match step {
+            CrateStep::Done(package) => return Ok(package),
+            CrateStep::Download(pending) => pending,
+        };
+        info!("Downloading `{}`", pending.url());
+        // Extract the `.crate` file while it is downloaded, instead of after.
+        let (mut stream, extraction) = if self.extract && !pending.url().starts_with("file://") {
+            let (tx, rx) = mpsc::channel(16);
+            let this = Clone::clone(self);
+            let name = name.to_string();
+            let download = pending.download.clone();
+            let extraction = tokio::task::spawn_blocking(move || {
+                this.extract_crate(&name, download, ChannelReader::new(rx))
+            });
+            (ExtractStream::new(tx), Some(extraction))
+        } else {
+            (ExtractStream::default(), None)
+        };
+        let result = self
+            .async_download_crate(
+                client,
+                name,
+                pending.base(),
+                pending.url(),
+                pending.checksum(),
+                &mut stream,
+            )
+            .await;
+        let streamed = result.is_ok() && stream.finish().await;
+        let extracted = match extraction {
+            // The extraction also has to finish if the stream stopped, to
+            // remove its temporary directory.
+            Some(extraction) => {
+                let extracted = extraction.await.context("Blocking task failed.")?;
+                streamed.then_some(extracted)
+            }
+            None => None,
+        };
+        let this = Clone::clone(self);
+        let name = name.to_string();
+        blocking(move || match result {
+            Ok(part) => this.finish_crate(&name, pending, &part, extracted),
+            Err(e) => this.crate_download_failed(&name, pending, e),
+        })
+        .await
+    }
+
+    /// Downloads a `.crate` file into a partial file in the output
+    /// directory, resuming and verifying it like `Cloner::download_crate`.
+    async fn async_download_crate(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+        base: &str,
+        url: &str,
+        checksum: Option<&str>,
+        stream: &mut ExtractStream,
+    ) -> Result<PathBuf, Error> {
+        let this = Clone::clone(self);
+        let (name, base, url) = (name.to_string(), base.to_string(), url.to_string());
+        let checksum = checksum.map(str::to_string);
+        if url.starts_with("file://") {
+            // A package of a local registry is only copied.
+            return blocking(move || this.download_crate(&name, &base, &url, checksum.as_deref()))
+                .await;
+        }
+        let part = blocking(move || this.part_path(&base)).await?;
+        let mut resumed = self
+            .async_download_part(client, &name, &url, &part, stream)
+            .await?;
+        while !self
+            .async_check_download(&url, &part, checksum.as_deref(), resumed)
+            .await?
+        {
+            resumed = self
+                .async_download_part(client, &name, &url, &part, stream)
+                .await?;
+        }
+        Ok(part)
+    }
+
+    /// Checks a finished download against its checksum on the blocking
+    /// thread pool, see `Cloner::check_download`.
+    async fn async_check_download(
+        &self,
+        url: &str,
+        part: &Path,
+        checksum: Option<&str>,
+        resumed: bool,
+    ) -> Result<bool, Error> {
+        let this = Clone::clone(self);
+        let (url, part) = (url.to_string(), part.to_path_buf());
+        let checksum = checksum.map(str::to_string);
+        blocking(move || this.check_download(&url, &part, checksum.as_deref(), resumed)).await
+    }
+
+    /// Downloads `url` into the partial file `part`, retrying and falling
+    /// back to mirrors, and returns whether the download was resumed.
+    ///
+    /// The bytes are also sent to `stream`, as long as they continue it.
+    async fn async_download_part(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+        url: &str,
+        part: &Path,
+        stream: &mut ExtractStream,
+    ) -> Result<bool, Error> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(part)
+            .await
+            .with_context(|| format!("Failed to create `{}`.", part.display()))?;
+        let mut retry = DownloadRetry::new(self, url);
+        loop {
+            let attempt =
+                self.async_download_attempt(client, name, retry.url(), part, &mut file, stream);
+            match attempt.await {
+                Ok(resumed) => return Ok(resumed),
+                Err(e) => tokio::time::sleep(retry.failed(self, e)?).await,
+            }
+        }
+    }
+
+    /// Downloads `url` into `file`, returning whether the download resumed
+    /// the bytes already in it.
+    async fn async_download_attempt(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+        url: &str,
+        part: &Path,
+        file: &mut File,
+        stream: &mut ExtractStream,
+    ) -> Result<bool, Error> {
+        let (mut response, mut received) = loop {
+            let offset = file.seek(SeekFrom::End(0)).await?;
+            let mut request = client.get(url);
+            if let Some(token) = self.registry_token_for(url) {
+                request = request.header(reqwest::header::AUTHORIZATION, token);
+            }
+            if offset > 0 {
+                debug!("Resuming `{}` at byte {}", part.display(), offset);
+                request = request.header(RANGE, format!("bytes={}-", offset));
+            }
+            debug!("GET {url}");
+            let response = request.send().await?;
+            debug!("GET {url} -> {}", response.status());
+            match resume_status(url, response.status(), response.headers(), offset)? {
+                Resume::Append => break (response, offset),
+                Resume::Restart => {
+                    debug!("Cannot resume `{}`, downloading it again", part.display());
+                    file.set_len(0).await?;
+                }
+                Resume::Replace => {
+                    let response = response.error_for_status()?;
+                    // The server sent the whole file.
+                    file.set_len(0).await?;
+                    break (response, 0);
+                }
+            }
+        };
+        let resumed = received > 0;
+        let total = response.content_length().map(|len| received + len);
+        loop {
+            self.check_cancelled()?;
+            let Some(chunk) = response.chunk().await? else {
+                break;
+            };
+            file.write_all(&chunk)
+                .await
+                .with_context(|| format!("Failed to write `{}`.", part.display()))?;
+            stream.send(received, &chunk).await;
+            received += chunk.len() as u64;
+            self.emit(CloneEvent::Downloading {
+                name: name.to_string(),
+                received,
+                total,
+            });
+        }
+        file.flush()
+            .await
+            .with_context(|| format!("Failed to write `{}`.", part.display()))?;
+        Ok(resumed)
+    }
+}
+
+/// A message to the extraction of a `.crate` file that is being downloaded.
+enum Chunk {
+    Data(Vec<u8>),
+    /// The download is complete and matches its checksum.
+    End,
+}
+
+/// Sends the bytes of a `.crate` file to its extraction while it is
+/// downloaded.
+///
+/// The stream stops if the download starts over or leaves a gap, such as
+/// when a partial file from an earlier run is resumed. The file is then
+/// extracted once downloaded instead.
+#[derive(Default)]
+struct ExtractStream {
+    tx: Option<mpsc::Sender<Chunk>>,
+    /// How many bytes were sent.
+    sent: u64,
+}
+
+impl ExtractStream {
+    fn new(tx: mpsc::Sender<Chunk>) -> ExtractStream {
+        ExtractStream {
+            tx: Some(tx),
+            sent: 0,
+        }
+    }
+
+    /// Sends the `data` at byte `offset` of the file, stopping the stream if
+    /// it doesn't continue it.
+    async fn send(&mut self, offset: u64, data: &[u8]) {
+        let Some(tx) = &self.tx else {
+            return;
+        };
+        // A send error means the extraction has already failed.
+        if offset != self.sent || tx.send(Chunk::Data(data.to_vec())).await.is_err() {
+            debug!("Extracting the `.crate` file once it is downloaded");
+            self.tx = None;
+            return;
+        }
+        self.sent += data.len() as u64;
+    }
+
+    /// Ends the stream of a verified download, returning whether the whole
+    /// file was sent.
+    async fn finish(&mut self) -> bool {
+        match self.tx.take() {
+            Some(tx) => tx.send(Chunk::End).await.is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// A reader of the bytes sent by an [`ExtractStream`].
+///
+/// Reading fails if the stream stops before it ends, so that nothing is
+/// extracted from a download that is incomplete or doesn't match its
+/// checksum.
+struct ChannelReader {
+    rx: mpsc::Receiver<Chunk>,
+    chunk: Vec<u8>,
+    pos: usize,
+    ended: bool,
+}
+
+impl ChannelReader {
+    fn new(rx: mpsc::Receiver<Chunk>) -> ChannelReader {
+        ChannelReader {
+            rx,
+            chunk: Vec::new(),
+            pos: 0,
+            ended: false,
+        }
+    }
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.chunk.len() {
+            if self.ended {
+                return Ok(0);
+            }
+            match self.rx.blocking_recv() {
+                Some(Chunk::Data(chunk)) => self.chunk = chunk,
+                Some(Chunk::End) => self.ended = true,
+                None => return Err(io::Error::other("The download stopped.")),
+            }
+            self.pos = 0;
+        }
+        let n = buf.len().min(self.chunk.len() - self.pos);
+        buf[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl Cloner {
+    fn async_http_client(&self) -> Result<reqwest::Client, Error> {
+        if let Some(client) = &self.async_http_client {
+            return Ok(client.clone());
+        }
+        Ok(client_builder!(self, reqwest::Client).build()?)
+    }
+
+    /// Sends a GET request to the registry, retrying after transient
+    /// failures and then trying each mirror, like `Cloner::registry_get`.
+    async fn async_registry_get(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<reqwest::Response, Error> {
+        let urls = self.mirror_urls(url);
+        let (last, urls) = urls.split_last().unwrap();
+        for url in urls {
+            match self.async_http_get(client, url).await {
+                Ok(response) if response.status().is_server_error() => {
+                    self.log_mirror_fallback(url, response.status())
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if is_transient(&e) => self.log_mirror_fallback(url, e),
+                Err(e) => return Err(e),
+            }
+        }
+        self.async_http_get(client, last).await
+    }
+    async fn async_http_get(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<reqwest::Response, Error> {
+        let mut attempt = 1;
+        loop {
+            debug!("GET {url}");
+            let mut request = client.get(url);
+            if let Some(token) = self.registry_token_for(url) {
+                request = request.header(reqwest::header::AUTHORIZATION, token);
+            }
+            let retry = attempt <= self.retries;
+            let (reason, retry_after) = match request.send().await {
+                Ok(response) if retry && is_transient_status(response.status()) => (
+                    response.status().to_string(),
+                    retry_after(response.headers()),
+                ),
+                Ok(response) => {
+                    debug!("GET {url} -> {}", response.status());
+                    return Ok(response);
+                }
+                Err(e) if retry && is_transient_request(&e) => (e.to_string(), None),
+                Err(e) => return Err(e.into()),
+            };
+            tokio::time::sleep(self.log_retry(url, attempt, reason, retry_after)).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Runs blocking work on tokio's blocking thread pool.
+async fn blocking<T, F>(f: F) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .context("Blocking task failed.")?
+}
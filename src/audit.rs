@@ -0,0 +1,251 @@
+//! Cloning of a package's dependency tree for license review.
+
+use crate::api::DependenciesResponse;
+use crate::{
+    parse_spec, select_version, CloneError, Cloner, CrateResponse, CrateVersion, Dependency,
+};
+use anyhow::{Context, Error};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name prefixes (uppercased) that are considered license or notice files.
+const NOTICE_PREFIXES: &[&str] = &[
+    "LICENSE",
+    "LICENCE",
+    "COPYING",
+    "COPYRIGHT",
+    "NOTICE",
+    "UNLICENSE",
+];
+
+/// A package that was downloaded as part of the tree.
+struct AuditedPackage {
+    license: Option<String>,
+    dir: PathBuf,
+    notice_files: Vec<PathBuf>,
+}
+
+impl Cloner {
+    /// Clones a package and all of its dependencies, and writes an aggregated
+    /// third-party notice report.
+    ///
+    /// Every package in the tree is downloaded as a `.crate` file into the
+    /// output directory. Normal and build dependencies are followed, using
+    /// the newest version matching each requirement. Optional dependencies
+    /// and dev-dependencies are not included.
+    ///
+    /// The report at `report` lists each package with the license expression
+    /// from the registry, followed by the contents of any license or notice
+    /// files found at the root of the package.
+    ///
+    /// - `spec` - The name of the crate to audit
+    /// - `version` - The semantic version (semver) of the spec crate to audit
+    /// - `report` - Path of the report file to write
+    ///
+    pub fn audit_tree(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+        report: &Path,
+    ) -> Result<(), CloneError> {
+        Ok(self.audit_tree_inner(spec, version, report)?)
+    }
+
+    fn audit_tree_inner(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+        report: &Path,
+    ) -> Result<(), Error> {
+        if self.dest_name.is_some() {
+            fail!(
+                InvalidInput,
+                "A destination name cannot be used when auditing a dependency tree."
+            );
+        }
+        let (name, version_req) = parse_spec(spec, version)?;
+        let mut pkg_infos: HashMap<String, CrateResponse> = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut packages = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back((name.to_string(), version_req));
+        let mut root = None;
+
+        while let Some((name, version_req)) = queue.pop_front() {
+            if !pkg_infos.contains_key(&name) {
+                let pkg_info = self.get_pkg_info(&name)?;
+                pkg_infos.insert(name.clone(), pkg_info);
+            }
+            let pkg_info = &pkg_infos[&name];
+            let crate_version = select_version(
+                pkg_info,
+                version_req.as_deref(),
+                self.include_prereleases,
+                self.include_yanked,
+            )
+            .with_context(|| format!("Failed to resolve `{}` {:?}.", name, version_req))?;
+            let num = crate_version.num.clone();
+            if !seen.insert((name.clone(), num.clone())) {
+                continue;
+            }
+            if root.is_none() {
+                root = Some(format!("{} {}", name, num));
+            }
+            let downloaded = self.clone_crate(&name, Some(&format!("={}", num)), pkg_info)?;
+            self.export(&downloaded)?;
+            let dir = downloaded.path;
+            // The sparse index has no license, read it from the package.
+            let license = match &crate_version.license {
+                Some(license) => Some(license.clone()),
+                None => manifest_license(&dir),
+            };
+            let notice_files = if self.dry_run {
+                Vec::new()
+            } else {
+                find_notice_files(&dir)?
+            };
+            packages.insert(
+                (name.clone(), num.clone()),
+                AuditedPackage {
+                    license,
+                    dir,
+                    notice_files,
+                },
+            );
+
+            let deps = match &crate_version.deps {
+                // Sparse index entries include the dependencies.
+                Some(deps) => deps.clone(),
+                None => self.get_dependencies(&name, crate_version)?,
+            };
+            for dep in deps {
+                if dep.kind.as_deref() == Some("dev") || dep.optional {
+                    continue;
+                }
+                queue.push_back((dep.package_name().to_string(), Some(dep.req)));
+            }
+        }
+
+        if self.dry_run {
+            info!(
+                "Would write notices for {} packages to `{}`",
+                packages.len(),
+                report.display()
+            );
+            return Ok(());
+        }
+        let report_text = render_report(root.as_deref().unwrap_or(name), &packages)?;
+        fs::write(report, report_text)
+            .with_context(|| format!("Failed to write report `{}`.", report.display()))?;
+        info!(
+            "Wrote notices for {} packages to `{}`",
+            packages.len(),
+            report.display()
+        );
+        Ok(())
+    }
+
+    /// Fetches the dependencies of a specific version, of all kinds.
+    fn get_dependencies(
+        &self,
+        name: &str,
+        crate_version: &CrateVersion,
+    ) -> Result<Vec<Dependency>, Error> {
+        let url = match &crate_version.links.dependencies {
+            Some(path) => format!("{}{}", self.registry_url(), path),
+            None => format!(
+                "{}/api/v1/crates/{}/{}/dependencies",
+                self.registry_url(),
+                name,
+                crate_version.num
+            ),
+        };
+        let response = self
+            .registry_get(&url)
+            .with_context(|| format!("Failed to fetch dependencies of `{}`.", name))?;
+        let code = response.status();
+        if !code.is_success() {
+            fail!(
+                Http,
+                "Failed to get dependencies of `{} {}` from crates.io: `{}`",
+                name,
+                crate_version.num,
+                code
+            );
+        }
+        let deps: DependenciesResponse = response.json().context("Failed to convert to json.")?;
+        Ok(deps.dependencies)
+    }
+}
+
+/// Reads the license expression from a package's `Cargo.toml`.
+fn manifest_license(dir: &Path) -> Option<String> {
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    let manifest: toml::Table = manifest.parse().ok()?;
+    Some(
+        manifest
+            .get("package")?
+            .get("license")?
+            .as_str()?
+            .to_string(),
+    )
+}
+
+/// Returns the license and notice files at the root of a package.
+fn find_notice_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    for entry in
+        fs::read_dir(dir).with_context(|| format!("Failed to read `{}`.", dir.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_uppercase();
+        if entry.file_type()?.is_file() && NOTICE_PREFIXES.iter().any(|p| name.starts_with(p)) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Renders the third-party notice report.
+fn render_report(
+    root: &str,
+    packages: &BTreeMap<(String, String), AuditedPackage>,
+) -> Result<String, Error> {
+    let mut out = String::new();
+    let title = format!("Third-party notices for {}", root);
+    writeln!(out, "{}\n{}\n", title, "=".repeat(title.len()))?;
+    writeln!(out, "Packages:\n")?;
+    for ((name, version), pkg) in packages {
+        writeln!(
+            out,
+            "- {} {}: {}",
+            name,
+            version,
+            pkg.license.as_deref().unwrap_or("(no license expression)")
+        )?;
+    }
+    for ((name, version), pkg) in packages {
+        let heading = format!("{} {}", name, version);
+        writeln!(out, "\n{}\n{}\n", heading, "-".repeat(heading.len()))?;
+        writeln!(
+            out,
+            "License: {}",
+            pkg.license.as_deref().unwrap_or("(no license expression)")
+        )?;
+        if pkg.notice_files.is_empty() {
+            writeln!(out, "No license files found.")?;
+        }
+        for path in &pkg.notice_files {
+            let rel = path.strip_prefix(&pkg.dir).unwrap_or(path);
+            let text =
+                fs::read(path).with_context(|| format!("Failed to read `{}`.", path.display()))?;
+            writeln!(out, "\n==> {} <==\n", rel.display())?;
+            out.push_str(String::from_utf8_lossy(&text).trim_end());
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,82 @@
+//! Cloning several packages at once.
+
+use crate::error::{msg, Kind};
+use crate::{CloneError, CloneRequest, ClonedPackage, Cloner};
+use anyhow::{Context, Error};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+impl Cloner {
+    /// Sets how many packages [`Cloner::clone_many`] clones at the same time.
+    ///
+    /// Defaults to 1, cloning one package after the other.
+    pub fn set_jobs(&mut self, value: usize) -> &mut Self {
+        self.jobs = value.max(1);
+        self
+    }
+
+    /// Clones several packages, using up to [`Cloner::set_jobs`] threads.
+    ///
+    /// A failure does not stop the remaining packages from being cloned.
+    /// Returns the result of each request, in the same order as the requests.
+    pub fn clone_many(&self, requests: &[CloneRequest]) -> Vec<Result<ClonedPackage, CloneError>> {
+        if self.jobs <= 1 || requests.len() <= 1 {
+            return requests.iter().map(|r| self.execute(r)).collect();
+        }
+        let next = AtomicUsize::new(0);
+        let results: Vec<_> = requests.iter().map(|_| Mutex::new(None)).collect();
+        thread::scope(|s| {
+            for _ in 0..self.jobs.min(requests.len()) {
+                s.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(request) = requests.get(i) else {
+                        break;
+                    };
+                    *results[i].lock().unwrap() = Some(self.execute(request));
+                });
+            }
+        });
+        results
+            .into_iter()
+            .map(|result| result.into_inner().unwrap().unwrap())
+            .collect()
+    }
+}
+
+/// Reads a list of packages to clone from a file, to pass to
+/// [`Cloner::clone_many`].
+///
+/// The file has one package spec per line, such as `serde` or `serde@1.0.200`.
+/// Empty lines and lines starting with `#` are skipped. A file starting with
+/// `[` is read as a JSON array of specs instead.
+pub fn read_request_list(path: &Path) -> Result<Vec<CloneRequest>, CloneError> {
+    Ok(parse_request_list(path)?)
+}
+
+fn parse_request_list(path: &Path) -> Result<Vec<CloneRequest>, Error> {
+    let contents = fs::read_to_string(path).with_context(|| {
+        msg(
+            Kind::InvalidInput,
+            format!("Failed to read `{}`.", path.display()),
+        )
+    })?;
+    let specs: Vec<String> = if contents.trim_start().starts_with('[') {
+        serde_json::from_str(&contents).with_context(|| {
+            msg(
+                Kind::InvalidInput,
+                format!("Failed to parse `{}` as a JSON list.", path.display()),
+            )
+        })?
+    } else {
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    };
+    Ok(specs.into_iter().map(CloneRequest::new).collect())
+}
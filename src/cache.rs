@@ -0,0 +1,125 @@
+//! A cache of downloaded `.crate` files, shared between runs, and reuse of
+//! the files cargo has already downloaded.
+
+use crate::registry::cargo_home;
+use crate::{Cloner, CrateDownload};
+use anyhow::{Context, Error};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+impl Cloner {
+    /// Sets a directory where downloaded `.crate` files are kept.
+    ///
+    /// Files are named `<name>-<version>.crate`, the same as in cargo's
+    /// `registry/cache` directory, so that directory can be used as well. A
+    /// cached file is only used if its SHA-256 checksum matches the one
+    /// reported by the registry, and downloads without a checksum are not
+    /// cached.
+    pub fn set_cache_dir(&mut self, value: impl Into<PathBuf>) -> &mut Self {
+        self.cache_dir = Some(value.into());
+        self
+    }
+
+    /// Sets whether `.crate` files already downloaded by cargo, in
+    /// `$CARGO_HOME/registry/cache`, are extracted instead of being
+    /// downloaded again. This is enabled by default.
+    ///
+    /// As with [`Cloner::set_cache_dir`], a file is only used if its
+    /// checksum matches the one reported by the registry.
+    pub fn set_cargo_cache(&mut self, value: bool) -> &mut Self {
+        self.cargo_cache = value;
+        self
+    }
+
+    /// Returns the path of a cached `.crate` file for a download, if there
+    /// is one with the expected checksum.
+    ///
+    /// The cache directory is checked first, and then cargo's cache.
+    pub(crate) fn cached_crate(&self, download: &CrateDownload) -> Option<PathBuf> {
+        let checksum = download.checksum.as_deref()?;
+        if let Some((path, _)) = self.cache_path(download) {
+            match file_checksum(&path) {
+                Ok(actual) if actual == checksum => return Some(path),
+                Ok(_) => warn!(
+                    "Ignoring cached `{}`, its checksum does not match the registry.",
+                    path.display()
+                ),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => warn!("Failed to read cached `{}`: {}", path.display(), e),
+            }
+        }
+        if !self.cargo_cache {
+            return None;
+        }
+        // Each registry has its own directory, and a file from another
+        // registry with the same name simply has a different checksum.
+        let file_name = crate_file_name(download);
+        let mut paths: Vec<PathBuf> = fs::read_dir(cargo_home()?.join("registry/cache"))
+            .ok()?
+            .filter_map(|entry| Some(entry.ok()?.path().join(&file_name)))
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .find(|path| file_checksum(path).is_ok_and(|actual| actual == checksum))
+    }
+
+    /// Copies a downloaded `.crate` file into the cache.
+    ///
+    /// Failures are only logged, since the download itself succeeded.
+    pub(crate) fn cache_crate(&self, download: &CrateDownload, file: &Path) {
+        let Some((path, checksum)) = self.cache_path(download) else {
+            return;
+        };
+        match store(file, &path, checksum) {
+            Ok(true) => debug!("Cached `{}`", path.display()),
+            Ok(false) => warn!(
+                "Not caching `{}`, its checksum does not match the registry.",
+                download.url
+            ),
+            Err(e) => warn!("Failed to cache `{}`: {:#}", path.display(), e),
+        }
+    }
+
+    fn cache_path<'a>(&self, download: &'a CrateDownload) -> Option<(PathBuf, &'a str)> {
+        let dir = self.cache_dir.as_ref()?;
+        let checksum = download.checksum.as_deref()?;
+        Some((dir.join(crate_file_name(download)), checksum))
+    }
+}
+
+/// Returns the name of a `.crate` file, as cargo names it, such as
+/// `Inflector-0.11.4.crate`.
+fn crate_file_name(download: &CrateDownload) -> String {
+    format!("{}-{}.crate", download.name, download.version)
+}
+
+/// Copies `file` to `path` if it has the given checksum, returning whether
+/// it did.
+///
+/// The file is written to a temporary file first, so that a concurrent run
+/// never sees a partial file.
+fn store(file: &Path, path: &Path, checksum: &str) -> Result<bool, Error> {
+    if file_checksum(file)? != checksum {
+        return Ok(false);
+    }
+    let dir = path.parent().unwrap();
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create `{}`.", dir.display()))?;
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    io::copy(&mut File::open(file)?, &mut tmp)?;
+    tmp.persist(path)?;
+    Ok(true)
+}
+
+/// Returns the SHA-256 checksum of a file, in hex.
+pub(crate) fn file_checksum(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
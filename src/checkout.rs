@@ -0,0 +1,108 @@
+//! Selecting a branch, tag, or revision for VCS clones.
+
+use crate::{CloneMethodKind, Cloner};
+use anyhow::Error;
+
+/// What to check out after cloning a repository.
+#[derive(Debug, Clone)]
+pub(crate) enum Checkout {
+    Branch(String),
+    Tag(String),
+    Rev(String),
+}
+
+impl Checkout {
+    /// The command line flag of the checkout, for error messages.
+    pub(crate) fn flag(&self) -> &str {
+        match self {
+            Checkout::Branch(_) => "--branch",
+            Checkout::Tag(_) => "--tag",
+            Checkout::Rev(_) => "--rev",
+        }
+    }
+
+    /// The branch, tag, or revision.
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            Checkout::Branch(name) | Checkout::Tag(name) | Checkout::Rev(name) => name,
+        }
+    }
+}
+
+impl Cloner {
+    /// Sets the branch to check out with a VCS method.
+    ///
+    /// For `pijul`, this is the channel. This replaces a tag or revision set
+    /// before.
+    pub fn set_branch(&mut self, value: impl Into<String>) -> &mut Self {
+        self.checkout = Some(Checkout::Branch(value.into()));
+        self
+    }
+
+    /// Sets the tag to check out with a VCS method.
+    ///
+    /// Tags are not supported by `pijul`. This replaces a branch or revision
+    /// set before.
+    pub fn set_tag(&mut self, value: impl Into<String>) -> &mut Self {
+        self.checkout = Some(Checkout::Tag(value.into()));
+        self
+    }
+
+    /// Sets the revision to check out with a VCS method, such as a git commit
+    /// hash.
+    ///
+    /// For `pijul`, this is a state. This replaces a branch or tag set
+    /// before.
+    pub fn set_rev(&mut self, value: impl Into<String>) -> &mut Self {
+        self.checkout = Some(Checkout::Rev(value.into()));
+        self
+    }
+
+    /// Returns the options of the clone command that select the branch, tag,
+    /// or revision.
+    ///
+    /// A git revision is checked out after cloning instead, and fossil
+    /// checks out with `fossil open`, so these have no options.
+    pub(crate) fn checkout_options(&self, method: &CloneMethodKind) -> Result<Vec<String>, Error> {
+        let Some(checkout) = &self.checkout else {
+            return Ok(Vec::new());
+        };
+        let flag = match (method, checkout) {
+            (CloneMethodKind::Git, Checkout::Branch(_) | Checkout::Tag(_)) => "--branch",
+            (CloneMethodKind::Git, Checkout::Rev(_)) | (CloneMethodKind::Fossil, _) => {
+                return Ok(Vec::new())
+            }
+            (CloneMethodKind::Mercurial, Checkout::Branch(_)) => "--branch",
+            (CloneMethodKind::Mercurial, Checkout::Tag(_) | Checkout::Rev(_)) => "--updaterev",
+            (CloneMethodKind::Pijul, Checkout::Branch(_)) => "--channel",
+            (CloneMethodKind::Pijul, Checkout::Rev(_)) => "--state",
+            (CloneMethodKind::Subversion, Checkout::Rev(_)) => "--revision",
+            (CloneMethodKind::Darcs, Checkout::Tag(_)) => "--tag",
+            (CloneMethodKind::Darcs, Checkout::Rev(_)) => "--to-hash",
+            (CloneMethodKind::Bazaar, Checkout::Tag(name)) => {
+                return Ok(vec!["--revision".to_string(), format!("tag:{}", name)])
+            }
+            (CloneMethodKind::Bazaar, Checkout::Rev(_)) => "--revision",
+            _ => fail!(
+                InvalidInput,
+                "{} is not supported with the `{}` method.",
+                checkout.flag(),
+                method.command()
+            ),
+        };
+        Ok(vec![flag.to_string(), checkout.name().to_string()])
+    }
+
+    /// Returns an error if a branch, tag, or revision is set, since they only
+    /// work with VCS methods.
+    pub(crate) fn check_crate_checkout(&self) -> Result<(), Error> {
+        if let Some(checkout) = &self.checkout {
+            fail!(
+                InvalidInput,
+                "{} only works with VCS methods, not `crate`.",
+                checkout.flag()
+            );
+        }
+        Ok(())
+    }
+}
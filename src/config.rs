@@ -0,0 +1,379 @@
+//! Configuration settings given as TOML, in configuration files or with
+//! `--config`.
+//!
+//! Settings are named by dotted keys, such as `registry.url`. A table in the
+//! TOML is flattened into its dotted keys, so `registry.url = "…"` and
+//! `[registry] url = "…"` are equivalent.
+
+use crate::registry::{load_configs, read_table};
+use crate::CloneError;
+use crate::{CloneMethodKind, Cloner, ExtractPolicy, Layout, DEFAULT_REGISTRY_URL};
+use anyhow::{Context, Error};
+use std::env;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+impl Cloner {
+    /// Applies a single `KEY=VALUE` setting, in the same form as cargo's
+    /// `--config` flag.
+    ///
+    /// The value is parsed as TOML, so strings must be quoted, for example
+    /// `registry.url="https://example.com"`. The supported keys are:
+    ///
+    /// - `registry.url` - See [`Cloner::set_registry_url`].
+    /// - `registry.token` - See [`Cloner::set_token`].
+    /// - `registry.mirrors` - An array of URLs, see
+    ///   [`Cloner::add_registry_mirror`].
+    /// - `hosts.github` - See [`Cloner::set_github_url`].
+    /// - `hosts.gitlab` - See [`Cloner::set_gitlab_url`].
+    /// - `hosts.bitbucket` - See [`Cloner::set_bitbucket_url`].
+    /// - `hosts.gitea` - An array of hosts, see [`Cloner::add_gitea_host`].
+    /// - `hosts.git` - An array of hosts, see [`Cloner::add_git_host`].
+    /// - `hosts.docs-rs` - See [`Cloner::set_docs_rs_url`].
+    /// - `out-dir` - See [`Cloner::set_out_dir`].
+    /// - `git-store` - See [`Cloner::set_git_store`].
+    /// - `http.proxy` - See [`Cloner::set_proxy`].
+    /// - `http.no-proxy` - See [`Cloner::set_no_proxy`].
+    /// - `http.cainfo` - See [`Cloner::add_root_certificate`]. Only with the
+    ///   `native-tls` or `rustls` feature.
+    /// - `method` - A method name as accepted by [`CloneMethodKind::from`],
+    ///   see [`Cloner::set_default_method`].
+    /// - `shallow` - A boolean, see [`Cloner::set_shallow`].
+    /// - `ssh` - A boolean, see [`Cloner::set_prefer_ssh`].
+    /// - `extract.max-size` - A number of bytes, see
+    ///   [`Cloner::set_max_extract_size`].
+    /// - `extract.max-file-size` - A number of bytes, see
+    ///   [`Cloner::set_max_file_size`].
+    /// - `extract.max-entries` - See [`Cloner::set_max_entries`].
+    /// - `extract.links` - A policy name as accepted by
+    ///   [`ExtractPolicy::from`], see [`Cloner::set_link_policy`].
+    /// - `extract.paths` - A policy name, see [`Cloner::set_path_policy`].
+    /// - `open` - A command to open cloned packages with, see
+    ///   [`Cloner::set_open_command`].
+    /// - `layout` - A layout name as accepted by [`Layout::from`], see
+    ///   [`Cloner::set_layout`].
+    ///
+    /// For the `extract` limits, `0` removes the limit.
+    ///
+    /// In `out-dir`, `git-store`, and `http.cainfo`, a leading `~/` is
+    /// replaced with the home directory.
+    pub fn apply_config_override(&mut self, arg: &str) -> Result<&mut Self, CloneError> {
+        let table: Table = arg
+            .parse()
+            .with_context(|| format!("Invalid --config value `{}`, expected KEY=VALUE.", arg))?;
+        Ok(self
+            .apply_config(&table)
+            .with_context(|| format!("Invalid --config value `{}`.", arg))?)
+    }
+
+    /// Creates a `Cloner` with the settings from the configuration files and
+    /// the environment.
+    ///
+    /// Settings are resolved in this order, with later ones taking
+    /// precedence:
+    ///
+    /// 1. The defaults of [`Cloner::new`].
+    /// 2. The configuration files, see [`Cloner::load_config_files`].
+    /// 3. The replacement of crates.io from cargo's source replacement, see
+    ///    [`Cloner::set_registry_name`], unless the files set `registry.url`.
+    /// 4. The `CARGO_CLONE_*` environment variables:
+    ///
+    ///    | Variable | Config key |
+    ///    |----------|------------|
+    ///    | `CARGO_CLONE_REGISTRY_URL` | `registry.url` |
+    ///    | `CARGO_CLONE_REGISTRY_TOKEN` | `registry.token` |
+    ///    | `CARGO_CLONE_REGISTRY_MIRRORS` | `registry.mirrors`, comma-separated |
+    ///    | `CARGO_CLONE_GITHUB_URL` | `hosts.github` |
+    ///    | `CARGO_CLONE_GITLAB_URL` | `hosts.gitlab` |
+    ///    | `CARGO_CLONE_BITBUCKET_URL` | `hosts.bitbucket` |
+    ///    | `CARGO_CLONE_GITEA_HOSTS` | `hosts.gitea`, comma-separated |
+    ///    | `CARGO_CLONE_GIT_HOSTS` | `hosts.git`, comma-separated |
+    ///    | `CARGO_CLONE_DOCS_RS_URL` | `hosts.docs-rs` |
+    ///    | `CARGO_CLONE_OUT_DIR` | `out-dir` |
+    ///    | `CARGO_CLONE_GIT_STORE` | `git-store` |
+    ///    | `CARGO_CLONE_HTTP_PROXY` | `http.proxy` |
+    ///    | `CARGO_CLONE_NO_PROXY` | `http.no-proxy` |
+    ///    | `CARGO_CLONE_METHOD` | `method` |
+    ///    | `CARGO_CLONE_SHALLOW` | `shallow`, `true` or `false` |
+    ///    | `CARGO_CLONE_SSH` | `ssh`, `true` or `false` |
+    ///
+    ///    Empty variables are ignored.
+    ///
+    /// Settings made on the returned `Cloner` take precedence over all of
+    /// these.
+    pub fn from_env() -> Result<Cloner, CloneError> {
+        let mut cloner = Cloner::new();
+        cloner.load_config_files()?;
+        if cloner.registry_url() == DEFAULT_REGISTRY_URL {
+            cloner
+                .replace_crates_io()
+                .context("Failed to read cargo's source replacement.")?;
+        }
+        cloner
+            .apply_config(&env_table()?)
+            .context("Invalid `CARGO_CLONE_*` environment variable.")?;
+        Ok(cloner)
+    }
+
+    /// Loads the settings from the configuration files.
+    ///
+    /// These are `cargo-clone.toml` in the user's configuration directory
+    /// (`$XDG_CONFIG_HOME`, or else `~/.config`), and then the `[clone]`
+    /// table of cargo's `.cargo/config.toml` files, from `$CARGO_HOME` to the
+    /// current directory, so that the closest setting wins. They take the same
+    /// keys as [`Cloner::apply_config_override`], for example:
+    ///
+    /// ```toml
+    /// method = "crate"
+    /// out-dir = "~/src"
+    ///
+    /// [hosts]
+    /// git = ["git.example.com"]
+    /// ```
+    ///
+    /// Settings made afterwards take precedence.
+    pub fn load_config_files(&mut self) -> Result<&mut Self, CloneError> {
+        if let Some(path) = user_config_path() {
+            if let Some(table) = read_table(&path)? {
+                self.apply_config(&table)
+                    .with_context(|| format!("Invalid config `{}`.", path.display()))?;
+            }
+        }
+        let cwd = env::current_dir().context("Failed to get the current directory.")?;
+        for (_, config) in load_configs(&cwd)?.iter().rev() {
+            if let Some(Value::Table(table)) = config.get("clone") {
+                self.apply_config(table)
+                    .context("Invalid `[clone]` table in the cargo config.")?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Applies every setting in a configuration table.
+    pub(crate) fn apply_config(&mut self, table: &Table) -> Result<&mut Self, Error> {
+        let mut settings = Vec::new();
+        flatten("", table, &mut settings);
+        for (key, value) in settings {
+            match key.as_str() {
+                "registry.url" => self.set_registry_url(config_str(&key, value)?),
+                "registry.token" => self.set_token(config_str(&key, value)?),
+                "registry.mirrors" => {
+                    for url in config_str_array(&key, value)? {
+                        self.add_registry_mirror(url);
+                    }
+                    &mut *self
+                }
+                "hosts.github" => self.set_github_url(config_str(&key, value)?),
+                "hosts.gitlab" => self.set_gitlab_url(config_str(&key, value)?),
+                "hosts.bitbucket" => self.set_bitbucket_url(config_str(&key, value)?),
+                "hosts.gitea" => {
+                    for host in config_str_array(&key, value)? {
+                        self.add_gitea_host(host);
+                    }
+                    &mut *self
+                }
+                "hosts.git" => {
+                    for host in config_str_array(&key, value)? {
+                        self.add_git_host(host);
+                    }
+                    &mut *self
+                }
+                "hosts.docs-rs" => self.set_docs_rs_url(config_str(&key, value)?),
+                "out-dir" => self.set_out_dir(config_path(&key, value)?),
+                "git-store" => self.set_git_store(config_path(&key, value)?),
+                "http.proxy" => self.set_proxy(config_str(&key, value)?),
+                "http.no-proxy" => self.set_no_proxy(config_str(&key, value)?),
+                #[cfg(any(feature = "native-tls", feature = "rustls"))]
+                "http.cainfo" => self.add_root_certificate(config_path(&key, value)?)?,
+                "method" => {
+                    let name = config_str(&key, value)?;
+                    let Some(method) = CloneMethodKind::from(name) else {
+                        fail!(InvalidInput, "Unknown method `{}` for `{}`.", name, key);
+                    };
+                    self.set_default_method(method)
+                }
+                "shallow" => self.set_shallow(config_bool(&key, value)?),
+                "ssh" => self.set_prefer_ssh(config_bool(&key, value)?),
+                "extract.max-size" => self.set_max_extract_size(config_limit(&key, value)?),
+                "extract.max-file-size" => self.set_max_file_size(config_limit(&key, value)?),
+                "extract.max-entries" => self.set_max_entries(config_limit(&key, value)?),
+                "extract.links" => self.set_link_policy(config_policy(&key, value)?),
+                "extract.paths" => self.set_path_policy(config_policy(&key, value)?),
+                "open" => self.set_open_command(config_str(&key, value)?),
+                "layout" => {
+                    let name = config_str(&key, value)?;
+                    let Some(layout) = Layout::from(name) else {
+                        fail!(
+                            InvalidInput,
+                            "Unknown layout `{}` for `{}`, expected `flat`, `versioned`, \
+                             or `registry`.",
+                            name,
+                            key
+                        );
+                    };
+                    self.set_layout(layout)
+                }
+                _ => fail!(InvalidInput, "Unknown config key `{}`.", key),
+            };
+        }
+        Ok(self)
+    }
+}
+
+/// The environment variables read by [`Cloner::from_env`], and their keys.
+const ENV_VARS: &[(&str, &str)] = &[
+    ("CARGO_CLONE_REGISTRY_URL", "registry.url"),
+    ("CARGO_CLONE_REGISTRY_TOKEN", "registry.token"),
+    ("CARGO_CLONE_REGISTRY_MIRRORS", "registry.mirrors"),
+    ("CARGO_CLONE_GITHUB_URL", "hosts.github"),
+    ("CARGO_CLONE_GITLAB_URL", "hosts.gitlab"),
+    ("CARGO_CLONE_BITBUCKET_URL", "hosts.bitbucket"),
+    ("CARGO_CLONE_GITEA_HOSTS", "hosts.gitea"),
+    ("CARGO_CLONE_GIT_HOSTS", "hosts.git"),
+    ("CARGO_CLONE_DOCS_RS_URL", "hosts.docs-rs"),
+    ("CARGO_CLONE_OUT_DIR", "out-dir"),
+    ("CARGO_CLONE_GIT_STORE", "git-store"),
+    ("CARGO_CLONE_HTTP_PROXY", "http.proxy"),
+    ("CARGO_CLONE_NO_PROXY", "http.no-proxy"),
+    ("CARGO_CLONE_METHOD", "method"),
+    ("CARGO_CLONE_SHALLOW", "shallow"),
+    ("CARGO_CLONE_SSH", "ssh"),
+];
+
+/// Builds a table of dotted keys from the `CARGO_CLONE_*` environment
+/// variables.
+fn env_table() -> Result<Table, Error> {
+    let mut table = Table::new();
+    for (var, key) in ENV_VARS {
+        let value = match env::var(var) {
+            Ok(value) if !value.is_empty() => value,
+            Ok(_) | Err(env::VarError::NotPresent) => continue,
+            Err(env::VarError::NotUnicode(_)) => {
+                fail!(InvalidInput, "`{}` is not valid UTF-8.", var)
+            }
+        };
+        let value = match *key {
+            "registry.mirrors" | "hosts.gitea" | "hosts.git" => Value::Array(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| Value::String(item.to_string()))
+                    .collect(),
+            ),
+            "shallow" | "ssh" => match value.as_str() {
+                "true" | "1" => Value::Boolean(true),
+                "false" | "0" => Value::Boolean(false),
+                _ => fail!(
+                    InvalidInput,
+                    "`{}` expected `true` or `false`, got `{}`.",
+                    var,
+                    value
+                ),
+            },
+            _ => Value::String(value),
+        };
+        table.insert(key.to_string(), value);
+    }
+    Ok(table)
+}
+
+/// Collects the dotted keys and values of a table.
+fn flatten<'a>(prefix: &str, table: &'a Table, out: &mut Vec<(String, &'a Value)>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Table(t) => flatten(&key, t, out),
+            _ => out.push((key, value)),
+        }
+    }
+}
+
+fn config_str<'a>(key: &str, value: &'a Value) -> Result<&'a str, Error> {
+    match value.as_str() {
+        Some(s) => Ok(s),
+        None => fail!(
+            InvalidInput,
+            "Config key `{}` expected a string, got `{}`.",
+            key,
+            value
+        ),
+    }
+}
+
+fn config_bool(key: &str, value: &Value) -> Result<bool, Error> {
+    match value.as_bool() {
+        Some(b) => Ok(b),
+        None => fail!(
+            InvalidInput,
+            "Config key `{}` expected a boolean, got `{}`.",
+            key,
+            value
+        ),
+    }
+}
+
+/// Reads a limit, where `0` is no limit.
+fn config_limit(key: &str, value: &Value) -> Result<Option<u64>, Error> {
+    match value.as_integer().map(u64::try_from) {
+        Some(Ok(0)) => Ok(None),
+        Some(Ok(limit)) => Ok(Some(limit)),
+        _ => fail!(
+            InvalidInput,
+            "Config key `{}` expected a non-negative integer, got `{}`.",
+            key,
+            value
+        ),
+    }
+}
+
+/// Reads an extraction policy value.
+fn config_policy(key: &str, value: &Value) -> Result<ExtractPolicy, Error> {
+    let name = config_str(key, value)?;
+    match ExtractPolicy::from(name) {
+        Some(policy) => Ok(policy),
+        None => fail!(
+            InvalidInput,
+            "Unknown policy `{}` for `{}`, expected `skip`, `error`, or `sanitize`.",
+            name,
+            key
+        ),
+    }
+}
+
+/// Reads a path, replacing a leading `~/` with the home directory.
+fn config_path(key: &str, value: &Value) -> Result<PathBuf, Error> {
+    let path = config_str(key, value)?;
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = env::var_os("HOME") {
+            return Ok(Path::new(&home).join(rest));
+        }
+    }
+    Ok(PathBuf::from(path))
+}
+
+/// Returns the path of `cargo-clone.toml` in the user's configuration
+/// directory.
+fn user_config_path() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => Path::new(&env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("cargo-clone.toml"))
+}
+
+fn config_str_array<'a>(key: &str, value: &'a Value) -> Result<Vec<&'a str>, Error> {
+    match value.as_array() {
+        Some(values) => values.iter().map(|value| config_str(key, value)).collect(),
+        None => fail!(
+            InvalidInput,
+            "Config key `{}` expected an array of strings, got `{}`.",
+            key,
+            value
+        ),
+    }
+}
@@ -0,0 +1,237 @@
+//! Comparing a published package with its repository, or with another
+//! version.
+
+use crate::error::{msg, Kind};
+use crate::{check_semver_req, get_repo, parse_spec, CloneError, CloneMethodKind, Cloner};
+use anyhow::{Context, Error};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files that `cargo package` generates, which are not in the repository.
+const GENERATED_FILES: &[&str] = &[".cargo_vcs_info.json", "Cargo.toml"];
+
+/// The result of [`Cloner::diff_source`].
+#[derive(Debug, Clone)]
+pub struct SourceDiff {
+    /// The version of the package that was compared.
+    pub version: String,
+    /// The commit of the repository that was compared.
+    pub commit: String,
+    /// Files of the package whose contents differ from the repository.
+    pub modified: Vec<PathBuf>,
+    /// Files of the package that are not in the repository.
+    pub only_in_crate: Vec<PathBuf>,
+}
+
+impl SourceDiff {
+    /// Returns whether the package matches the repository.
+    pub fn is_empty(&self) -> bool {
+        self.modified.is_empty() && self.only_in_crate.is_empty()
+    }
+}
+
+impl Cloner {
+    /// Compares the published `.crate` file of a package with its git
+    /// repository at the commit it was published from.
+    ///
+    /// The commit is read from the `.cargo_vcs_info.json` of the package, or
+    /// else a tag matching the version is used. Both are downloaded into a
+    /// temporary directory, which is removed afterwards. The `Cargo.toml`
+    /// rewritten by `cargo package` is skipped, and `Cargo.toml.orig` is
+    /// compared with the repository's `Cargo.toml` instead.
+    ///
+    /// - `spec` - The name of the crate, optionally with a version
+    /// - `version` - The semantic version (semver) of the crate
+    ///
+    pub fn diff_source(&self, spec: &str, version: Option<&str>) -> Result<SourceDiff, CloneError> {
+        Ok(self.diff_source_inner(spec, version)?)
+    }
+
+    fn diff_source_inner(&self, spec: &str, version: Option<&str>) -> Result<SourceDiff, Error> {
+        let (name, version_req) = parse_spec(spec, version)?;
+        let pkg_info = self.get_pkg_info(name)?;
+        let download = self.select_download(&pkg_info, version_req.as_deref())?;
+        let version_req = format!("={}", download.version);
+        let Some(repo) = get_repo(&pkg_info) else {
+            fail!(Repository, "Could not find repository path in crates.io.");
+        };
+        let url = match self.detect_repo_inner(&repo) {
+            Ok((CloneMethodKind::Git, url)) => url,
+            _ => fail!(
+                Repository,
+                "Only git repositories can be compared, `{}` is not one.",
+                repo
+            ),
+        };
+
+        let tmp = tempfile::tempdir().context("Failed to create temporary directory.")?;
+        let cloner = self.scratch_cloner(tmp.path());
+        let downloaded = cloner.clone_crate(name, Some(&version_req), &pkg_info)?;
+        let (_, Some(commit)) = cloner.version_commit(name, &url, &version_req, &pkg_info)? else {
+            fail!(
+                Version,
+                "Could not find the commit `{} {}` was published from.",
+                name,
+                download.version
+            );
+        };
+        cloner.clone_git(&url, &[], &["repo"], Some(&commit))?;
+        let mut repo_dir = tmp.path().join("repo");
+        if let Some(path) = path_in_vcs(&downloaded.path) {
+            repo_dir.push(path);
+        }
+
+        let mut diff = SourceDiff {
+            version: download.version,
+            commit,
+            modified: Vec::new(),
+            only_in_crate: Vec::new(),
+        };
+        for path in list_files(&downloaded.path)? {
+            let Some(name) = path.to_str() else { continue };
+            if GENERATED_FILES.contains(&name) {
+                continue;
+            }
+            let repo_path = match name {
+                "Cargo.toml.orig" => repo_dir.join("Cargo.toml"),
+                // A workspace member uses the workspace's lock file.
+                "Cargo.lock" if !repo_dir.join(name).exists() => tmp.path().join("repo").join(name),
+                _ => repo_dir.join(&path),
+            };
+            let Ok(repo_contents) = fs::read(&repo_path) else {
+                diff.only_in_crate.push(path);
+                continue;
+            };
+            let crate_path = downloaded.path.join(&path);
+            let contents = fs::read(&crate_path)
+                .with_context(|| format!("Failed to read `{}`.", crate_path.display()))?;
+            if contents != repo_contents {
+                diff.modified.push(path);
+            }
+        }
+        Ok(diff)
+    }
+
+    /// Compares two versions of a package, returning the output of
+    /// `git diff`.
+    ///
+    /// Both `.crate` files are downloaded into a temporary directory, which
+    /// is removed afterwards, and compared with `git diff --no-index`. With
+    /// `stat`, only a summary of the changed files is returned, as with
+    /// `git diff --stat`. The result is empty if the versions are the same.
+    ///
+    /// - `name` - The name of the crate
+    /// - `old` - The version (semver) to compare from
+    /// - `new` - The version (semver) to compare to
+    ///
+    pub fn diff_versions(
+        &self,
+        name: &str,
+        old: &str,
+        new: &str,
+        stat: bool,
+    ) -> Result<String, CloneError> {
+        Ok(self.diff_versions_inner(name, old, new, stat)?)
+    }
+
+    fn diff_versions_inner(
+        &self,
+        name: &str,
+        old: &str,
+        new: &str,
+        stat: bool,
+    ) -> Result<String, Error> {
+        let pkg_info = self.get_pkg_info(name)?;
+        let old = self.select_download(&pkg_info, Some(&check_semver_req(old)?))?;
+        let new = self.select_download(&pkg_info, Some(&check_semver_req(new)?))?;
+        if old.version == new.version {
+            fail!(
+                InvalidInput,
+                "Both versions are `{} {}`, there is nothing to compare.",
+                name,
+                old.version
+            );
+        }
+
+        let tmp = tempfile::tempdir().context("Failed to create temporary directory.")?;
+        let cloner = self.scratch_cloner(tmp.path());
+        let mut dirs = Vec::new();
+        for download in [&old, &new] {
+            let version_req = format!("={}", download.version);
+            let downloaded = cloner.clone_crate(name, Some(&version_req), &pkg_info)?;
+            dirs.push(downloaded.path.file_name().unwrap().to_owned());
+        }
+        let output = Command::new("git")
+            .args(["diff", "--no-index", "--no-color", "--no-ext-diff"])
+            .args(stat.then_some("--stat"))
+            .arg("--")
+            .args(&dirs)
+            .current_dir(tmp.path())
+            .output()
+            .context(msg(Kind::Vcs, "Failed to run `git`."))?;
+        // `git diff` exits with 1 if there are differences.
+        if !matches!(output.status.code(), Some(0 | 1)) {
+            fail!(
+                Vcs,
+                "`git diff` did not finish successfully: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Returns a copy of the cloner for downloading into a temporary
+    /// directory, without the options that affect the destination.
+    pub(crate) fn scratch_cloner(&self, out_dir: &Path) -> Cloner {
+        let mut cloner = Clone::clone(self);
+        cloner.out_dir = Some(out_dir.to_path_buf());
+        cloner.dest_name = None;
+        cloner.strip_prefix = false;
+        cloner.extract_filter = None;
+        cloner.vendor = false;
+        cloner.reproducible = false;
+        cloner.metadata_file = false;
+        cloner.keep_archive = false;
+        cloner.extract = true;
+        cloner.force = false;
+        cloner.dry_run = false;
+        cloner.export = None;
+        cloner.event_handler = None;
+        cloner.git_store = None;
+        cloner.checkout = None;
+        cloner
+    }
+}
+
+/// Reads the directory of the package within its repository from its
+/// `.cargo_vcs_info.json`.
+fn path_in_vcs(dir: &Path) -> Option<String> {
+    let vcs_info = fs::read_to_string(dir.join(".cargo_vcs_info.json")).ok()?;
+    let vcs_info: Value = serde_json::from_str(&vcs_info).ok()?;
+    let path = vcs_info["path_in_vcs"].as_str()?;
+    (!path.is_empty()).then(|| path.to_string())
+}
+
+/// Lists the files in a directory recursively, relative to it, sorted.
+pub(crate) fn list_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    let mut dirs = vec![PathBuf::new()];
+    while let Some(rel) = dirs.pop() {
+        let full = dir.join(&rel);
+        for entry in
+            fs::read_dir(&full).with_context(|| format!("Failed to read `{}`.", full.display()))?
+        {
+            let entry = entry?;
+            let path = rel.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                dirs.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,199 @@
+//! Rebuilding a package from the source view of docs.rs, for versions whose
+//! `.crate` file can't be downloaded.
+
+use crate::error::{msg, Kind};
+use crate::extract_limits::ExtractCount;
+use crate::{percent_decode, CloneEvent, ClonedPackage, Cloner, CrateDownload};
+use anyhow::{Context, Error};
+use regex::{Captures, Regex};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+impl Cloner {
+    /// Sets whether to rebuild a package from the source view of docs.rs if
+    /// its `.crate` file can't be downloaded, such as an old version that was
+    /// removed from the registry's storage.
+    ///
+    /// Defaults to `false`. docs.rs does not show binary files or very large
+    /// files, so those are missing from the package. The fallback is not used
+    /// with [`Cloner::set_vendor`] or when keeping the `.crate` file.
+    pub fn set_docs_rs_fallback(&mut self, value: bool) -> &mut Self {
+        self.docs_rs_fallback = value;
+        self
+    }
+
+    /// Sets the URL of docs.rs, for [`Cloner::set_docs_rs_fallback`].
+    pub fn set_docs_rs_url(&mut self, value: impl Into<String>) -> &mut Self {
+        self.docs_rs_url = value.into();
+        self
+    }
+
+    /// Whether a failed `.crate` download falls back to docs.rs.
+    pub(crate) fn uses_docs_rs(&self) -> bool {
+        self.docs_rs_fallback && !self.vendor && !self.saves_archive()
+    }
+
+    /// Downloads each file of a package from its source view on docs.rs,
+    /// into the destination of its `.crate` file.
+    pub(crate) fn clone_docs_rs(
+        &self,
+        name: &str,
+        download: CrateDownload,
+    ) -> Result<ClonedPackage, Error> {
+        let source = format!(
+            "{}/crate/{}/{}/source/",
+            self.docs_rs_url.trim_end_matches('/'),
+            name,
+            download.version
+        );
+        warn!(
+            "Rebuilding `{} {}` from `{}`, binary and large files may be missing.",
+            name, download.version, source
+        );
+        let dest = self.crate_dest(name, &download.version)?;
+        let out_dir = self.out_dir()?;
+        let base = format!("{}-{}", name.to_lowercase(), download.version);
+        // Download into a temporary sibling directory, as when extracting.
+        let tmp = tempfile::Builder::new()
+            .prefix(&format!(".{}.", base))
+            .tempdir_in(&out_dir)
+            .context(format!(
+                "Failed to create temporary directory in `{}`",
+                out_dir.display()
+            ))?;
+        let top = tmp.path().join(&base);
+        fs::create_dir_all(&top)?;
+        let mut count = ExtractCount::default();
+        let mut dirs = vec![PathBuf::new()];
+        while let Some(dir) = dirs.pop() {
+            let listing = self.docs_rs_page(&source, &dir, true)?;
+            for (file_name, is_dir) in listing_entries(&listing)? {
+                let path = dir.join(file_name);
+                if is_dir {
+                    dirs.push(path);
+                    continue;
+                }
+                if let Some(filter) = &self.extract_filter {
+                    if !filter.is_match(&path) {
+                        continue;
+                    }
+                }
+                let page = self.docs_rs_page(&source, &path, false)?;
+                let Some(contents) = source_code(&page) else {
+                    warn!(
+                        "Skipping `{}`, docs.rs does not show its source.",
+                        path.display()
+                    );
+                    continue;
+                };
+                let entry_path = Path::new(&base).join(&path);
+                self.extract_limits.add_entry(&mut count)?;
+                self.extract_limits
+                    .add_file(&mut count, &entry_path, contents.len() as u64)?;
+                info!("{}", entry_path.display());
+                self.emit(CloneEvent::Extracting {
+                    name: name.to_string(),
+                    path: entry_path,
+                });
+                let file = top.join(&path);
+                fs::create_dir_all(file.parent().unwrap())?;
+                fs::write(&file, contents)
+                    .with_context(|| format!("Failed to write `{}`.", file.display()))?;
+            }
+        }
+        self.move_extracted(name, &top, &dest)?;
+        Ok(ClonedPackage {
+            source,
+            checksum: None,
+            ..download.into_package(name, dest)
+        })
+    }
+
+    /// Gets the page of a directory or file in the source view of docs.rs.
+    fn docs_rs_page(&self, source: &str, path: &Path, is_dir: bool) -> Result<String, Error> {
+        self.check_cancelled()?;
+        let mut url = source.to_string();
+        for c in path.components() {
+            url.push_str(&c.as_os_str().to_string_lossy());
+            url.push('/');
+        }
+        if !is_dir {
+            url.pop();
+        }
+        let response = self.reqwest_get(&url)?;
+        let code = response.status();
+        if !code.is_success() {
+            fail!(Http, "Failed to get `{}` from docs.rs: `{}`", url, code);
+        }
+        response
+            .text()
+            .context(msg(Kind::Http, format!("Failed to read `{}`.", url)))
+    }
+}
+
+/// Returns the entries of a directory page in the source view of docs.rs,
+/// and whether each is a directory.
+fn listing_entries(page: &str) -> Result<Vec<(String, bool)>, Error> {
+    let mut entries = Vec::new();
+    for c in Regex::new(r#"href="\./([^"/]+)(/?)""#)
+        .unwrap()
+        .captures_iter(page)
+    {
+        let file_name = percent_decode(&unescape_html(&c[1]))?;
+        // Only names within the directory, so that a page can't write
+        // elsewhere.
+        let mut components = Path::new(&file_name).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        ) {
+            fail!(
+                Extract,
+                "Unexpected file name `{}` in the docs.rs source view.",
+                file_name
+            );
+        }
+        entries.push((file_name, !c[2].is_empty()));
+    }
+    Ok(entries)
+}
+
+/// Returns the text of the source code on a file page of docs.rs, or `None`
+/// if the page doesn't show it.
+fn source_code(page: &str) -> Option<String> {
+    let start = page.find(r#"id="source-code""#).unwrap_or(0);
+    let page = &page[start..];
+    let code = &page[page.find("<code")?..];
+    let code = &code[code.find('>')? + 1..];
+    let code = &code[..code.find("</code>")?];
+    // The code is highlighted with `<span>`s.
+    let text = Regex::new(r"<[^>]*>").unwrap().replace_all(code, "");
+    Some(unescape_html(&text))
+}
+
+/// Replaces the character references of HTML text.
+fn unescape_html(text: &str) -> String {
+    Regex::new(r"&(#[xX][0-9a-fA-F]+|#[0-9]+|[a-z]+);")
+        .unwrap()
+        .replace_all(text, |c: &Captures<'_>| {
+            let reference = &c[1];
+            let ch = match reference {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => match reference
+                    .strip_prefix("#x")
+                    .or(reference.strip_prefix("#X"))
+                {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => reference.strip_prefix('#').and_then(|d| d.parse().ok()),
+                }
+                .and_then(char::from_u32),
+            };
+            ch.map_or_else(|| c[0].to_string(), String::from)
+        })
+        .into_owned()
+}
@@ -0,0 +1,254 @@
+//! Downloading of `.crate` files, resuming after failures.
+
+use crate::cache::file_checksum;
+use crate::http::is_transient;
+use crate::{check_auth_status, send_request, CloneEvent, Cloner};
+use anyhow::{Context, Error};
+use reqwest::header::{HeaderMap, CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+impl Cloner {
+    /// Downloads a `.crate` file into a partial file in the output directory,
+    /// and returns its path.
+    ///
+    /// If the connection fails, the download is retried, resuming from where
+    /// it left off with a `Range` request. The partial file is also resumed
+    /// if it was left behind by an earlier, interrupted run. When the retries
+    /// run out, the download continues from the registry's mirrors.
+    ///
+    /// The finished file is checked against `checksum`, the SHA-256 the
+    /// registry lists. A resumed download that does not match is downloaded
+    /// again from the start, since the partial file may have been of a
+    /// different file.
+    ///
+    /// Progress is reported with [`CloneEvent::Downloading`] for the package
+    /// `name`.
+    pub(crate) fn download_crate(
+        &self,
+        name: &str,
+        base: &str,
+        url: &str,
+        checksum: Option<&str>,
+    ) -> Result<PathBuf, Error> {
+        let part = self.part_path(base)?;
+        // A package of a local registry is copied, since the caller removes
+        // the file.
+        if let Some(path) = url.strip_prefix("file://") {
+            fs::copy(path, &part).with_context(|| format!("Failed to copy `{}`.", path))?;
+            self.check_download(url, &part, checksum, false)?;
+            return Ok(part);
+        }
+        let mut resumed = self.download_part(name, url, &part)?;
+        while !self.check_download(url, &part, checksum, resumed)? {
+            resumed = self.download_part(name, url, &part)?;
+        }
+        Ok(part)
+    }
+
+    /// Returns the path of the partial file a `.crate` file named `base` is
+    /// downloaded into, creating the output directory.
+    pub(crate) fn part_path(&self, base: &str) -> Result<PathBuf, Error> {
+        let out_dir = self.out_dir()?;
+        fs::create_dir_all(&out_dir)
+            .with_context(|| format!("Failed to create `{}`.", out_dir.display()))?;
+        Ok(out_dir.join(format!(".{}.crate.part", base)))
+    }
+
+    /// Checks a finished download of `url` against its `checksum`, returning
+    /// `false` if it was `resumed` and has to be downloaded again from the
+    /// start.
+    pub(crate) fn check_download(
+        &self,
+        url: &str,
+        part: &Path,
+        checksum: Option<&str>,
+        resumed: bool,
+    ) -> Result<bool, Error> {
+        let Some(checksum) = checksum else {
+            return Ok(true);
+        };
+        if file_checksum(part)? == checksum {
+            return Ok(true);
+        }
+        fs::remove_file(part).with_context(|| format!("Failed to remove `{}`.", part.display()))?;
+        if resumed {
+            warn!(
+                "The resumed download of `{}` does not match its checksum, downloading it again.",
+                url
+            );
+            return Ok(false);
+        }
+        fail!(
+            Http,
+            "The download of `{}` does not match the checksum in the registry.",
+            url
+        );
+    }
+
+    /// Downloads `url` into the partial file `part`, retrying and falling
+    /// back to mirrors, and returns whether the download was resumed.
+    fn download_part(&self, name: &str, url: &str, part: &Path) -> Result<bool, Error> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(part)
+            .with_context(|| format!("Failed to create `{}`.", part.display()))?;
+        let mut retry = DownloadRetry::new(self, url);
+        let mut resumed = false;
+        loop {
+            match self.download_attempt(name, retry.url(), part, &mut file, &mut resumed) {
+                Ok(()) => return Ok(resumed),
+                Err(e) => thread::sleep(retry.failed(self, e)?),
+            }
+        }
+    }
+
+    fn download_attempt(
+        &self,
+        name: &str,
+        url: &str,
+        part: &Path,
+        file: &mut File,
+        resumed: &mut bool,
+    ) -> Result<(), Error> {
+        let offset = file.seek(SeekFrom::End(0))?;
+        let mut request = self.http_client()?.get(url);
+        if let Some(token) = self.registry_token_for(url) {
+            request = request.header(reqwest::header::AUTHORIZATION, token);
+        }
+        if offset > 0 {
+            debug!("Resuming `{}` at byte {}", part.display(), offset);
+            request = request.header(RANGE, format!("bytes={}-", offset));
+        }
+        let mut response = send_request(url, request)?;
+        let resume = resume_status(url, response.status(), response.headers(), offset)?;
+        let mut received = match resume {
+            Resume::Append => {
+                *resumed = true;
+                offset
+            }
+            Resume::Restart => {
+                debug!("Cannot resume `{}`, downloading it again", part.display());
+                file.set_len(0)?;
+                return self.download_attempt(name, url, part, file, resumed);
+            }
+            Resume::Replace => {
+                response = response.error_for_status()?;
+                // The server sent the whole file.
+                file.set_len(0)?;
+                0
+            }
+        };
+        let total = response.content_length().map(|len| received + len);
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            self.check_cancelled()?;
+            let n = response.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            file.write_all(&buf[..n])
+                .with_context(|| format!("Failed to write `{}`.", part.display()))?;
+            received += n as u64;
+            self.emit(CloneEvent::Downloading {
+                name: name.to_string(),
+                received,
+                total,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// How to continue a partial file that was `offset` bytes long, given the
+/// response to a download request.
+pub(crate) enum Resume {
+    /// Append the response to the partial file.
+    Append,
+    /// The whole file was sent, or an error, which replaces the partial file.
+    Replace,
+    /// The partial file doesn't match the server's file, start over.
+    Restart,
+}
+
+/// Checks the response to a download request that asked for the bytes after
+/// `offset`, if it is not 0.
+pub(crate) fn resume_status(
+    url: &str,
+    status: StatusCode,
+    headers: &HeaderMap,
+    offset: u64,
+) -> Result<Resume, Error> {
+    check_auth_status(status)?;
+    match status {
+        StatusCode::PARTIAL_CONTENT if resumes_at(headers, offset) => Ok(Resume::Append),
+        StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+            Ok(Resume::Restart)
+        }
+        StatusCode::PARTIAL_CONTENT => fail!(
+            Http,
+            "Got a partial response for `{}` without asking for one.",
+            url
+        ),
+        _ => Ok(Resume::Replace),
+    }
+}
+
+/// The URL a download is attempted from, which moves on to the registry's
+/// mirrors when the retries run out.
+pub(crate) struct DownloadRetry {
+    url: String,
+    mirrors: std::vec::IntoIter<String>,
+    attempt: u32,
+}
+
+impl DownloadRetry {
+    pub(crate) fn new(cloner: &Cloner, url: &str) -> DownloadRetry {
+        let mut mirrors = cloner.mirror_urls(url).into_iter();
+        mirrors.next();
+        DownloadRetry {
+            url: url.to_string(),
+            mirrors,
+            attempt: 1,
+        }
+    }
+
+    /// The URL of the next attempt.
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Handles a failed attempt, returning how long to wait before the next
+    /// one, or the error if there are no more attempts.
+    pub(crate) fn failed(&mut self, cloner: &Cloner, e: Error) -> Result<Duration, Error> {
+        if self.attempt <= cloner.retries && is_transient(&e) {
+            let delay = cloner.log_retry(&self.url, self.attempt, format!("{:#}", e), None);
+            self.attempt += 1;
+            return Ok(delay);
+        }
+        match self.mirrors.next() {
+            Some(mirror) if is_transient(&e) => {
+                cloner.log_mirror_fallback(&self.url, e);
+                self.url = mirror;
+                self.attempt = 1;
+                Ok(Duration::ZERO)
+            }
+            _ => Err(e.context(format!("Failed to download `{}`", self.url))),
+        }
+    }
+}
+
+/// Checks that a partial response starts where the download left off.
+fn resumes_at(headers: &HeaderMap, offset: u64) -> bool {
+    headers
+        .get(CONTENT_RANGE)
+        .and_then(|range| range.to_str().ok())
+        .and_then(|range| range.strip_prefix("bytes "))
+        .and_then(|range| range.split('-').next())
+        .is_some_and(|start| start.parse() == Ok(offset))
+}
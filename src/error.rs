@@ -0,0 +1,166 @@
+//! The error type of the public API.
+//!
+//! Internally errors are `anyhow::Error`s. Errors whose kind matters to a
+//! caller are created with [`fail!`] or tagged with a [`Message`] context,
+//! and are classified into a [`CloneError`] at the public API boundary.
+
+use std::error::Error as StdError;
+use std::fmt;
+
+/// Returns early with an error of the given [`Kind`].
+macro_rules! fail {
+    ($kind:ident, $($arg:tt)*) => {
+        return Err($crate::error::msg($crate::error::Kind::$kind, format!($($arg)*)).into())
+    };
+}
+
+/// The boxed error held by each [`CloneError`] variant.
+///
+/// Its `Display` is the error message, and its `source` chain holds the
+/// underlying causes.
+pub type ErrorDetail = Box<dyn StdError + Send + Sync + 'static>;
+
+/// An error from cloning a package.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CloneError {
+    /// An HTTP request failed, or the server responded with an error status.
+    Http(ErrorDetail),
+    /// The package was not found in the registry.
+    NotFound(ErrorDetail),
+    /// The version requirement was invalid, or no version matched it.
+    Version(ErrorDetail),
+    /// The repository of the package could not be determined.
+    Repository(ErrorDetail),
+    /// A VCS command could not be run, or failed.
+    Vcs(ErrorDetail),
+    /// The `.crate` file could not be extracted.
+    Extract(ErrorDetail),
+    /// The destination directory already exists.
+    DestinationExists(ErrorDetail),
+    /// The arguments or configuration were invalid.
+    InvalidInput(ErrorDetail),
+    /// The clone was cancelled with [`crate::Cloner::set_cancel_flag`].
+    Cancelled(ErrorDetail),
+    /// Any other error, such as a filesystem error.
+    Other(ErrorDetail),
+}
+
+impl CloneError {
+    /// Returns the underlying error.
+    pub fn detail(&self) -> &ErrorDetail {
+        match self {
+            CloneError::Http(e)
+            | CloneError::NotFound(e)
+            | CloneError::Version(e)
+            | CloneError::Repository(e)
+            | CloneError::Vcs(e)
+            | CloneError::Extract(e)
+            | CloneError::DestinationExists(e)
+            | CloneError::InvalidInput(e)
+            | CloneError::Cancelled(e)
+            | CloneError::Other(e) => e,
+        }
+    }
+}
+
+impl fmt::Display for CloneError {
+    /// With the alternate flag (`{:#}`), the causes are included, separated
+    /// by colons.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.detail())?;
+        if f.alternate() {
+            let mut source = self.source();
+            while let Some(e) = source {
+                write!(f, ": {}", e)?;
+                source = e.source();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StdError for CloneError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.detail().source()
+    }
+}
+
+impl From<anyhow::Error> for CloneError {
+    fn from(e: anyhow::Error) -> CloneError {
+        let kind = error_kind(&e);
+        let detail = e.into();
+        match kind {
+            Kind::Http => CloneError::Http(detail),
+            Kind::NotFound => CloneError::NotFound(detail),
+            Kind::Version => CloneError::Version(detail),
+            Kind::Repository => CloneError::Repository(detail),
+            Kind::Vcs => CloneError::Vcs(detail),
+            Kind::Extract => CloneError::Extract(detail),
+            Kind::DestinationExists => CloneError::DestinationExists(detail),
+            Kind::InvalidInput => CloneError::InvalidInput(detail),
+            Kind::Cancelled => CloneError::Cancelled(detail),
+            Kind::Other => CloneError::Other(detail),
+        }
+    }
+}
+
+/// The internal counterpart of the [`CloneError`] variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Http,
+    NotFound,
+    Version,
+    Repository,
+    Vcs,
+    Extract,
+    DestinationExists,
+    InvalidInput,
+    Cancelled,
+    Other,
+}
+
+/// An error message tagged with its kind.
+///
+/// This is used either as the error itself, or as context on another error.
+#[derive(Debug)]
+pub(crate) struct Message {
+    kind: Kind,
+    text: String,
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl StdError for Message {}
+
+/// Returns the kind of an error created with [`msg`], or with it as context.
+pub(crate) fn message_kind(e: &anyhow::Error) -> Option<Kind> {
+    e.downcast_ref::<Message>().map(|m| m.kind)
+}
+
+/// Returns the kind of [`CloneError`] an error is classified as.
+pub(crate) fn error_kind(e: &anyhow::Error) -> Kind {
+    if let Some(kind) = message_kind(e) {
+        kind
+    } else if e.chain().any(|e| e.is::<reqwest::Error>()) {
+        Kind::Http
+    } else if e.chain().any(|e| e.is::<semver::Error>()) {
+        Kind::Version
+    } else if e.chain().any(|e| e.is::<toml::de::Error>()) {
+        Kind::InvalidInput
+    } else {
+        Kind::Other
+    }
+}
+
+/// Creates a message of the given kind.
+pub(crate) fn msg(kind: Kind, text: impl Into<String>) -> Message {
+    Message {
+        kind,
+        text: text.into(),
+    }
+}
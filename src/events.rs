@@ -0,0 +1,138 @@
+//! Events reported while cloning, for tools that follow the progress.
+
+use crate::Cloner;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// A step of a clone, reported to the handler set with
+/// [`Cloner::set_event_handler`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum CloneEvent {
+    /// A package is being looked up in the registry, to select its version
+    /// and how to clone it.
+    Resolving {
+        /// The name of the package.
+        name: String,
+        /// The version requirement, if one was given.
+        requirement: Option<String>,
+    },
+    /// The method to clone a package with was chosen.
+    Method {
+        /// The name of the package.
+        name: String,
+        /// The method, as in [`CloneMethodKind::command`](crate::CloneMethodKind::command).
+        method: String,
+        /// The repository URL, for VCS methods.
+        repository: Option<String>,
+    },
+    /// The version of a package to download was selected.
+    Version {
+        /// The name of the package.
+        name: String,
+        /// The selected version.
+        version: String,
+    },
+    /// The directory a package is cloned into was determined.
+    Destination {
+        /// The name of the package.
+        name: String,
+        /// The directory.
+        path: PathBuf,
+    },
+    /// The package was found in a cloned repository, such as a member of a
+    /// workspace.
+    ///
+    /// This is only reported for VCS methods, after the clone.
+    Package {
+        /// The name of the package.
+        name: String,
+        /// The directory of the package.
+        path: PathBuf,
+    },
+    /// Part of a download was received, such as of a `.crate` file.
+    ///
+    /// This is reported after each chunk of the response, so it can be
+    /// frequent.
+    Downloading {
+        /// The name of the package.
+        name: String,
+        /// The number of bytes received so far, including any part of an
+        /// earlier attempt that was resumed.
+        received: u64,
+        /// The size of the file, if the server reported it.
+        total: Option<u64>,
+    },
+    /// A file or directory of a `.crate` file is being extracted.
+    Extracting {
+        /// The name of the package.
+        name: String,
+        /// The path within the `.crate` file.
+        path: PathBuf,
+    },
+    /// A `.crate` file was extracted.
+    Extracted {
+        /// The name of the package.
+        name: String,
+        /// The directory it was extracted into.
+        path: PathBuf,
+    },
+    /// A VCS command is about to run, such as `git clone`.
+    ///
+    /// This is not reported with [`Cloner::set_dry_run`].
+    Running {
+        /// The program.
+        command: String,
+        /// Its arguments.
+        args: Vec<String>,
+    },
+    /// A package was cloned.
+    Cloned {
+        /// The name of the package.
+        name: String,
+    },
+}
+
+/// The function called with each [`CloneEvent`].
+#[derive(Clone)]
+pub(crate) struct EventHandler(Arc<dyn Fn(&CloneEvent) + Send + Sync>);
+
+impl fmt::Debug for EventHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EventHandler")
+    }
+}
+
+impl Cloner {
+    /// Sets a function that is called with each step of a clone.
+    ///
+    /// With [`Cloner::clone_many`], events of different packages may be
+    /// interleaved, and the handler is called from several threads.
+    pub fn set_event_handler(
+        &mut self,
+        handler: impl Fn(&CloneEvent) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.event_handler = Some(EventHandler(Arc::new(handler)));
+        self
+    }
+
+    /// Reports that a command is about to run.
+    pub(crate) fn emit_running<S: AsRef<OsStr>>(&self, command: &str, args: &[S]) {
+        self.emit(CloneEvent::Running {
+            command: command.to_string(),
+            args: args
+                .iter()
+                .map(|arg| arg.as_ref().to_string_lossy().into_owned())
+                .collect(),
+        });
+    }
+
+    /// Calls the event handler, if one is set.
+    pub(crate) fn emit(&self, event: CloneEvent) {
+        if let Some(handler) = &self.event_handler {
+            (handler.0)(&event);
+        }
+    }
+}
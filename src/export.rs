@@ -0,0 +1,91 @@
+//! Export of clone results for supply-chain tooling.
+
+use crate::{CloneMethodKind, ClonedPackage, Cloner};
+use anyhow::{Context, Error};
+use serde_json::json;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// The format of the records written with [`Cloner::set_export`].
+#[derive(Debug, Clone)]
+pub enum ExportFormat {
+    /// One JSON object per line, with the package name, version, method,
+    /// source URL, and checksum.
+    Json,
+    /// `[[audits.<name>]]` tables suitable for merging into a cargo-vet
+    /// `audits.toml`, claiming the given criteria.
+    ///
+    /// Only `.crate` downloads are written, since cargo-vet audits are keyed
+    /// by version.
+    Vet {
+        /// The cargo-vet criteria, such as `safe-to-deploy`.
+        criteria: String,
+    },
+}
+
+impl Cloner {
+    /// Sets a file where a record of every clone is appended.
+    ///
+    /// The file is created if it does not exist. Existing contents are kept
+    /// so that multiple runs can accumulate into the same file.
+    pub fn set_export(&mut self, format: ExportFormat, path: impl Into<PathBuf>) -> &mut Self {
+        self.export = Some((format, path.into()));
+        self
+    }
+
+    /// Appends a record to the export file, if one is set.
+    pub(crate) fn export(&self, record: &ClonedPackage) -> Result<(), Error> {
+        let Some((format, path)) = &self.export else {
+            return Ok(());
+        };
+        if self.dry_run {
+            return Ok(());
+        }
+        let text = match format {
+            ExportFormat::Json => {
+                let value = json!({
+                    "name": record.name,
+                    "version": record.version,
+                    "method": record.method.command(),
+                    "source": record.source,
+                    "checksum": record.checksum,
+                });
+                format!("{}\n", value)
+            }
+            ExportFormat::Vet { criteria } => {
+                let (CloneMethodKind::Crate, Some(version)) = (&record.method, &record.version)
+                else {
+                    return Ok(());
+                };
+                let mut notes = format!("Downloaded by cargo-clone from {}", record.source);
+                if let Some(checksum) = &record.checksum {
+                    notes.push_str(&format!(" (sha256 {})", checksum));
+                }
+                notes.push('.');
+                format!(
+                    "[[audits.{}]]\ncriteria = {}\nversion = {}\nnotes = {}\n\n",
+                    toml_string(&record.name),
+                    toml_string(criteria),
+                    toml_string(version),
+                    toml_string(&notes)
+                )
+            }
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open export file `{}`.", path.display()))?;
+        file.write_all(text.as_bytes())
+            .with_context(|| format!("Failed to write export file `{}`.", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Quotes a string as a TOML basic string.
+///
+/// JSON string escapes are a subset of TOML's, so this reuses serde_json.
+fn toml_string(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
@@ -0,0 +1,63 @@
+//! Filtering the files extracted from a `.crate` file.
+
+use crate::error::{msg, Kind};
+use crate::{CloneError, Cloner};
+use anyhow::{Context, Error};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+/// The globs set with [`Cloner::set_extract_filter`].
+#[derive(Debug, Clone)]
+pub(crate) struct ExtractFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl ExtractFilter {
+    /// Returns whether a file is extracted, given its path relative to the
+    /// root of the package.
+    pub(crate) fn is_match(&self, path: &Path) -> bool {
+        self.include.as_ref().is_none_or(|set| set.is_match(path)) && !self.exclude.is_match(path)
+    }
+}
+
+impl Cloner {
+    /// Sets which files are extracted from a `.crate` file.
+    ///
+    /// The globs are matched against the path of each file relative to the
+    /// root of the package, such as `src/lib.rs`. `*` does not match `/`,
+    /// while `**` matches any number of directories. If `include` is not
+    /// empty, only files matching one of its globs are extracted. Files
+    /// matching one of the `exclude` globs are skipped.
+    ///
+    /// This only works with the `crate` method.
+    pub fn set_extract_filter(
+        &mut self,
+        include: &[&str],
+        exclude: &[&str],
+    ) -> Result<&mut Self, CloneError> {
+        let include = match include {
+            [] => None,
+            include => Some(glob_set(include)?),
+        };
+        self.extract_filter = Some(ExtractFilter {
+            include,
+            exclude: glob_set(exclude)?,
+        });
+        Ok(self)
+    }
+}
+
+fn glob_set(globs: &[&str]) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        let glob = GlobBuilder::new(glob)
+            .literal_separator(true)
+            .build()
+            .context(msg(Kind::InvalidInput, format!("Invalid glob `{}`.", glob)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .context(msg(Kind::InvalidInput, "Invalid globs."))
+}
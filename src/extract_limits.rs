@@ -0,0 +1,117 @@
+//! Limits on what is extracted from a `.crate` file, so that a malicious
+//! file can't fill the disk.
+
+use crate::Cloner;
+use anyhow::Error;
+use std::path::Path;
+
+/// The limits set with [`Cloner::set_max_extract_size`],
+/// [`Cloner::set_max_file_size`], and [`Cloner::set_max_entries`].
+#[derive(Debug, Clone)]
+pub(crate) struct ExtractLimits {
+    max_size: Option<u64>,
+    max_file_size: Option<u64>,
+    max_entries: Option<u64>,
+}
+
+impl Default for ExtractLimits {
+    fn default() -> ExtractLimits {
+        ExtractLimits {
+            // The same as cargo's limit when it extracts packages.
+            max_size: Some(512 * 1024 * 1024),
+            max_file_size: None,
+            max_entries: Some(100_000),
+        }
+    }
+}
+
+/// What has been extracted from a `.crate` file so far.
+#[derive(Debug, Default)]
+pub(crate) struct ExtractCount {
+    entries: u64,
+    size: u64,
+}
+
+impl ExtractLimits {
+    /// Counts an entry of a `.crate` file, failing if there are too many.
+    pub(crate) fn add_entry(&self, count: &mut ExtractCount) -> Result<(), Error> {
+        count.entries += 1;
+        if let Some(max) = self.max_entries.filter(|max| count.entries > *max) {
+            fail!(
+                Extract,
+                "The `.crate` file has more than {} entries, \
+                 raise the limit with the `extract.max-entries` config key.",
+                max
+            );
+        }
+        Ok(())
+    }
+
+    /// Counts a file that is extracted, failing if it is too large.
+    pub(crate) fn add_file(
+        &self,
+        count: &mut ExtractCount,
+        path: &Path,
+        size: u64,
+    ) -> Result<(), Error> {
+        self.check_file(path, size)?;
+        count.size += size;
+        if let Some(max) = self.max_size.filter(|max| count.size > *max) {
+            fail!(
+                Extract,
+                "The `.crate` file extracts to more than {} bytes, \
+                 raise the limit with the `extract.max-size` config key.",
+                max
+            );
+        }
+        Ok(())
+    }
+
+    /// Fails if a single file is too large.
+    pub(crate) fn check_file(&self, path: &Path, size: u64) -> Result<(), Error> {
+        if let Some(max) = self.max_file_size.filter(|max| size > *max) {
+            fail!(
+                Extract,
+                "`{}` is {} bytes, more than the limit of {} bytes per file, \
+                 raise it with the `extract.max-file-size` config key.",
+                path.display(),
+                size,
+                max
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Cloner {
+    /// Sets the most bytes extracted from a `.crate` file in total.
+    ///
+    /// Extraction fails with [`CloneError::Extract`] if the files add up to
+    /// more. Defaults to 512 MiB, the same limit as cargo. `None` removes the
+    /// limit.
+    ///
+    /// [`CloneError::Extract`]: crate::CloneError::Extract
+    pub fn set_max_extract_size(&mut self, value: Option<u64>) -> &mut Self {
+        self.extract_limits.max_size = value;
+        self
+    }
+
+    /// Sets the most bytes of a single file extracted from a `.crate` file.
+    ///
+    /// Defaults to `None`, which only limits the total with
+    /// [`Cloner::set_max_extract_size`]. This also applies to
+    /// [`Cloner::read_crate_file`].
+    pub fn set_max_file_size(&mut self, value: Option<u64>) -> &mut Self {
+        self.extract_limits.max_file_size = value;
+        self
+    }
+
+    /// Sets the most entries, files and directories, a `.crate` file may
+    /// have.
+    ///
+    /// Defaults to 100,000. `None` removes the limit.
+    pub fn set_max_entries(&mut self, value: Option<u64>) -> &mut Self {
+        self.extract_limits.max_entries = value;
+        self
+    }
+}
@@ -0,0 +1,205 @@
+//! How unusual entries of a `.crate` file are extracted, such as links and
+//! paths that could write outside of the package.
+
+use crate::error::{msg, Kind};
+use crate::Cloner;
+use anyhow::{Context, Error};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// What to do with an entry of a `.crate` file that could be unsafe to
+/// extract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractPolicy {
+    /// Skip the entry, with a warning.
+    Skip,
+    /// Fail the extraction with [`CloneError::Extract`].
+    ///
+    /// [`CloneError::Extract`]: crate::CloneError::Extract
+    Error,
+    /// Extract the entry if it can be made safe, otherwise skip it.
+    Sanitize,
+}
+
+impl ExtractPolicy {
+    /// Creates an `ExtractPolicy` from its name, `skip`, `error`, or
+    /// `sanitize`. If no name matches then None is returned.
+    pub fn from(name: &str) -> Option<ExtractPolicy> {
+        match name {
+            "skip" => Some(ExtractPolicy::Skip),
+            "error" => Some(ExtractPolicy::Error),
+            "sanitize" => Some(ExtractPolicy::Sanitize),
+            _ => None,
+        }
+    }
+}
+
+/// The policies set with [`Cloner::set_link_policy`] and
+/// [`Cloner::set_path_policy`].
+#[derive(Debug, Clone)]
+pub(crate) struct ExtractPolicies {
+    links: ExtractPolicy,
+    paths: ExtractPolicy,
+}
+
+impl Default for ExtractPolicies {
+    fn default() -> ExtractPolicies {
+        ExtractPolicies {
+            links: ExtractPolicy::Sanitize,
+            paths: ExtractPolicy::Error,
+        }
+    }
+}
+
+/// How an entry is extracted, from [`ExtractPolicies::check_link`].
+pub(crate) enum Unpack {
+    /// Unpack the entry itself.
+    Entry,
+    /// Skip the entry.
+    Skip,
+    /// Copy the given, already extracted, file for a hard link.
+    Copy(PathBuf),
+}
+
+impl ExtractPolicies {
+    /// Returns the path an entry is extracted to, or `None` to skip it.
+    ///
+    /// Paths that are absolute or have `..` components are handled by the
+    /// path policy.
+    pub(crate) fn entry_path(&self, path: &Path) -> Result<Option<PathBuf>, Error> {
+        if path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Ok(Some(path.to_path_buf()));
+        }
+        match self.paths {
+            ExtractPolicy::Error => fail!(
+                Extract,
+                "Entry `{}` has an absolute path or a `..` component, \
+                 skip or sanitize it with the `extract.paths` config key.",
+                path.display()
+            ),
+            ExtractPolicy::Skip => {
+                warn!(
+                    "Skipping `{}`, it has an absolute path or a `..` component.",
+                    path.display()
+                );
+                Ok(None)
+            }
+            ExtractPolicy::Sanitize => {
+                // Remove the root, and resolve `..` without following links.
+                let mut sanitized = PathBuf::new();
+                for c in path.components() {
+                    match c {
+                        Component::Normal(c) => sanitized.push(c),
+                        Component::ParentDir if !sanitized.pop() => {
+                            warn!(
+                                "Skipping `{}`, it is outside of the package.",
+                                path.display()
+                            );
+                            return Ok(None);
+                        }
+                        _ => {}
+                    }
+                }
+                debug!(
+                    "Extracting `{}` as `{}`",
+                    path.display(),
+                    sanitized.display()
+                );
+                Ok(Some(sanitized))
+            }
+        }
+    }
+
+    /// Returns how an entry is extracted, handling symbolic and hard links
+    /// with the link policy.
+    ///
+    /// `tmp` is the directory the package is being extracted into.
+    pub(crate) fn check_link(
+        &self,
+        entry: &tar::Entry<impl Read>,
+        path: &Path,
+        tmp: &Path,
+    ) -> Result<Unpack, Error> {
+        let kind = entry.header().entry_type();
+        if !kind.is_symlink() && !kind.is_hard_link() {
+            return Ok(Unpack::Entry);
+        }
+        let target = entry
+            .link_name()
+            .context(msg(Kind::Extract, "Failed to read entry link."))?
+            .unwrap_or_default()
+            .into_owned();
+        // Only targets that can't leave the package are kept, since `..`
+        // could be resolved through another link.
+        let is_safe = !target.as_os_str().is_empty()
+            && target
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+        match self.links {
+            ExtractPolicy::Error => fail!(
+                Extract,
+                "Entry `{}` is a link to `{}`, \
+                 skip or sanitize links with the `extract.links` config key.",
+                path.display(),
+                target.display()
+            ),
+            ExtractPolicy::Skip => {
+                warn!(
+                    "Skipping the link `{}` to `{}`.",
+                    path.display(),
+                    target.display()
+                );
+                Ok(Unpack::Skip)
+            }
+            ExtractPolicy::Sanitize if !is_safe => {
+                warn!(
+                    "Skipping the link `{}` to `{}`, it could point outside of the package.",
+                    path.display(),
+                    target.display()
+                );
+                Ok(Unpack::Skip)
+            }
+            ExtractPolicy::Sanitize if kind.is_symlink() => Ok(Unpack::Entry),
+            ExtractPolicy::Sanitize => {
+                // The target of a hard link is a path in the `.crate` file.
+                let source = tmp.join(&target);
+                if !source.is_file() {
+                    warn!(
+                        "Skipping the hard link `{}`, `{}` was not extracted.",
+                        path.display(),
+                        target.display()
+                    );
+                    return Ok(Unpack::Skip);
+                }
+                Ok(Unpack::Copy(source))
+            }
+        }
+    }
+}
+
+impl Cloner {
+    /// Sets how symbolic and hard links in a `.crate` file are extracted.
+    ///
+    /// Defaults to [`ExtractPolicy::Sanitize`], which only keeps links to a
+    /// relative path without `..`, so that they stay within the package. A
+    /// hard link is extracted as a copy of the file it links to.
+    pub fn set_link_policy(&mut self, value: ExtractPolicy) -> &mut Self {
+        self.extract_policies.links = value;
+        self
+    }
+
+    /// Sets how entries of a `.crate` file with an absolute path or `..`
+    /// components are extracted.
+    ///
+    /// Defaults to [`ExtractPolicy::Error`]. With
+    /// [`ExtractPolicy::Sanitize`], the root is removed and the `..`
+    /// components are resolved, skipping entries that would still be outside
+    /// of the package.
+    pub fn set_path_policy(&mut self, value: ExtractPolicy) -> &mut Self {
+        self.extract_policies.paths = value;
+        self
+    }
+}
@@ -0,0 +1,86 @@
+//! Starting a git repository in an extracted `.crate` file.
+
+use crate::store::run_git;
+use crate::{CloneMethodKind, ClonedPackage, Cloner};
+use anyhow::Error;
+use std::path::Path;
+use std::process::Command;
+
+impl Cloner {
+    /// Sets whether a git repository is created in an extracted `.crate`
+    /// file, with a commit of its files, so that changes to it can be seen
+    /// with `git diff`.
+    ///
+    /// Defaults to `false`. This is not done with [`Cloner::set_vendor`] or
+    /// [`Cloner::set_strip_prefix`], or for VCS clones.
+    pub fn set_git_init(&mut self, value: bool) -> &mut Self {
+        self.git_init = value;
+        self
+    }
+
+    /// Sets whether the commit of [`Cloner::set_git_init`] is tagged with the
+    /// version of the package, such as `v1.0.5`.
+    ///
+    /// Defaults to `false`.
+    pub fn set_git_init_tag(&mut self, value: bool) -> &mut Self {
+        self.git_init_tag = value;
+        self
+    }
+
+    /// Creates a git repository in an extracted package, if enabled.
+    pub(crate) fn git_init(&self, package: &ClonedPackage) -> Result<(), Error> {
+        if !self.git_init
+            || self.vendor
+            || self.strip_prefix
+            || !self.extract
+            || !matches!(package.method, CloneMethodKind::Crate)
+        {
+            return Ok(());
+        }
+        let dir = &package.path;
+        info!("{}: git init {}", self.running(), dir.display());
+        if self.dry_run {
+            return Ok(());
+        }
+        run_git(git_command(dir).args(["init", "-q"]))?;
+        let version = package.version.as_deref().unwrap_or_default();
+        commit_all(dir, &format!("{} {}", package.name, version), None)?;
+        if self.git_init_tag && !version.is_empty() {
+            run_git(git_command(dir).args(["tag", &format!("v{}", version)]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a `git` command run in `dir`, for commits made by cargo-clone.
+pub(crate) fn git_command(dir: &Path) -> Command {
+    let mut cmd = Command::new("git");
+    // Any identity or signing configuration of the user is left out.
+    cmd.arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=cargo-clone"])
+        .args(["-c", "user.email=cargo-clone@localhost"])
+        .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false"]);
+    cmd
+}
+
+/// Commits every file in `dir`, with the commit date `date` if given.
+pub(crate) fn commit_all(dir: &Path, message: &str, date: Option<&str>) -> Result<(), Error> {
+    // Packages may include files that their `.gitignore` ignores, such as
+    // `Cargo.lock`.
+    run_git(git_command(dir).args(["add", "--all", "--force"]))?;
+    let mut cmd = git_command(dir);
+    cmd.args([
+        "commit",
+        "-q",
+        "--no-verify",
+        "--allow-empty",
+        "-m",
+        message,
+    ]);
+    if let Some(date) = date {
+        cmd.env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date);
+    }
+    run_git(&mut cmd)
+}
@@ -0,0 +1,181 @@
+//! Downloading GitHub repositories as tarballs, without a VCS.
+
+use crate::error::{msg, Kind};
+use crate::{CloneEvent, CloneMethodKind, ClonedPackage, Cloner, CrateResponse};
+use anyhow::{Context, Error};
+use flate2::read::GzDecoder;
+use regex::Regex;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+use tar::{Archive, EntryType};
+
+impl Cloner {
+    /// Downloads a GitHub repository from codeload.github.com and extracts
+    /// it.
+    ///
+    /// The branch, tag, or revision set on the `Cloner` is downloaded, or the
+    /// commit a version was published from, or else the default branch. The
+    /// only extra argument allowed is the destination directory, which
+    /// defaults to the name of the repository.
+    pub(crate) fn clone_github_archive(
+        &self,
+        name: &str,
+        repo: &str,
+        version_req: Option<&str>,
+        extra: &[&str],
+        pkg_info: &CrateResponse,
+    ) -> Result<ClonedPackage, Error> {
+        let Some(c) = Regex::new(r"https?://(?:www\.)?github\.com/([^/]+)/([^/#?]+)")
+            .unwrap()
+            .captures(repo)
+        else {
+            fail!(
+                Repository,
+                "The `github-archive` method only works with GitHub repositories, got `{}`.",
+                repo
+            );
+        };
+        let owner = c.get(1).unwrap().as_str();
+        let repo_name = c.get(2).unwrap().as_str();
+        let repo_name = repo_name.strip_suffix(".git").unwrap_or(repo_name);
+        if self.recurse_submodules {
+            warn!("GitHub archives do not include submodules, ignoring them.");
+        }
+
+        let dir = match extra {
+            [] => repo_name,
+            [dir] if !dir.starts_with('-') => dir,
+            _ => fail!(
+                InvalidInput,
+                "The `github-archive` method only takes a directory as an extra argument."
+            ),
+        };
+        let (version, git_ref) = match (&self.checkout, version_req) {
+            (Some(checkout), Some(version_req)) => fail!(
+                InvalidInput,
+                "Cannot use {} together with a version `{}`.",
+                checkout.flag(),
+                version_req
+            ),
+            (Some(checkout), None) => (None, checkout.name().to_string()),
+            (None, Some(version_req)) if self.dry_run => {
+                info!(
+                    "Would download the commit `{} {}` was published from",
+                    name, version_req
+                );
+                return Ok(ClonedPackage {
+                    name: name.to_string(),
+                    version: None,
+                    method: CloneMethodKind::GithubArchive,
+                    source: repo.to_string(),
+                    path: self.out_dir()?.join(dir),
+                    checksum: None,
+                    summary: None,
+                    archive: None,
+                });
+            }
+            (None, Some(version_req)) => {
+                match self.published_commit(name, version_req, pkg_info)? {
+                    (version, Some(commit)) => (Some(version), commit),
+                    (version, None) => fail!(
+                        Version,
+                        "`{} {}` does not record the commit it was published from, \
+                         use --tag or --rev to pick what to download.",
+                        name,
+                        version
+                    ),
+                }
+            }
+            (None, None) => (None, "HEAD".to_string()),
+        };
+        let dest = self.out_dir()?.join(dir);
+        self.emit(CloneEvent::Destination {
+            name: name.to_string(),
+            path: dest.clone(),
+        });
+        self.prepare_destination(&dest)?;
+
+        let url = format!(
+            "https://codeload.github.com/{}/{}/tar.gz/{}",
+            owner, repo_name, git_ref
+        );
+        let package = ClonedPackage {
+            name: name.to_string(),
+            version,
+            method: CloneMethodKind::GithubArchive,
+            source: url,
+            path: dest,
+            checksum: None,
+            summary: None,
+            archive: None,
+        };
+        if self.dry_run {
+            info!(
+                "Would download `{}` to `{}`",
+                package.source,
+                package.path.display()
+            );
+            return Ok(package);
+        }
+        info!("Downloading `{}`", package.source);
+        let base = format!("{}-{}", repo_name, git_ref.replace('/', "-"));
+        let part = self.download_crate(name, &base, &package.source, None)?;
+        let result = self.extract_archive(&part, &package.path);
+        if self.check_cancelled().is_ok() {
+            let _ = fs::remove_file(&part);
+        }
+        result?;
+        Ok(package)
+    }
+
+    /// Extracts a GitHub tarball into `dest`, without its top-level
+    /// directory.
+    fn extract_archive(&self, path: &Path, dest: &Path) -> Result<(), Error> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open `{}`.", path.display()))?;
+        let mut tar = Archive::new(GzDecoder::new(file));
+        let parent = dest.parent().unwrap();
+        let tmp = tempfile::Builder::new()
+            .prefix(".github-archive.")
+            .tempdir_in(parent)
+            .context(format!(
+                "Failed to create temporary directory in `{}`",
+                parent.display()
+            ))?;
+        for entry in tar.entries()? {
+            self.check_cancelled()?;
+            let mut entry = entry.context(msg(Kind::Extract, "Failed to get tar entry."))?;
+            // GitHub adds a global header with the commit.
+            if entry.header().entry_type() == EntryType::XGlobalHeader {
+                continue;
+            }
+            let entry_path = entry
+                .path()
+                .context(msg(Kind::Extract, "Failed to read entry path."))?
+                .into_owned();
+            info!("{}", entry_path.display());
+            let rel: PathBuf = entry_path.components().skip(1).collect();
+            if !rel.components().all(|c| matches!(c, Component::Normal(_))) {
+                fail!(
+                    Extract,
+                    "Unexpected path `{}` in tarball.",
+                    entry_path.display()
+                );
+            }
+            if rel.as_os_str().is_empty() {
+                continue;
+            }
+            let target = tmp.path().join("archive").join(&rel);
+            if let Some(dir) = target.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            entry.unpack(&target).context(msg(
+                Kind::Extract,
+                format!("failed to unpack entry at `{}`", entry_path.display()),
+            ))?;
+        }
+        fs::rename(tmp.path().join("archive"), dest)
+            .context(format!("Failed to move repository to `{}`", dest.display()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! Cloning git repositories with gix, without the `git` binary.
+
+use crate::checkout::Checkout;
+use crate::error::{msg, Kind};
+use crate::store::worktree_name;
+use crate::Cloner;
+use anyhow::{Context, Error};
+use gix::progress::Discard;
+use gix::refs::transaction::PreviousValue;
+use gix::remote::fetch::Shallow;
+use std::num::NonZeroU32;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+impl Cloner {
+    /// Clones a git repository with gix, and checks out a commit if given.
+    ///
+    /// Returns `false` without cloning if there are extra arguments other
+    /// than the destination directory, since those are for the `git`
+    /// binary, or if submodules are requested, which gix cannot check out.
+    pub(crate) fn clone_gix(
+        &self,
+        repo: &str,
+        extra: &[&str],
+        commit: Option<&str>,
+    ) -> Result<bool, Error> {
+        let dir = match extra {
+            [] => worktree_name(repo),
+            [dir] if !dir.starts_with('-') => dir.to_string(),
+            _ => return Ok(false),
+        };
+        if self.recurse_submodules {
+            return Ok(false);
+        }
+        let dest = self.out_dir()?.join(dir);
+        let ref_name = match &self.checkout {
+            Some(Checkout::Branch(name) | Checkout::Tag(name)) => Some(name.as_str()),
+            _ => None,
+        };
+        if self.dry_run {
+            info!("Would clone `{}` into `{}` with gix", repo, dest.display());
+            return Ok(true);
+        }
+        info!("Cloning `{}` into `{}` with gix", repo, dest.display());
+        let interrupt = self.cancel.clone().unwrap_or_default();
+        let shallow = self.shallow && commit.is_none();
+        if self.shallow && !shallow {
+            warn!(
+                "Shallow clones of a commit are not supported with gix, cloning the full history."
+            );
+        }
+        let result = gix_clone(repo, &dest, ref_name, commit, shallow, &interrupt);
+        if result.is_err() {
+            self.check_cancelled()?;
+        }
+        result.with_context(|| msg(Kind::Vcs, format!("Failed to clone `{}` with gix.", repo)))?;
+        Ok(true)
+    }
+}
+
+fn gix_clone(
+    repo: &str,
+    dest: &std::path::Path,
+    ref_name: Option<&str>,
+    commit: Option<&str>,
+    shallow: bool,
+    interrupt: &Arc<AtomicBool>,
+) -> Result<(), Error> {
+    let mut prepare = gix::prepare_clone(repo, dest)?.with_ref_name(ref_name)?;
+    if shallow {
+        prepare = prepare.with_shallow(Shallow::DepthAtRemote(NonZeroU32::new(1).unwrap()));
+    }
+    let (mut checkout, _) = prepare.fetch_then_checkout(Discard, interrupt)?;
+    if let Some(commit) = commit {
+        let repo = checkout.repo();
+        let id = repo
+            .rev_parse_single(commit)
+            .with_context(|| format!("Failed to find `{}` in the repository.", commit))?
+            .object()?
+            .peel_to_commit()?
+            .id;
+        info!("Checking out {}", id);
+        repo.reference("HEAD", id, PreviousValue::Any, "cargo clone")?;
+    }
+    checkout.main_worktree(Discard, interrupt)?;
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! Importing the versions of a package as the history of a git repository.
+
+use crate::git_init::{commit_all, git_command};
+use crate::store::run_git;
+use crate::{found_name, parse_spec, CloneError, Cloner, VersionSelection};
+use anyhow::{Context, Error};
+use std::fs;
+use std::path::PathBuf;
+
+impl Cloner {
+    /// Downloads several versions of a package and commits each one, oldest
+    /// first, into a new git repository, so that `git log -p` shows how the
+    /// package changed from release to release.
+    ///
+    /// Each commit is tagged with its version, such as `v1.0.5`, and dated
+    /// when the version was published, if the registry reports it. The
+    /// repository is created in the output directory, named after the
+    /// package, or the name set with [`Cloner::set_dest_name`]. Returns the
+    /// path of the repository.
+    ///
+    /// - `spec` - The name of the crate, optionally with a version
+    ///   requirement such as `serde@1` to only import the matching versions
+    /// - `selection` - Which versions to import
+    ///
+    pub fn clone_history(
+        &self,
+        spec: &str,
+        selection: &VersionSelection,
+    ) -> Result<PathBuf, CloneError> {
+        Ok(self.clone_history_inner(spec, selection)?)
+    }
+
+    fn clone_history_inner(
+        &self,
+        spec: &str,
+        selection: &VersionSelection,
+    ) -> Result<PathBuf, Error> {
+        let (name, spec_req) = parse_spec(spec, None)?;
+        self.emit_resolving(name, spec_req.as_deref());
+        let pkg_info = self.get_pkg_info(name)?;
+        let name = found_name(name, &pkg_info);
+        let mut versions = self.select_versions(name, spec_req.as_deref(), selection, &pkg_info)?;
+        versions.reverse();
+        let out_dir = self.out_dir()?;
+        let dest = out_dir.join(
+            self.dest_name
+                .clone()
+                .unwrap_or_else(|| name.to_lowercase()),
+        );
+        self.prepare_destination(&dest)?;
+        if self.dry_run {
+            for version in &versions {
+                info!(
+                    "Would commit `{} {}` to `{}`",
+                    name,
+                    version,
+                    dest.display()
+                );
+            }
+            return Ok(dest);
+        }
+        fs::create_dir_all(&dest)
+            .with_context(|| format!("Failed to create `{}`.", dest.display()))?;
+        run_git(git_command(&dest).args(["init", "-q"]))?;
+        // Each version is extracted next to the repository, and then moved
+        // into it.
+        let tmp = tempfile::Builder::new()
+            .prefix(&format!(".{}.", name.to_lowercase()))
+            .tempdir_in(&out_dir)
+            .context(format!(
+                "Failed to create temporary directory in `{}`",
+                out_dir.display()
+            ))?;
+        let mut cloner = Clone::clone(self);
+        cloner.set_out_dir(tmp.path());
+        cloner.dest_name = None;
+        cloner.strip_prefix = false;
+        cloner.vendor = false;
+        cloner.extract = true;
+        cloner.keep_archive = false;
+        for version in &versions {
+            self.check_cancelled()?;
+            let package = cloner.clone_crate(name, Some(&format!("={}", version)), &pkg_info)?;
+            // Replace the files of the previous version.
+            for entry in fs::read_dir(&dest)? {
+                let path = entry?.path();
+                if path.file_name() == Some(".git".as_ref()) {
+                    continue;
+                }
+                let result = if path.is_dir() {
+                    fs::remove_dir_all(&path)
+                } else {
+                    fs::remove_file(&path)
+                };
+                result.with_context(|| format!("Failed to remove `{}`.", path.display()))?;
+            }
+            for entry in fs::read_dir(&package.path)? {
+                let entry = entry?;
+                fs::rename(entry.path(), dest.join(entry.file_name()))
+                    .with_context(|| format!("Failed to move `{}`.", entry.path().display()))?;
+            }
+            fs::remove_dir(&package.path)?;
+            let num = version.to_string();
+            let created_at = pkg_info
+                .versions
+                .iter()
+                .find(|v| v.num == num)
+                .and_then(|v| v.created_at.as_deref());
+            info!("Committing `{} {}`", name, version);
+            commit_all(&dest, &format!("{} {}", name, version), created_at)?;
+            run_git(git_command(&dest).args(["tag", &format!("v{}", version)]))?;
+        }
+        info!(
+            "Committed {} versions of `{}` to `{}`",
+            versions.len(),
+            name,
+            dest.display()
+        );
+        Ok(dest)
+    }
+}
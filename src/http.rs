@@ -0,0 +1,179 @@
+//! HTTP clients, and the timeouts and retries of requests.
+
+use crate::error::Message;
+use crate::Cloner;
+use anyhow::Error;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::fmt::Display;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Starts a builder for a client of type `$client`, either the blocking or
+/// the async client, with the settings of a [`Cloner`].
+macro_rules! client_builder {
+    ($cloner:expr, $client:ty) => {{
+        let cloner: &$crate::Cloner = $cloner;
+        let mut builder = <$client>::builder().user_agent($crate::APP_USER_AGENT);
+        if let Some(proxy) = cloner.proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(timeout) = cloner.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = cloner.timeout {
+            builder = builder.timeout(timeout);
+        }
+        #[cfg(any(feature = "native-tls", feature = "rustls"))]
+        {
+            for cert in &cloner.root_certificates {
+                builder = builder.add_root_certificate(cert.clone());
+            }
+            if let Some(identity) = &cloner.client_identity {
+                builder = builder.identity(identity.clone());
+            }
+            builder = builder.danger_accept_invalid_certs(cloner.accept_invalid_certs);
+        }
+        builder
+    }};
+}
+
+/// The longest wait between two attempts, including one asked for with
+/// `Retry-After`.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+impl Cloner {
+    /// Sets how long to wait for a connection to be established.
+    ///
+    /// There is no limit by default.
+    pub fn set_connect_timeout(&mut self, value: Duration) -> &mut Self {
+        self.connect_timeout = Some(value);
+        self
+    }
+
+    /// Sets how long a request may take, from connecting until the response
+    /// has been read.
+    ///
+    /// The default is 30 seconds for the blocking client, with no limit for
+    /// the registry requests of `Cloner::clone_async`. A download that times
+    /// out is resumed when retried.
+    pub fn set_timeout(&mut self, value: Duration) -> &mut Self {
+        self.timeout = Some(value);
+        self
+    }
+
+    /// Sets how many times a request is retried after a transient failure,
+    /// such as a failed connection, a timeout, a server error (`5xx`), or
+    /// rate limiting (`429`).
+    ///
+    /// The wait between attempts doubles each time, starting at one second,
+    /// unless the server asks for a different wait with `Retry-After`.
+    /// The default is 4.
+    pub fn set_retries(&mut self, value: u32) -> &mut Self {
+        self.retries = value;
+        self
+    }
+
+    /// Sets the client used for all HTTP requests, instead of one built from
+    /// the proxy and timeout settings.
+    ///
+    /// This allows adding default headers, TLS client certificates, and
+    /// other settings reqwest supports. Note that crates.io requires a
+    /// `User-Agent` header. Requests are still retried as set with
+    /// [`Cloner::set_retries`].
+    pub fn set_http_client(&mut self, client: reqwest::blocking::Client) -> &mut Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets the client used for the requests made by
+    /// [`Cloner::clone_async`], like [`Cloner::set_http_client`].
+    ///
+    /// This is used for the registry requests and `.crate` downloads. The
+    /// fallback to docs.rs (see [`Cloner::set_docs_rs_fallback`]) runs on the
+    /// blocking thread pool, and uses the blocking client.
+    #[cfg(feature = "async")]
+    pub fn set_async_http_client(&mut self, client: reqwest::Client) -> &mut Self {
+        self.async_http_client = Some(client);
+        self
+    }
+
+    /// Logs a failed attempt and waits before the next one.
+    ///
+    /// `retry_after` is the wait requested by the server, if any, otherwise
+    /// the wait doubles with each attempt.
+    pub(crate) fn wait_to_retry(
+        &self,
+        url: &str,
+        attempt: u32,
+        reason: impl Display,
+        retry_after: Option<Duration>,
+    ) {
+        thread::sleep(self.log_retry(url, attempt, reason, retry_after));
+    }
+
+    /// Logs a failed attempt, returning how long to wait before the next one.
+    pub(crate) fn log_retry(
+        &self,
+        url: &str,
+        attempt: u32,
+        reason: impl Display,
+        retry_after: Option<Duration>,
+    ) -> Duration {
+        let delay = retry_after.unwrap_or_else(|| backoff(attempt));
+        warn!(
+            "Request to `{}` failed (attempt {}/{}), retrying in {}s: {}",
+            url,
+            attempt,
+            self.retries + 1,
+            delay.as_secs(),
+            reason
+        );
+        delay
+    }
+}
+
+/// Returns how long to wait after the given failed attempt, starting at 1.
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1 << attempt.saturating_sub(1).min(5)).min(MAX_BACKOFF)
+}
+
+/// Whether a response is worth retrying: a server error, or too many
+/// requests.
+pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Reads how long the server asks to wait before retrying, from the
+/// `Retry-After` header in seconds or as an HTTP date.
+///
+/// This is the wait used between the retries of [`Cloner::set_retries`]. It
+/// is capped at 30 seconds, so that a server can't stall a run for hours.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    let delay = match value.parse() {
+        Ok(secs) => Duration::from_secs(secs),
+        Err(_) => httpdate::parse_http_date(value)
+            .ok()?
+            .duration_since(SystemTime::now())
+            .unwrap_or_default(),
+    };
+    Some(delay.min(MAX_BACKOFF))
+}
+
+/// Whether a request that failed without a response is worth retrying.
+pub(crate) fn is_transient_request(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.is_timeout() || e.is_request()
+}
+
+/// Whether a failed attempt is worth retrying: connection failures, server
+/// errors, and rate limiting.
+pub(crate) fn is_transient(e: &Error) -> bool {
+    if e.chain().any(|e| e.is::<Message>()) {
+        return false;
+    }
+    e.chain().any(|e| match e.downcast_ref::<reqwest::Error>() {
+        Some(e) => e.status().is_none_or(is_transient_status),
+        None => e.is::<std::io::Error>(),
+    })
+}
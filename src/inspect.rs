@@ -0,0 +1,200 @@
+//! Reading the files of a published package without extracting it.
+
+use crate::error::{msg, Kind};
+use crate::{check_auth_status, found_name, CloneError, Cloner, CrateDownload};
+use anyhow::{Context, Error};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tar::Archive;
+
+/// The files of a published package, from [`Cloner::list_crate_files`].
+#[derive(Debug, Clone)]
+pub struct CrateListing {
+    /// The name of the package.
+    pub name: String,
+    /// The version that was listed.
+    pub version: String,
+    /// The files in the `.crate` file, in the order they are stored.
+    pub files: Vec<CrateEntry>,
+}
+
+/// A file in a `.crate` file.
+#[derive(Debug, Clone)]
+pub struct CrateEntry {
+    /// The path of the file within the package, such as `src/lib.rs`.
+    pub path: PathBuf,
+    /// The size of the file in bytes.
+    pub size: u64,
+}
+
+/// An opened `.crate` file.
+struct CrateArchive {
+    name: String,
+    version: String,
+    /// The directory the files are in, `<name>-<version>`.
+    base: String,
+    archive: Archive<GzDecoder<Box<dyn Read>>>,
+}
+
+impl Cloner {
+    /// Lists the files of a published package, without writing anything to
+    /// disk.
+    ///
+    /// The `.crate` file is read from the cache if it is there, otherwise it
+    /// is downloaded into memory.
+    ///
+    /// - `spec` - The name of the crate, optionally with a version
+    /// - `version` - The semantic version (semver) of the crate
+    ///
+    pub fn list_crate_files(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+    ) -> Result<CrateListing, CloneError> {
+        Ok(self.list_crate_files_inner(spec, version)?)
+    }
+
+    fn list_crate_files_inner(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+    ) -> Result<CrateListing, Error> {
+        let mut krate = self.open_crate(spec, version)?;
+        info!("Listing the files of `{} {}`", krate.name, krate.version);
+        let mut files = Vec::new();
+        for entry in krate.archive.entries()? {
+            let entry = entry.context(msg(Kind::Extract, "Failed to get tar entry."))?;
+            if entry.header().entry_type().is_dir() {
+                continue;
+            }
+            files.push(CrateEntry {
+                path: package_path(&krate.base, &entry)?,
+                size: entry.size(),
+            });
+        }
+        Ok(CrateListing {
+            name: krate.name,
+            version: krate.version,
+            files,
+        })
+    }
+
+    /// Reads a single file of a published package, such as `build.rs`,
+    /// without writing anything to disk.
+    ///
+    /// The `.crate` file is read as with [`Cloner::list_crate_files`]. It
+    /// fails with [`CloneError::NotFound`] if the package has no such file.
+    ///
+    /// - `spec` - The name of the crate, optionally with a version
+    /// - `version` - The semantic version (semver) of the crate
+    /// - `path` - The path of the file within the package
+    ///
+    pub fn read_crate_file(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+        path: &Path,
+    ) -> Result<Vec<u8>, CloneError> {
+        Ok(self.read_crate_file_inner(spec, version, path)?)
+    }
+
+    fn read_crate_file_inner(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+        path: &Path,
+    ) -> Result<Vec<u8>, Error> {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        let mut krate = self.open_crate(spec, version)?;
+        debug!(
+            "Reading `{}` of `{} {}`",
+            path.display(),
+            krate.name,
+            krate.version
+        );
+        for entry in krate.archive.entries()? {
+            let mut entry = entry.context(msg(Kind::Extract, "Failed to get tar entry."))?;
+            if entry.header().entry_type().is_dir() || package_path(&krate.base, &entry)? != path {
+                continue;
+            }
+            self.extract_limits.check_file(path, entry.size())?;
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents).context(msg(
+                Kind::Extract,
+                format!("Failed to read `{}`.", path.display()),
+            ))?;
+            return Ok(contents);
+        }
+        fail!(
+            NotFound,
+            "`{}` is not in `{} {}`.",
+            path.display(),
+            krate.name,
+            krate.version
+        );
+    }
+
+    /// Selects the version of a package and opens its `.crate` file.
+    fn open_crate(&self, spec: &str, version: Option<&str>) -> Result<CrateArchive, Error> {
+        let (name, version_req) = self.parse_request(spec, version)?;
+        let pkg_info = self.get_pkg_info(name)?;
+        let name = found_name(name, &pkg_info);
+        let version_req = self.crate_version_req(name, version_req.as_deref());
+        let download = self.select_download(&pkg_info, version_req.as_deref())?;
+        let body = self.crate_reader(&download)?;
+        Ok(CrateArchive {
+            name: name.to_string(),
+            base: format!("{}-{}", name.to_lowercase(), download.version),
+            version: download.version,
+            archive: Archive::new(GzDecoder::new(body)),
+        })
+    }
+
+    /// Returns a reader of a `.crate` file, from the cache or the registry.
+    fn crate_reader(&self, download: &CrateDownload) -> Result<Box<dyn Read>, Error> {
+        let path = match self.cached_crate(download) {
+            Some(path) => {
+                debug!("Using cached `{}`", path.display());
+                Some(path)
+            }
+            // A package of a local registry is already on disk.
+            None => download.url.strip_prefix("file://").map(PathBuf::from),
+        };
+        if let Some(path) = path {
+            let file = File::open(&path)
+                .with_context(|| format!("Failed to open `{}`.", path.display()))?;
+            return Ok(Box::new(file));
+        }
+        self.check_cancelled()?;
+        info!("Downloading `{}`", download.url);
+        let response = self
+            .registry_get(&download.url)
+            .with_context(|| format!("Failed to download `{}`", download.url))?;
+        check_auth_status(response.status())?;
+        let response = response
+            .error_for_status()
+            .with_context(|| format!("Failed to download `{}`", download.url))?;
+        Ok(Box::new(response))
+    }
+}
+
+/// Returns the path of a tar entry within the package, checking that it is
+/// in the `base` directory.
+fn package_path(base: &str, entry: &tar::Entry<impl Read>) -> Result<PathBuf, Error> {
+    let path = entry
+        .path()
+        .context(msg(Kind::Extract, "Failed to read entry path."))?;
+    let mut components = path.components();
+    match components.next() {
+        // The directory keeps the case of the package name.
+        Some(c) if c.as_os_str().eq_ignore_ascii_case(base) => Ok(components.as_path().into()),
+        _ => fail!(
+            Extract,
+            "Expected path `{}` in tarball, got `{}`.",
+            base,
+            path.display()
+        ),
+    }
+}
@@ -0,0 +1,91 @@
+//! Where packages are placed within the output directory.
+
+use crate::Cloner;
+use anyhow::{Context, Error};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How the directories of packages are arranged in the output directory,
+/// set with [`Cloner::set_layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// `<name>-<version>`, as in the `.crate` file.
+    #[default]
+    Flat,
+    /// `<name>/<version>`.
+    Versioned,
+    /// `<registry>/<name>/<version>`, where the registry is the host of its
+    /// URL, such as `crates.io`.
+    Registry,
+}
+
+impl Layout {
+    /// Creates a `Layout` from its name, `flat`, `versioned`, or `registry`.
+    /// If no name matches then None is returned.
+    pub fn from(name: &str) -> Option<Layout> {
+        match name {
+            "flat" => Some(Layout::Flat),
+            "versioned" => Some(Layout::Versioned),
+            "registry" => Some(Layout::Registry),
+            _ => None,
+        }
+    }
+}
+
+impl Cloner {
+    /// Sets how the directories of extracted packages are arranged in the
+    /// output directory, such as to keep many packages apart.
+    ///
+    /// Defaults to [`Layout::Flat`]. This also names the directories of
+    /// several versions from [`Cloner::version_requests`], and `.crate` files
+    /// that are kept. It is not used with [`Cloner::set_vendor`], which needs
+    /// the flat layout of `cargo vendor`.
+    pub fn set_layout(&mut self, value: Layout) -> &mut Self {
+        self.layout = value;
+        self
+    }
+
+    /// Returns the directory of a version of a package, relative to the
+    /// output directory.
+    pub(crate) fn package_dir(&self, name: &str, version: &str) -> PathBuf {
+        let name = name.to_lowercase();
+        let layout = if self.vendor {
+            Layout::Flat
+        } else {
+            self.layout
+        };
+        match layout {
+            Layout::Flat => format!("{}-{}", name, version).into(),
+            Layout::Versioned => Path::new(&name).join(version),
+            Layout::Registry => Path::new(&self.registry_dir()).join(name).join(version),
+        }
+    }
+
+    /// Returns the directory name of the registry for [`Layout::Registry`].
+    fn registry_dir(&self) -> String {
+        if let Some(path) = &self.local_registry {
+            if let Some(file_name) = path.file_name() {
+                return file_name.to_string_lossy().into_owned();
+            }
+        }
+        let url = self.registry_url().trim_start_matches("sparse+");
+        match reqwest::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+        {
+            // The sparse index of crates.io is the same registry.
+            Some(host) if host == "index.crates.io" => "crates.io".to_string(),
+            Some(host) => host,
+            None => "registry".to_string(),
+        }
+    }
+}
+
+/// Creates the parent directories of a package or `.crate` file.
+pub(crate) fn create_parent(path: &Path) -> Result<(), Error> {
+    match path.parent() {
+        Some(parent) => fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create `{}`.", parent.display())),
+        None => Ok(()),
+    }
+}
@@ -5,29 +5,114 @@
 //! `.crate` file from crates.io.
 
 #![warn(missing_docs)]
-use anyhow::{anyhow, bail, Context, Error};
+use anyhow::{Context, Error};
+use checkout::Checkout;
+use error::{error_kind, message_kind, msg, Kind};
+use events::EventHandler;
+use extract_filter::ExtractFilter;
+use extract_limits::{ExtractCount, ExtractLimits};
+use extract_policy::{ExtractPolicies, Unpack};
 use flate2::read::GzDecoder;
+use locate::find_package;
 use regex::Regex;
 use reqwest::StatusCode;
 use serde_json::Value;
 use std::env;
-use std::path::PathBuf;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use store::worktree_name;
 use tar::Archive;
 
 #[macro_use]
 extern crate log;
 
+#[macro_use]
+mod error;
+#[macro_use]
+mod http;
+
+mod api;
+mod archive;
+#[cfg(feature = "async")]
+mod async_clone;
+mod audit;
+mod batch;
+mod cache;
+mod checkout;
+mod config;
+mod diff;
+mod docs_rs;
+mod download;
+mod events;
+mod export;
+mod extract_filter;
+mod extract_limits;
+mod extract_policy;
+mod git_init;
+mod github_archive;
+#[cfg(feature = "gix")]
+mod gix_clone;
+mod history;
+mod inspect;
+mod layout;
+mod locate;
+mod lockfile;
+mod metadata;
+mod open;
+mod patch;
+mod programs;
+mod provenance;
+mod proxy;
+mod registry;
+mod repo_check;
+mod reproducible;
+mod request;
+mod search;
+mod sparse;
+mod store;
+mod temp;
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+mod tls;
+mod update;
+mod vcs_info;
+mod vendor;
+mod versions;
+
+pub use api::{CrateData, CrateResponse, CrateVersion, Dependency, VersionLinks};
+pub use batch::read_request_list;
+pub use diff::SourceDiff;
+pub use error::{CloneError, ErrorDetail};
+pub use events::CloneEvent;
+pub use export::ExportFormat;
+pub use extract_policy::ExtractPolicy;
+pub use http::retry_after;
+pub use inspect::{CrateEntry, CrateListing};
+pub use layout::Layout;
+pub use lockfile::{read_lockfile, LockedPackage};
+pub use patch::find_workspace_manifest;
+pub use provenance::METADATA_FILE;
+pub use request::CloneRequest;
+pub use temp::TempClone;
+pub use versions::{ResolvedVersion, VersionInfo, VersionSelection};
+
 static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
 /// https://api.bitbucket.org/2.0/repositories
-pub const DEFAULT_BITBUCKET_URL: &'static str = "https://api.bitbucket.org/2.0/repositories";
+pub const DEFAULT_BITBUCKET_URL: &str = "https://api.bitbucket.org/2.0/repositories";
+/// https://docs.rs
+pub const DEFAULT_DOCS_RS_URL: &str = "https://docs.rs";
 /// https://github.com
-pub const DEFAULT_GITHUB_URL: &'static str = "https://github.com";
+pub const DEFAULT_GITHUB_URL: &str = "https://github.com";
 /// https://gitlab.com
-pub const DEFAULT_GITLAB_URL: &'static str = "https://gitlab.com";
+pub const DEFAULT_GITLAB_URL: &str = "https://gitlab.com";
 /// https://crates.io
-pub const DEFAULT_REGISTRY_URL: &'static str = "https://crates.io";
+pub const DEFAULT_REGISTRY_URL: &str = "https://crates.io";
 
 /// An enum representation of supported cloning methods.
 #[derive(Debug, Clone)]
@@ -42,6 +127,24 @@ pub enum CloneMethodKind {
     Pijul,
     /// Clones using `fossil`.
     Fossil,
+    /// Checks out using `svn`.
+    Subversion,
+    /// Clones using `darcs`.
+    Darcs,
+    /// Branches using `bzr`.
+    Bazaar,
+    /// Downloads a tarball of a GitHub repository, without using git.
+    GithubArchive,
+    /// Runs a custom command, such as a wrapper around another VCS.
+    Custom {
+        /// The program to run.
+        command: String,
+        /// The arguments, separated by whitespace. `{repo}` is replaced with
+        /// the repository URL, and `{dest}` with the destination directory,
+        /// which is the first extra argument or else the repository name.
+        /// The other extra arguments are appended.
+        args_template: String,
+    },
     /// Attempts to automatically detect which method to use using heuristics.
     Auto,
 }
@@ -55,12 +158,19 @@ impl CloneMethodKind {
             CloneMethodKind::Mercurial => "hg",
             CloneMethodKind::Pijul => "pijul",
             CloneMethodKind::Fossil => "fossil",
+            CloneMethodKind::Subversion => "svn",
+            CloneMethodKind::Darcs => "darcs",
+            CloneMethodKind::Bazaar => "bzr",
+            CloneMethodKind::GithubArchive => "github-archive",
+            CloneMethodKind::Custom { .. } => "custom",
             CloneMethodKind::Auto => "auto",
         }
     }
 
     /// Creates a `CloneMethodKind` from a method name. If no name matches then None is returned.
-    /// Current options are `crate`, `git`, `hg`, `mercurial`, `pijul`, `fossil`, and `auto`
+    /// Current options are `crate`, `git`, `hg`, `mercurial`, `pijul`, `fossil`,
+    /// `svn`, `subversion`, `darcs`, `bzr`, `bazaar`, `github-archive`, and `auto`.
+    /// A [`CloneMethodKind::Custom`] method must be created directly.
     pub fn from(method_name: &str) -> Option<CloneMethodKind> {
         match method_name {
             "crate" => Some(CloneMethodKind::Crate),
@@ -69,18 +179,89 @@ impl CloneMethodKind {
             "mercurial" => Some(CloneMethodKind::Mercurial),
             "pijul" => Some(CloneMethodKind::Pijul),
             "fossil" => Some(CloneMethodKind::Fossil),
+            "svn" => Some(CloneMethodKind::Subversion),
+            "subversion" => Some(CloneMethodKind::Subversion),
+            "darcs" => Some(CloneMethodKind::Darcs),
+            "bzr" => Some(CloneMethodKind::Bazaar),
+            "bazaar" => Some(CloneMethodKind::Bazaar),
+            "github-archive" => Some(CloneMethodKind::GithubArchive),
             "auto" => Some(CloneMethodKind::Auto),
             _ => None,
         }
     }
 }
 
+/// A package that was cloned, returned by [`Cloner::clone`].
+#[derive(Debug, Clone)]
+pub struct ClonedPackage {
+    /// The name of the package.
+    pub name: String,
+    /// The version that was downloaded or checked out.
+    ///
+    /// This is `None` for a VCS clone without a version.
+    pub version: Option<String>,
+    /// The method that was used, after detecting it for
+    /// [`CloneMethodKind::Auto`].
+    pub method: CloneMethodKind,
+    /// The repository URL for VCS methods, or the URL the `.crate` file or
+    /// archive was downloaded from.
+    pub source: String,
+    /// The directory of the package, which may be in a subdirectory of the
+    /// clone, such as a member of a workspace.
+    ///
+    /// With [`Cloner::set_dry_run`], this is where the package would be.
+    pub path: PathBuf,
+    /// The SHA-256 checksum of the `.crate` file, if the registry reported
+    /// one.
+    pub checksum: Option<String>,
+    /// Information about the package from the registry.
+    ///
+    /// This is `None` for a package that was not looked up in the registry,
+    /// from [`Cloner::clone_url`] or [`Cloner::extract_crate_file`].
+    pub summary: Option<PackageSummary>,
+    /// The saved `.crate` file, with [`Cloner::set_keep_archive`] or
+    /// without [`Cloner::set_extract`].
+    pub archive: Option<PathBuf>,
+}
+
+/// Information about a cloned package, in [`ClonedPackage::summary`].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct PackageSummary {
+    /// The description of the package.
+    pub description: Option<String>,
+    /// The SPDX license expression of the cloned version, or of the latest
+    /// version for a VCS clone without a version.
+    pub license: Option<String>,
+    /// The documentation URL of the package.
+    ///
+    /// Packages from crates.io without one link to docs.rs.
+    pub documentation: Option<String>,
+    /// The newest version that is not yanked or a pre-release.
+    pub latest_version: Option<String>,
+}
+
 /// A struct containg all url and workspace information necessary to clone a crate.
 #[derive(Debug, Clone)]
 pub struct Cloner {
     /// Defaults to https://crates.io
     registry_url: String,
 
+    /// A git index, whose web API is used as the registry URL once it has
+    /// been looked up.
+    git_index: Option<String>,
+    /// The web API of `git_index`, shared with clones of the `Cloner`.
+    git_index_api: Arc<OnceLock<String>>,
+    /// The download URL template from the `config.json` of a sparse index,
+    /// shared with clones of the `Cloner`.
+    sparse_dl: Arc<OnceLock<String>>,
+
+    /// Mirrors of the registry, tried in order when it is unavailable.
+    registry_mirrors: Vec<String>,
+
+    /// A local registry directory used in place of the registry.
+    local_registry: Option<PathBuf>,
+
     /// Defaults to https://github.com
     github_url: String,
 
@@ -90,17 +271,273 @@ pub struct Cloner {
     /// Defaults to https://api.bitbucket.org/2.0/repositories
     bitbutcket_url: String,
 
+    /// Hosts of Gitea and Forgejo instances, defaults to codeberg.org.
+    gitea_hosts: Vec<String>,
+
+    /// Hosts of other forges whose repositories are cloned with git.
+    git_hosts: Vec<String>,
+
+    /// The method used in place of `CloneMethodKind::Auto`.
+    default_method: CloneMethodKind,
+
+    /// The methods tried, in order, when cloning with a method fails.
+    fallback_methods: Vec<CloneMethodKind>,
+
+    /// Whether to clone GitHub, GitLab, and Bitbucket repositories over SSH.
+    prefer_ssh: bool,
+
+    /// Whether a GitHub repository is requested to follow a redirect to its
+    /// new location.
+    follow_redirects: bool,
+
+    /// Whether to only log what would be downloaded or run.
+    dry_run: bool,
+
+    /// Whether pre-release versions can be selected.
+    include_prereleases: bool,
+    /// Whether yanked versions can be selected.
+    include_yanked: bool,
+
+    /// Whether a package that is not found is looked up with `-` and `_`
+    /// swapped.
+    fuzzy_name: bool,
+
+    /// Called with each step of a clone.
+    event_handler: Option<EventHandler>,
+
     /// Output directory of the Crate source code.
     ///
     /// Uses `std::env::current_dir()` if `None`.
     out_dir: Option<PathBuf>,
+
+    /// Where to append a record of each clone, and in which format.
+    export: Option<(ExportFormat, PathBuf)>,
+
+    /// The pijul channel to clone.
+    pijul_channel: Option<String>,
+
+    /// Whether to run `fossil open` after a fossil clone.
+    fossil_open: bool,
+
+    /// Whether to clone only the latest commit with a VCS method.
+    shallow: bool,
+
+    /// Whether to clone the submodules of a git repository.
+    recurse_submodules: bool,
+
+    /// Lockfile to take the version from.
+    lockfile: Option<PathBuf>,
+
+    /// Lockfile of the current project, to take the version from when none
+    /// is given.
+    project_lockfile: Option<PathBuf>,
+
+    /// Name of the directory to clone or extract into.
+    dest_name: Option<String>,
+
+    /// Whether to replace an existing destination.
+    force: bool,
+
+    /// Whether to extract the contents of a `.crate` file directly into the
+    /// output directory.
+    strip_prefix: bool,
+
+    /// Which files to extract from a `.crate` file.
+    extract_filter: Option<ExtractFilter>,
+
+    /// Limits on the files extracted from a `.crate` file.
+    extract_limits: ExtractLimits,
+
+    /// How links and unsafe paths in a `.crate` file are extracted.
+    extract_policies: ExtractPolicies,
+
+    /// Whether to write a `.cargo-clone.json` into cloned packages.
+    metadata_file: bool,
+
+    /// Whether to normalize the timestamps and permissions of extracted
+    /// packages.
+    reproducible: bool,
+
+    /// Whether to check that a repository exists before running a VCS.
+    check_repo: bool,
+
+    /// Whether to rebuild a package from docs.rs if its `.crate` file can't
+    /// be downloaded.
+    docs_rs_fallback: bool,
+
+    /// Defaults to https://docs.rs
+    docs_rs_url: String,
+
+    /// Whether [`Cloner::clone_temp`] keeps the temporary directory when the
+    /// clone fails.
+    keep_temp: bool,
+
+    /// The command that [`Cloner::open`] runs, instead of `$VISUAL` or
+    /// `$EDITOR`.
+    open_command: Option<String>,
+
+    /// Whether a git repository is created in an extracted `.crate` file.
+    git_init: bool,
+
+    /// Whether the commit of `git_init` is tagged with the version.
+    git_init_tag: bool,
+
+    /// How packages are arranged in the output directory.
+    layout: Layout,
+
+    /// Whether to extract packages in the layout of `cargo vendor`.
+    vendor: bool,
+
+    /// Whether to save the downloaded `.crate` file.
+    keep_archive: bool,
+
+    /// Whether to extract `.crate` files.
+    extract: bool,
+
+    /// Whether a VCS clone that doesn't contain the package is an error.
+    strict: bool,
+
+    /// Directory of bare git repositories shared between clones.
+    git_store: Option<PathBuf>,
+
+    /// Directory where downloaded `.crate` files are kept.
+    cache_dir: Option<PathBuf>,
+
+    /// Whether `.crate` files in cargo's registry cache are used.
+    cargo_cache: bool,
+
+    /// When set to `true`, in-progress downloads are abandoned.
+    cancel: Option<Arc<AtomicBool>>,
+
+    /// Token sent with requests to the registry.
+    registry_token: Option<String>,
+
+    /// Proxy for HTTP requests.
+    ///
+    /// Uses cargo's `http.proxy` setting if `None`.
+    proxy: Option<String>,
+
+    /// Hosts that are not reached through the proxy.
+    no_proxy: Option<String>,
+
+    /// How long to wait for a connection.
+    connect_timeout: Option<Duration>,
+
+    /// How long a request may take.
+    timeout: Option<Duration>,
+
+    /// How many times a request is retried after a transient failure.
+    retries: u32,
+
+    /// Extra certificate authorities to trust.
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    root_certificates: Vec<reqwest::Certificate>,
+
+    /// Client certificate to authenticate with.
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    client_identity: Option<reqwest::Identity>,
+
+    /// Whether invalid server certificates are accepted.
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    accept_invalid_certs: bool,
+
+    /// Client for HTTP requests supplied by the user.
+    http_client: Option<reqwest::blocking::Client>,
+
+    /// Client for async HTTP requests supplied by the user.
+    #[cfg(feature = "async")]
+    async_http_client: Option<reqwest::Client>,
+
+    /// How many packages to clone at the same time.
+    jobs: usize,
+
+    /// The branch, tag, or revision to check out with a VCS method.
+    checkout: Option<Checkout>,
+}
+
+/// A `.crate` file selected for download.
+#[derive(Clone)]
+struct CrateDownload {
+    /// The name of the package as published, which `.crate` files are named
+    /// after.
+    name: String,
+    /// The version to download.
+    version: String,
+    /// The download URL.
+    url: String,
+    /// The SHA-256 checksum reported by the registry.
+    checksum: Option<String>,
+}
+
+/// What [`Cloner::match_repo`] found for a repository URL.
+pub(crate) enum RepoMatch<'a> {
+    /// The method and URL to clone with.
+    Found(CloneMethodKind, String),
+    /// A GitHub repository, with its owner and name, which may have moved.
+    GitHub(&'a str, &'a str),
+    /// A Bitbucket repository, with its user and name, whose VCS is looked
+    /// up with the Bitbucket API.
+    Bitbucket(&'a str, &'a str),
+}
+
+/// What is left of a `.crate` download after [`Cloner::prepare_crate`].
+pub(crate) enum CrateStep {
+    /// Nothing, such as for a dry run or a cached `.crate` file.
+    Done(ClonedPackage),
+    /// Downloading the `.crate` file.
+    Download(PendingCrate),
+}
+
+/// A `.crate` file to download, as set up by [`Cloner::prepare_crate`].
+pub(crate) struct PendingCrate {
+    download: CrateDownload,
+    /// Where to save the `.crate` file, if it is kept.
+    archive: Option<PathBuf>,
+    /// Where the package is extracted to, or the `.crate` file is saved to.
+    dest: PathBuf,
+    /// The download's file name without the extension, such as
+    /// `bitflags-1.0.5`.
+    base: String,
+}
+
+impl PendingCrate {
+    /// The download URL.
+    pub(crate) fn url(&self) -> &str {
+        &self.download.url
+    }
+
+    /// The SHA-256 checksum reported by the registry.
+    pub(crate) fn checksum(&self) -> Option<&str> {
+        self.download.checksum.as_deref()
+    }
+
+    /// The download's file name without the extension.
+    pub(crate) fn base(&self) -> &str {
+        &self.base
+    }
+}
+
+impl CrateDownload {
+    /// Describes the package once extracted into `path`.
+    fn into_package(self, name: &str, path: PathBuf) -> ClonedPackage {
+        ClonedPackage {
+            name: name.to_string(),
+            version: Some(self.version),
+            method: CloneMethodKind::Crate,
+            source: self.url,
+            path,
+            checksum: self.checksum,
+            summary: None,
+            archive: None,
+        }
+    }
 }
 
 fn check_semver_req(version: &str) -> Result<String, Error> {
     let first = version
         .chars()
-        .nth(0)
-        .ok_or_else(|| anyhow!("version is empty"))?;
+        .next()
+        .ok_or_else(|| msg(Kind::Version, "version is empty"))?;
 
     let is_req = "<>=^~".contains(first) || version.contains('*');
     if is_req {
@@ -108,39 +545,371 @@ fn check_semver_req(version: &str) -> Result<String, Error> {
     } else {
         match semver::Version::parse(version) {
             Ok(v) => Ok(format!("={}", v)),
-            Err(e) => Err(e).context(anyhow!(
-                "`{}` is not a valid semver version.\n\
-                 Use an exact version like 1.2.3 or a version requirement expression.",
-                version
+            Err(e) => Err(e).context(msg(
+                Kind::Version,
+                format!(
+                    "`{}` is not a valid semver version.\n\
+                     Use an exact version like 1.2.3 or a version requirement expression.",
+                    version
+                ),
             ))?,
         }
     }
 }
 
+/// Splits a `name[:@]version` spec into the name and a version requirement.
+///
+/// The spec may also be a link to the package on crates.io, docs.rs, or
+/// lib.rs, with the version in the link if it has one, or a package URL
+/// (purl) such as `pkg:cargo/serde@1.0.200`.
+fn parse_spec<'a>(
+    spec: &'a str,
+    version: Option<&str>,
+) -> Result<(&'a str, Option<String>), Error> {
+    let (name, spec_version_req) = if spec.starts_with("http://") || spec.starts_with("https://") {
+        let (name, version) = parse_package_url(spec)?;
+        (name, version.map(str::to_string))
+    } else if spec.starts_with("pkg:") {
+        parse_purl(spec)?
+    } else {
+        let mut parts = spec.splitn(2, &[':', '@']);
+        (parts.next().unwrap(), parts.next().map(str::to_string))
+    };
+    if spec_version_req.is_some() && version.is_some() {
+        fail!(
+            InvalidInput,
+            "Cannot specify both a :version and --version."
+        );
+    }
+    let version_req = version
+        .or(spec_version_req.as_deref())
+        .map(check_semver_req)
+        .transpose()?;
+    Ok((name, version_req))
+}
+
+/// Splits a package URL (purl) of a cargo package, such as
+/// `pkg:cargo/serde@1.0.200`, into the name and version. Qualifiers and a
+/// subpath are ignored.
+fn parse_purl(purl: &str) -> Result<(&str, Option<String>), Error> {
+    // UNWRAP: The first part of a split always exists.
+    let purl = purl.split(['?', '#']).next().unwrap();
+    let path = purl["pkg:".len()..].trim_start_matches('/');
+    let (ty, path) = path.split_once('/').unwrap_or((path, ""));
+    if !ty.eq_ignore_ascii_case("cargo") {
+        fail!(
+            InvalidInput,
+            "`{}` is not a package URL of a cargo package, which starts with `pkg:cargo/`.",
+            purl
+        );
+    }
+    let (name, version) = match path.rsplit_once('@') {
+        Some((name, version)) => (name, Some(percent_decode(version)?)),
+        None => (path, None),
+    };
+    // Cargo packages have no namespace.
+    if name.is_empty() || name.contains('/') {
+        fail!(InvalidInput, "Package URL `{}` has no package name.", purl);
+    }
+    Ok((name, version))
+}
+
+/// Decodes the `%XX` escapes of a component of a package URL.
+fn percent_decode(s: &str) -> Result<String, Error> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        if b != b'%' {
+            bytes.push(b);
+            continue;
+        }
+        let hex = rest.get(..2).and_then(|h| std::str::from_utf8(h).ok());
+        let Some(b) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) else {
+            fail!(
+                InvalidInput,
+                "Invalid escape in package URL component `{}`.",
+                s
+            );
+        };
+        bytes.push(b);
+        rest = &rest[2..];
+    }
+    String::from_utf8(bytes)
+        .map_err(|_| msg(Kind::InvalidInput, format!("`{}` is not valid UTF-8.", s)).into())
+}
+
+/// Splits a link to a package on crates.io, docs.rs, or lib.rs into the name
+/// and the version, if the link is to a version of the package.
+fn parse_package_url(url: &str) -> Result<(&str, Option<&str>), Error> {
+    let Some(c) = Regex::new(concat!(
+        r"^https?://(?:www\.)?(?:crates\.io/crates|lib\.rs/crates|docs\.rs(?:/crate)?)",
+        r"/([^/?#]+)(?:/([^/?#]+))?"
+    ))
+    .unwrap()
+    .captures(url) else {
+        fail!(
+            InvalidInput,
+            "`{}` is not a link to a package on crates.io, docs.rs, or lib.rs.",
+            url
+        );
+    };
+    // Links may also be to `latest`, or to a page such as `versions`.
+    let version = c
+        .get(2)
+        .map(|v| v.as_str())
+        .filter(|v| semver::Version::parse(v).is_ok());
+    Ok((c.get(1).unwrap().as_str(), version))
+}
+
+/// Reads the name and version of the package in a `.crate` file, from the
+/// `<name>-<version>` directory of its first entry.
+fn crate_file_package(path: &Path, file: impl Read) -> Result<(String, String), Error> {
+    let mut tar = Archive::new(GzDecoder::new(file));
+    let first = (|| -> std::io::Result<Option<PathBuf>> {
+        match tar.entries()?.next() {
+            Some(entry) => Ok(Some(entry?.path()?.into_owned())),
+            None => Ok(None),
+        }
+    })()
+    .context(msg(
+        Kind::Extract,
+        format!("Failed to read `{}` as a `.crate` file.", path.display()),
+    ))?;
+    let base = first
+        .as_ref()
+        .and_then(|p| p.components().next())
+        .and_then(|c| c.as_os_str().to_str());
+    // Both names and versions may contain `-`, so split at the first one
+    // that is followed by a valid version.
+    let package = base.and_then(|base| {
+        base.match_indices('-')
+            .map(|(i, _)| (&base[..i], &base[i + 1..]))
+            .find(|(name, version)| !name.is_empty() && semver::Version::parse(version).is_ok())
+    });
+    match package {
+        Some((name, version)) => Ok((name.to_string(), version.to_string())),
+        None => fail!(
+            Extract,
+            "Expected a `<name>-<version>` directory in `{}`.",
+            path.display()
+        ),
+    }
+}
+
+/// Finds the largest version in the package info matching the requirement.
+///
+/// Pre-releases are skipped unless `include_pre` is set, or the requirement
+/// names a pre-release. Without a requirement, a package that only has
+/// pre-releases still selects the newest one. Yanked versions are skipped
+/// unless `include_yanked` is set, or only yanked versions match.
+fn select_version<'a>(
+    pkg_info: &'a CrateResponse,
+    version_req: Option<&str>,
+    include_pre: bool,
+    include_yanked: bool,
+) -> Result<&'a CrateVersion, Error> {
+    let versions =
+        pkg_info.versions.iter().filter_map(|crate_version| {
+            match semver::Version::parse(&crate_version.num) {
+                Ok(v) => Some((crate_version, v)),
+                Err(e) => {
+                    debug!("Skipping invalid version `{}`: {}", crate_version.num, e);
+                    None
+                }
+            }
+        });
+    let mut versions: Vec<_> = if let Some(version_req) = version_req {
+        let req = semver::VersionReq::parse(version_req)?;
+        versions
+            .filter(|(_crate_version, ver)| {
+                // `VersionReq` only matches pre-releases it names, so with
+                // `include_pre` the pre-release is matched as its release.
+                req.matches(ver)
+                    || (include_pre
+                        && !ver.pre.is_empty()
+                        && req.matches(&semver::Version::new(ver.major, ver.minor, ver.patch)))
+            })
+            .collect()
+    } else {
+        let versions: Vec<_> = versions.collect();
+        if include_pre || versions.iter().all(|(_, ver)| !ver.pre.is_empty()) {
+            versions
+        } else {
+            versions
+                .into_iter()
+                .filter(|(_crate_version, ver)| ver.pre.is_empty())
+                .collect()
+        }
+    };
+    // Find the largest version.
+    if versions.is_empty() {
+        fail!(Version, "Could not find any matching versions.");
+    }
+    versions.sort_unstable_by_key(|x| x.1.clone());
+    let yanked = !include_yanked && versions.iter().all(|(v, _)| v.yanked);
+    if !include_yanked && !yanked {
+        versions.retain(|(v, _)| !v.yanked);
+    }
+    let (selected, ver) = versions.last().unwrap();
+    if yanked {
+        warn!(
+            "Only yanked versions of `{}` match, using yanked version {}.",
+            pkg_info.krate.name, ver
+        );
+    }
+    debug!(
+        "Selected version {} out of {} candidates matching {}",
+        ver,
+        versions.len(),
+        version_req.unwrap_or("*")
+    );
+    Ok(selected)
+}
+
+/// Returns the path of a project from the path of a URL, such as
+/// `group/subgroup/project` from `group/subgroup/project/-/tree/main`.
+///
+/// GitLab projects can be nested in any number of groups, so everything
+/// before the `/-/` separator of GitLab's pages is part of the project path.
+/// GitHub's `/tree/` and `/blob/` pages are also removed.
+fn project_path(path: &str) -> Option<&str> {
+    let end = ["/-/", "/tree/", "/blob/"]
+        .iter()
+        .filter_map(|sep| path.find(sep))
+        .min()
+        .unwrap_or(path.len());
+    let path = path[..end].trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    path.contains('/').then_some(path)
+}
+
+/// Returns the directory a deep repository link points to, such as
+/// `crates/foo` from `https://github.com/org/repo/tree/master/crates/foo`.
+///
+/// Packages in a monorepo often link to their directory this way. The ref
+/// after `/tree/` is assumed to have no `/`.
+fn repo_subdir(repo: &str) -> Option<&str> {
+    let re =
+        Regex::new(r"^https?://[^/]+/[^#?]+?/(?:-/)?tree/[^/#?]+/([^#?]+?)/?(?:[#?].*)?$").unwrap();
+    Some(re.captures(repo)?.get(1).unwrap().as_str())
+}
+
+/// Splits a channel off of a nest.pijul.com URL such as
+/// `https://nest.pijul.com/owner/repo:channel` or `…/repo#channel`.
+fn split_pijul_channel(repo: &str) -> (&str, Option<&str>) {
+    let re = Regex::new(r"^(https://nest\.pijul\.com/[^/]+/[^/:#]+)[:#]([^/:#]+)$").unwrap();
+    match re.captures(repo) {
+        Some(c) => (c.get(1).unwrap().as_str(), Some(c.get(2).unwrap().as_str())),
+        None => (repo, None),
+    }
+}
+
 /// Determine the repo path from the package info.
-fn get_repo(pkg_info: &Value) -> Result<Option<String>, Error> {
-    let krate = pkg_info
-        .get("crate")
-        .ok_or_else(|| anyhow!("`crate` expected in pkg info"))?;
-    let repo = &krate["repository"];
-    if repo.is_string() {
-        return Ok(Some(repo.as_str().unwrap().to_string()));
+fn get_repo(pkg_info: &CrateResponse) -> Option<String> {
+    let krate = &pkg_info.krate;
+    krate.repository.clone().or_else(|| krate.homepage.clone())
+}
+
+/// Adds the `suggestions` for a package that was not found to its error.
+fn not_found_error(e: Error, suggestions: Vec<String>) -> Error {
+    if suggestions.is_empty() {
+        return e;
+    }
+    let suggestions: Vec<_> = suggestions.iter().map(|s| format!("`{}`", s)).collect();
+    msg(
+        Kind::NotFound,
+        format!("{} Did you mean {}?", e, suggestions.join(" or ")),
+    )
+    .into()
+}
+
+/// Checks the status of a request to the Bitbucket API.
+fn check_bitbucket_status(api_url: &str, code: StatusCode) -> Result<(), Error> {
+    if !code.is_success() {
+        fail!(
+            Http,
+            "Failed to get repo info from bitbucket API `{}`: `{}`",
+            api_url,
+            code
+        );
+    }
+    Ok(())
+}
+
+/// Returns the repository whose method is detected when selecting a method,
+/// if it is needed.
+fn repo_to_detect<'a>(
+    method_kind: &CloneMethodKind,
+    version_req: Option<&str>,
+    repo: Option<&'a str>,
+) -> Option<&'a str> {
+    match method_kind {
+        // A version is only available as a `.crate` file.
+        CloneMethodKind::Auto if version_req.is_some() => None,
+        CloneMethodKind::Crate => None,
+        _ => repo,
+    }
+}
+
+/// Returns the name a package was found by, which is `name` unless it was
+/// found with `-` and `_` swapped.
+fn found_name<'a>(name: &'a str, pkg_info: &'a CrateResponse) -> &'a str {
+    if pkg_info.krate.name.eq_ignore_ascii_case(name) {
+        name
+    } else {
+        &pkg_info.krate.name
+    }
+}
+
+/// Checks the HTTP status of a package info request.
+fn check_pkg_info_status(name: &str, code: StatusCode) -> Result<(), Error> {
+    check_auth_status(code)?;
+    match code {
+        StatusCode::OK => Ok(()),
+        StatusCode::NOT_FOUND => fail!(NotFound, "Package `{}` not found on crates.io.", name),
+        StatusCode::TOO_MANY_REQUESTS => fail!(
+            Http,
+            "Rate limited by crates.io: `{}`\n\
+             Try again later, or with fewer `--jobs` or more `--retries`.",
+            code
+        ),
+        _ => fail!(
+            Http,
+            "Failed to get package info from crates.io: `{}`",
+            code
+        ),
     }
-    let home = &krate["homepage"];
-    if home.is_string() {
-        return Ok(Some(home.as_str().unwrap().to_string()));
+}
+
+/// Checks if a registry request was rejected for lack of authentication.
+fn check_auth_status(code: StatusCode) -> Result<(), Error> {
+    if code == StatusCode::UNAUTHORIZED || code == StatusCode::FORBIDDEN {
+        fail!(
+            Http,
+            "The registry requires authentication: `{}`\n\
+             Set a token with `CARGO_REGISTRIES_<NAME>_TOKEN` or `--config registry.token`.",
+            code
+        );
     }
-    Ok(None)
+    Ok(())
 }
 
-/// A wrapper around `reqwest::blocking::get` that provides a User Agent. This
-/// is required by crates.io
-fn reqwest_get(url: &str) -> reqwest::Result<reqwest::blocking::Response> {
-    let client = reqwest::blocking::Client::builder()
-        .user_agent(APP_USER_AGENT)
-        .build()?;
+/// Sends a request, logging it and its response status.
+fn send_request(
+    url: &str,
+    request: reqwest::blocking::RequestBuilder,
+) -> reqwest::Result<reqwest::blocking::Response> {
+    debug!("GET {url}");
+    let response = request.send()?;
+    debug!("GET {url} -> {}", response.status());
+    Ok(response)
+}
 
-    client.get(url).send()
+impl Default for Cloner {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Cloner {
@@ -148,16 +917,91 @@ impl Cloner {
     pub fn new() -> Cloner {
         Cloner {
             registry_url: DEFAULT_REGISTRY_URL.to_string(),
+            git_index: None,
+            git_index_api: Arc::default(),
+            sparse_dl: Arc::default(),
+            registry_mirrors: Vec::new(),
+            local_registry: None,
             github_url: DEFAULT_GITHUB_URL.to_string(),
             gitlab_url: DEFAULT_GITLAB_URL.to_string(),
             bitbutcket_url: DEFAULT_BITBUCKET_URL.to_string(),
+            gitea_hosts: vec!["codeberg.org".to_string()],
+            git_hosts: Vec::new(),
+            default_method: CloneMethodKind::Auto,
+            fallback_methods: Vec::new(),
+            prefer_ssh: false,
+            follow_redirects: false,
+            dry_run: false,
+            include_prereleases: false,
+            include_yanked: false,
+            fuzzy_name: false,
+            event_handler: None,
             out_dir: None,
+            export: None,
+            pijul_channel: None,
+            fossil_open: true,
+            shallow: false,
+            recurse_submodules: false,
+            lockfile: None,
+            project_lockfile: None,
+            dest_name: None,
+            force: false,
+            strip_prefix: false,
+            extract_filter: None,
+            extract_limits: ExtractLimits::default(),
+            extract_policies: ExtractPolicies::default(),
+            metadata_file: true,
+            reproducible: false,
+            check_repo: true,
+            docs_rs_fallback: false,
+            docs_rs_url: DEFAULT_DOCS_RS_URL.to_string(),
+            keep_temp: false,
+            open_command: None,
+            git_init: false,
+            git_init_tag: false,
+            layout: Layout::Flat,
+            vendor: false,
+            keep_archive: false,
+            extract: true,
+            strict: false,
+            git_store: None,
+            cache_dir: None,
+            cargo_cache: true,
+            cancel: None,
+            registry_token: None,
+            proxy: None,
+            no_proxy: None,
+            connect_timeout: None,
+            timeout: None,
+            retries: 4,
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            root_certificates: Vec::new(),
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            client_identity: None,
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            accept_invalid_certs: false,
+            http_client: None,
+            #[cfg(feature = "async")]
+            async_http_client: None,
+            jobs: 1,
+            checkout: None,
         }
     }
 
     /// Sets the URL to use for downloading `.crate` files from crates.io.
+    ///
+    /// A URL starting with `sparse+`, such as `sparse+https://index.crates.io/`,
+    /// is read as a sparse registry index instead of the crates.io web API.
+    /// The sparse index has no repository information, so the `auto` method
+    /// always downloads the `.crate` file.
+    ///
+    /// This replaces any local registry set with
+    /// [`Cloner::set_local_registry`].
     pub fn set_registry_url(&mut self, value: impl Into<String>) -> &mut Self {
         self.registry_url = value.into();
+        self.git_index = None;
+        self.sparse_dl = Arc::default();
+        self.local_registry = None;
         self
     }
 
@@ -179,6 +1023,169 @@ impl Cloner {
         self
     }
 
+    /// Adds the host of a Gitea or Forgejo instance, such as
+    /// `gitea.example.com`.
+    ///
+    /// Repository URLs like `https://<host>/<owner>/<repo>` on these hosts are
+    /// cloned with git. codeberg.org is always included.
+    pub fn add_gitea_host(&mut self, host: impl Into<String>) -> &mut Self {
+        self.gitea_hosts.push(host.into());
+        self
+    }
+
+    /// Adds the host of a forge whose repositories are cloned with git, such
+    /// as a GitHub Enterprise or self-hosted GitLab instance at
+    /// `git.example.com`.
+    ///
+    /// The repository is cloned from the project path of its URL with `.git`
+    /// appended. Projects may be nested in groups, as with GitLab.
+    pub fn add_git_host(&mut self, host: impl Into<String>) -> &mut Self {
+        self.git_hosts.push(host.into());
+        self
+    }
+
+    /// Sets the method used in place of [`CloneMethodKind::Auto`].
+    ///
+    /// For example, with [`CloneMethodKind::Crate`], packages are downloaded
+    /// from the registry unless another method is asked for. Defaults to
+    /// [`CloneMethodKind::Auto`], which detects the method.
+    pub fn set_default_method(&mut self, value: CloneMethodKind) -> &mut Self {
+        self.default_method = value;
+        self
+    }
+
+    /// Sets the methods tried, in order, when cloning with the requested
+    /// method fails.
+    ///
+    /// A method falls back to the next one when its VCS command can't be
+    /// run or fails, a download fails, or the package has no repository.
+    /// Without fallback methods, [`CloneMethodKind::Auto`] falls back to
+    /// [`CloneMethodKind::Crate`] when a VCS clone fails, unless there are
+    /// extra arguments or a checkout for the VCS.
+    pub fn set_fallback_methods(&mut self, value: Vec<CloneMethodKind>) -> &mut Self {
+        self.fallback_methods = value;
+        self
+    }
+
+    /// Sets whether git repositories on GitHub, GitLab, and Bitbucket are
+    /// cloned over SSH, with URLs like `git@github.com:owner/repo.git`.
+    ///
+    /// Defaults to `false`, which clones over HTTPS.
+    pub fn set_prefer_ssh(&mut self, value: bool) -> &mut Self {
+        self.prefer_ssh = value;
+        self
+    }
+
+    /// Sets whether a GitHub repository that was renamed or transferred is
+    /// cloned from its new location.
+    ///
+    /// This sends a request to the repository URL to see where GitHub
+    /// redirects it, which is skipped with [`Cloner::set_dry_run`]. Defaults
+    /// to `false`, which clones from the URL of the package.
+    pub fn set_follow_redirects(&mut self, value: bool) -> &mut Self {
+        self.follow_redirects = value;
+        self
+    }
+
+    /// Sets whether to only resolve what would be cloned.
+    ///
+    /// Defaults to `false`. When `true`, the package information, version,
+    /// and method are resolved as usual, and the download or VCS command
+    /// that would be run is logged, but nothing is written and no VCS is
+    /// run.
+    pub fn set_dry_run(&mut self, value: bool) -> &mut Self {
+        self.dry_run = value;
+        self
+    }
+
+    /// Sets the name of the directory the package is cloned or extracted
+    /// into, within the output directory.
+    ///
+    /// By default, a `.crate` file is extracted to `<name>-<version>`, and a
+    /// VCS clones into its default directory, usually named after the
+    /// repository. For VCS methods, the name is passed as the directory
+    /// argument, so it cannot be combined with a directory in the extra
+    /// arguments. A fossil repository is cloned to `<dest_name>.fossil`.
+    pub fn set_dest_name(&mut self, value: impl Into<String>) -> &mut Self {
+        self.dest_name = Some(value.into());
+        self
+    }
+
+    /// Sets whether the contents of a `.crate` file are extracted directly
+    /// into the output directory, without the `<name>-<version>` directory.
+    ///
+    /// Defaults to `false`. This only works with the `crate` method. Files
+    /// that already exist in the output directory are an error, unless
+    /// replaced with [`Cloner::set_force`].
+    pub fn set_strip_prefix(&mut self, value: bool) -> &mut Self {
+        self.strip_prefix = value;
+        self
+    }
+
+    /// Sets whether it is an error if a VCS clone does not contain the
+    /// package.
+    ///
+    /// After a VCS clone, the `Cargo.toml` files of the repository are read
+    /// to find the package. Defaults to `false`, which only logs a warning
+    /// if it is not found, since the `repository` of a package may be wrong
+    /// or outdated. When `true`, this fails with
+    /// [`CloneError::Repository`], keeping the clone.
+    pub fn set_strict(&mut self, value: bool) -> &mut Self {
+        self.strict = value;
+        self
+    }
+
+    /// Sets whether an existing destination is removed before cloning.
+    ///
+    /// Defaults to `false`, which fails with
+    /// [`CloneError::DestinationExists`] if the destination exists. A
+    /// custom method's destination is not checked, since it depends on the
+    /// command.
+    pub fn set_force(&mut self, value: bool) -> &mut Self {
+        self.force = value;
+        self
+    }
+
+    /// Sets whether pre-release versions, such as `2.0.0-beta.1`, can be
+    /// selected.
+    ///
+    /// Defaults to `false`, which selects the newest stable release, unless
+    /// the version requirement names a pre-release or there are only
+    /// pre-releases.
+    pub fn set_include_prereleases(&mut self, value: bool) -> &mut Self {
+        self.include_prereleases = value;
+        self
+    }
+
+    /// Sets whether yanked versions can be selected like any other version.
+    ///
+    /// Defaults to `false`, which skips yanked versions, unless only yanked
+    /// versions match the version requirement, such as an exact version that
+    /// was yanked. That version is then used, with a warning.
+    pub fn set_include_yanked(&mut self, value: bool) -> &mut Self {
+        self.include_yanked = value;
+        self
+    }
+
+    /// Sets whether a package that is not found is looked up again with `-`
+    /// and `_` swapped, such as `serde_json` for `serde-json`.
+    ///
+    /// The registry treats these as different names. Defaults to `false`,
+    /// where the swapped name is only suggested in the error.
+    pub fn set_fuzzy_name(&mut self, value: bool) -> &mut Self {
+        self.fuzzy_name = value;
+        self
+    }
+
+    /// Returns the prefix of the log message for a command that is run.
+    pub(crate) fn running(&self) -> &'static str {
+        if self.dry_run {
+            "Would run"
+        } else {
+            "Running"
+        }
+    }
+
     /// Sets the directory where the clone will be done.
     ///
     /// The package will appear as a directory underneath the given path.
@@ -187,12 +1194,71 @@ impl Cloner {
         self
     }
 
+    /// Sets the channel to clone with the `pijul` method.
+    ///
+    /// If not set, a channel given in a nest.pijul.com repository URL (such
+    /// as `https://nest.pijul.com/owner/repo:channel`) is used, otherwise the
+    /// default channel.
+    pub fn set_pijul_channel(&mut self, value: impl Into<String>) -> &mut Self {
+        self.pijul_channel = Some(value.into());
+        self
+    }
+
+    /// Sets whether a fossil clone is opened into a checkout directory.
+    ///
+    /// Defaults to `true`. When `false`, only the `.fossil` repository file
+    /// is created.
+    pub fn set_fossil_open(&mut self, value: bool) -> &mut Self {
+        self.fossil_open = value;
+        self
+    }
+
+    /// Sets whether a VCS clone only fetches the latest commit.
+    ///
+    /// Defaults to `false`. This is supported by git, and passes
+    /// `--depth=1`. Other methods, and git with a store (see
+    /// [`Cloner::set_git_store`]), clone the full history with a warning.
+    pub fn set_shallow(&mut self, value: bool) -> &mut Self {
+        self.shallow = value;
+        self
+    }
+
+    /// Sets whether a git clone also clones the repository's submodules,
+    /// recursively.
+    ///
+    /// Defaults to `false`. Other methods ignore this with a warning.
+    pub fn set_recurse_submodules(&mut self, value: bool) -> &mut Self {
+        self.recurse_submodules = value;
+        self
+    }
+
+    /// Sets a flag that can be used to cancel a clone from another thread,
+    /// for example from a Ctrl-C handler.
+    ///
+    /// When the flag becomes `true`, a `.crate` extraction in progress stops
+    /// with an error, and the partially extracted directory is removed.
+    pub fn set_cancel_flag(&mut self, flag: Arc<AtomicBool>) -> &mut Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    /// Returns an error if the clone has been cancelled.
+    fn check_cancelled(&self) -> Result<(), Error> {
+        if let Some(cancel) = &self.cancel {
+            if cancel.load(Ordering::SeqCst) {
+                fail!(Cancelled, "Interrupted.");
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the output directory.
-    fn out_dir(&self) -> Result<PathBuf, Error> {
+    pub fn out_dir(&self) -> Result<PathBuf, CloneError> {
         Ok(self
             .out_dir
             .as_ref()
-            .map_or_else(|| env::current_dir(), |v| Ok(v.to_path_buf()))?)
+            .map_or_else(env::current_dir, |v| Ok(v.to_path_buf()))
+            .context("Failed to get the current directory.")?)
     }
 
     /// Clones a crate using the provided method.
@@ -202,260 +1268,1640 @@ impl Cloner {
     /// - `version` - The semantic version (semver) of the spec crate to clone
     /// - `extra` - Additional arguments passed to clone command.
     ///
+    /// Returns what was cloned and where.
     pub fn clone(
         &self,
         method_kind: CloneMethodKind,
         spec: &str,
         version: Option<&str>,
         extra: &[&str],
-    ) -> Result<(), Error> {
-        let mut parts = spec.splitn(2, &[':', '@']);
-        let name = parts.next().unwrap();
-        let spec_version_req = parts.next();
-        if spec_version_req.is_some() && version.is_some() {
-            bail!("Cannot specify both a :version and --version.");
-        }
-        let version_req = version
-            .or(spec_version_req)
-            .map(check_semver_req)
-            .transpose()?;
+    ) -> Result<ClonedPackage, CloneError> {
+        Ok(self.clone_inner(method_kind, spec, version, extra)?)
+    }
+
+    fn clone_inner(
+        &self,
+        method_kind: CloneMethodKind,
+        spec: &str,
+        version: Option<&str>,
+        extra: &[&str],
+    ) -> Result<ClonedPackage, Error> {
+        let (name, version_req) = self.parse_request(spec, version)?;
+        self.emit_resolving(name, version_req.as_deref());
         let pkg_info = self.get_pkg_info(name)?;
-        let repo = get_repo(&pkg_info)?;
+        let name = found_name(name, &pkg_info);
+        let methods = self.method_chain(method_kind, extra);
+        self.clone_methods(name, &methods, version_req.as_deref(), extra, &pkg_info)
+    }
+
+    /// Clones a package with the first of `methods` that works.
+    fn clone_methods(
+        &self,
+        name: &str,
+        methods: &[CloneMethodKind],
+        version_req: Option<&str>,
+        extra: &[&str],
+        pkg_info: &CrateResponse,
+    ) -> Result<ClonedPackage, Error> {
+        let mut tried: Vec<String> = Vec::new();
+        let mut error = None;
+        for (i, method_kind) in methods.iter().enumerate() {
+            let mut method_name = method_kind.command().to_string();
+            let result = self
+                .select_method(method_kind.clone(), name, version_req, pkg_info)
+                .and_then(|(method, repo)| {
+                    method_name = method.command().to_string();
+                    // Auto may have selected a method that already failed.
+                    if tried.contains(&method_name) {
+                        return Ok(None);
+                    }
+                    tried.push(method_name.clone());
+                    self.clone_with(name, method, &repo, version_req, extra, pkg_info)
+                        .map(Some)
+                });
+            let e = match result {
+                Ok(Some(package)) => return Ok(package),
+                Ok(None) => continue,
+                Err(e) => e,
+            };
+            error = Some(self.fall_back(name, &methods[i + 1..], &tried, &method_name, e)?);
+        }
+        // UNWRAP: A method is only skipped after another one failed.
+        Err(error.unwrap())
+    }
+
+    /// Checks whether to try the next of the `remaining` methods after the
+    /// method `method_name` failed with `e`, returning the error if not.
+    pub(crate) fn fall_back(
+        &self,
+        name: &str,
+        remaining: &[CloneMethodKind],
+        tried: &[String],
+        method_name: &str,
+        e: Error,
+    ) -> Result<Error, Error> {
+        let next = remaining
+            .iter()
+            .find(|m| !tried.iter().any(|t| t == m.command()));
+        match next {
+            Some(next) if self.can_fall_back(&e) => {
+                warn!(
+                    "Cloning `{}` with the `{}` method failed ({}), trying the `{}` method.",
+                    name,
+                    method_name,
+                    e,
+                    next.command()
+                );
+                Ok(e)
+            }
+            _ => Err(e),
+        }
+    }
+
+    /// Returns the methods to try in order, starting with `method_kind`.
+    pub(crate) fn method_chain(
+        &self,
+        method_kind: CloneMethodKind,
+        extra: &[&str],
+    ) -> Vec<CloneMethodKind> {
+        let auto_fallback = self.fallback_methods.is_empty()
+            && matches!(method_kind, CloneMethodKind::Auto)
+            && extra.is_empty()
+            && self.checkout.is_none();
+        let mut methods = vec![method_kind];
+        if auto_fallback {
+            methods.push(CloneMethodKind::Crate);
+        }
+        methods.extend(self.fallback_methods.iter().cloned());
+        methods
+    }
+
+    /// Returns whether a failed clone is tried again with a fallback method.
+    fn can_fall_back(&self, e: &Error) -> bool {
+        match error_kind(e) {
+            Kind::Vcs | Kind::Http => true,
+            // With strict, a clone without the package is kept.
+            Kind::Repository => !self.strict,
+            _ => false,
+        }
+    }
+
+    /// Clones a package with a method selected by [`Cloner::select_method`].
+    fn clone_with(
+        &self,
+        name: &str,
+        method: CloneMethodKind,
+        repo: &str,
+        version_req: Option<&str>,
+        extra: &[&str],
+        pkg_info: &CrateResponse,
+    ) -> Result<ClonedPackage, Error> {
+        self.emit_method(name, &method, repo);
+        let package = match method {
+            CloneMethodKind::Crate => {
+                let version_req = self.crate_request(name, version_req, extra)?;
+                self.clone_crate(name, version_req.as_deref(), pkg_info)?
+            }
+            _ => self.clone_vcs(name, &method, repo, version_req, extra, pkg_info)?,
+        };
+        self.finish_clone(package, Some(pkg_info))
+    }
+
+    /// Checks the arguments of a crate download, and returns the version
+    /// requirement to download.
+    pub(crate) fn crate_request(
+        &self,
+        name: &str,
+        version_req: Option<&str>,
+        extra: &[&str],
+    ) -> Result<Option<String>, Error> {
+        if !extra.is_empty() {
+            fail!(
+                InvalidInput,
+                "Got extra arguments, crate downloads take no extra arguments."
+            );
+        }
+        self.check_crate_checkout()?;
+        Ok(self.crate_version_req(name, version_req))
+    }
+
+    /// Clones a repository from its URL, without looking up a package in
+    /// the registry.
+    ///
+    /// This works like [`Cloner::clone`] for a package whose repository is
+    /// `url`, such as for a package that has not been published yet. With
+    /// [`CloneMethodKind::Auto`], the method is detected from the URL. The
+    /// package is looked for in the clone by the name of the repository.
+    ///
+    /// - `method_kind` - Method to clone with, any but `Crate`.
+    /// - `url` - The repository URL
+    /// - `extra` - Additional arguments passed to clone command.
+    ///
+    pub fn clone_url(
+        &self,
+        method_kind: CloneMethodKind,
+        url: &str,
+        extra: &[&str],
+    ) -> Result<ClonedPackage, CloneError> {
+        Ok(self.clone_url_inner(method_kind, url, extra)?)
+    }
+
+    fn clone_url_inner(
+        &self,
+        method_kind: CloneMethodKind,
+        url: &str,
+        extra: &[&str],
+    ) -> Result<ClonedPackage, Error> {
+        let name = worktree_name(url);
+        // Stands in for the package information from the registry.
+        let pkg_info = CrateResponse {
+            krate: CrateData {
+                name: name.clone(),
+                repository: Some(url.to_string()),
+                ..CrateData::default()
+            },
+            versions: Vec::new(),
+        };
         let (method, repo) = match method_kind {
-            CloneMethodKind::Auto => {
-                if version_req.is_some() {
-                    (CloneMethodKind::Crate, "".to_string())
-                } else if let Some(repo) = repo {
-                    self.detect_repo(&repo)?
-                } else {
-                    (CloneMethodKind::Crate, "".to_string())
-                }
+            CloneMethodKind::Crate => fail!(
+                InvalidInput,
+                "The `crate` method needs a package from the registry, not a URL."
+            ),
+            CloneMethodKind::Auto => self.detect_repo_inner(url)?,
+            // Use the detected URL if it is for the same method, which may be
+            // rewritten for SSH or point to the root of a monorepo.
+            method_kind => match self.detect_repo_inner(url) {
+                Ok((method, repo)) if method.command() == method_kind.command() => (method, repo),
+                _ => (method_kind, url.to_string()),
+            },
+        };
+        self.check_method(&method)?;
+        self.emit_method(&name, &method, &repo);
+        let package = self.clone_vcs(&name, &method, &repo, None, extra, &pkg_info)?;
+        self.finish_clone(package, None)
+    }
+
+    /// Extracts a `.crate` file on disk into `out_dir`, such as one made by
+    /// `cargo package` or copied from cargo's cache.
+    ///
+    /// The name and version are read from the archive. The same checks and
+    /// settings apply as for a download, such as [`Cloner::set_dest_name`]
+    /// and [`Cloner::set_extract_filter`]. The source of the returned package
+    /// is the path of the `.crate` file.
+    pub fn extract_crate_file(
+        &self,
+        path: impl AsRef<Path>,
+        out_dir: impl AsRef<Path>,
+    ) -> Result<ClonedPackage, CloneError> {
+        Ok(self.extract_crate_file_inner(path.as_ref(), out_dir.as_ref())?)
+    }
+
+    fn extract_crate_file_inner(
+        &self,
+        path: &Path,
+        out_dir: &Path,
+    ) -> Result<ClonedPackage, Error> {
+        let open = || {
+            fs::File::open(path).with_context(|| format!("Failed to open `{}`.", path.display()))
+        };
+        let (name, version) = crate_file_package(path, open()?)?;
+        let mut cloner = Clone::clone(self);
+        cloner.set_out_dir(out_dir);
+        cloner.check_crate_checkout()?;
+        cloner.emit_method(&name, &CloneMethodKind::Crate, "");
+        let dest = cloner.crate_dest(&name, &version)?;
+        cloner.emit_download(&name, &version, &dest);
+        let checksum = cache::file_checksum(path)
+            .with_context(|| format!("Failed to read `{}`.", path.display()))?;
+        let download = CrateDownload {
+            name: name.clone(),
+            version,
+            url: path.display().to_string(),
+            checksum: Some(checksum),
+        };
+        if cloner.dry_run {
+            info!("Would extract `{}` to `{}`", path.display(), dest.display());
+            return Ok(download.into_package(&name, dest));
+        }
+        info!("Extracting `{}`", path.display());
+        let package = cloner.extract_crate(&name, download, open()?)?;
+        cloner.finish_clone(package, None)
+    }
+
+    /// Records a cloned package in the export file and reports it.
+    ///
+    /// The summary of the package is filled in from `pkg_info`, for packages
+    /// looked up in the registry.
+    pub(crate) fn finish_clone(
+        &self,
+        mut package: ClonedPackage,
+        pkg_info: Option<&CrateResponse>,
+    ) -> Result<ClonedPackage, Error> {
+        if let Some(pkg_info) = pkg_info {
+            package.summary = Some(self.package_summary(pkg_info, package.version.as_deref()));
+        }
+        self.write_metadata_file(&package)?;
+        self.git_init(&package)?;
+        self.export(&package)?;
+        self.emit(CloneEvent::Cloned {
+            name: package.name.clone(),
+        });
+        Ok(package)
+    }
+
+    /// Builds the summary of a package, for the cloned `version`.
+    fn package_summary(&self, pkg_info: &CrateResponse, version: Option<&str>) -> PackageSummary {
+        let latest = pkg_info
+            .versions
+            .iter()
+            .filter(|v| !v.yanked)
+            .filter_map(|v| Some((v, semver::Version::parse(&v.num).ok()?)))
+            .filter(|(_, ver)| ver.pre.is_empty())
+            .max_by(|a, b| a.1.cmp(&b.1))
+            .map(|(v, _)| v);
+        let license = version
+            .and_then(|version| pkg_info.versions.iter().find(|v| v.num == version))
+            .or(latest)
+            .and_then(|v| v.license.clone());
+        let krate = &pkg_info.krate;
+        let documentation = krate.documentation.clone().or_else(|| {
+            (self.registry_url() == DEFAULT_REGISTRY_URL)
+                .then(|| format!("https://docs.rs/{}", krate.name))
+        });
+        PackageSummary {
+            description: krate.description.as_ref().map(|d| d.trim().to_string()),
+            license,
+            documentation,
+            latest_version: latest.map(|v| v.num.clone()),
+        }
+    }
+
+    /// Reports that a package is being looked up.
+    pub(crate) fn emit_resolving(&self, name: &str, requirement: Option<&str>) {
+        self.emit(CloneEvent::Resolving {
+            name: name.to_string(),
+            requirement: requirement.map(str::to_string),
+        });
+    }
+
+    /// Reports the chosen method.
+    pub(crate) fn emit_method(&self, name: &str, method: &CloneMethodKind, repo: &str) {
+        self.emit(CloneEvent::Method {
+            name: name.to_string(),
+            method: method.command().to_string(),
+            repository: (!repo.is_empty()).then(|| repo.to_string()),
+        });
+    }
+
+    /// Reports the version and destination of a `.crate` download.
+    pub(crate) fn emit_download(&self, name: &str, version: &str, dest: &Path) {
+        self.emit(CloneEvent::Version {
+            name: name.to_string(),
+            version: version.to_string(),
+        });
+        self.emit(CloneEvent::Destination {
+            name: name.to_string(),
+            path: dest.to_path_buf(),
+        });
+    }
+
+    /// Splits the spec into a name and version requirement, applying the
+    /// lockfile if one is set.
+    fn parse_request<'a>(
+        &self,
+        spec: &'a str,
+        version: Option<&str>,
+    ) -> Result<(&'a str, Option<String>), Error> {
+        let (name, mut version_req) = parse_spec(spec, version)?;
+        if let Some(lockfile) = &self.lockfile {
+            version_req = Some(self.locked_version_req(lockfile, name, version_req.as_deref())?);
+        }
+        Ok((name, version_req))
+    }
+
+    /// Determines the method to use, along with the repository URL for VCS
+    /// methods.
+    fn select_method(
+        &self,
+        method_kind: CloneMethodKind,
+        name: &str,
+        version_req: Option<&str>,
+        pkg_info: &CrateResponse,
+    ) -> Result<(CloneMethodKind, String), Error> {
+        let method_kind = self.requested_method(method_kind)?;
+        let repo = get_repo(pkg_info);
+        let detected = repo_to_detect(&method_kind, version_req, repo.as_deref())
+            .map(|repo| self.detect_repo_inner(repo));
+        self.selected_method(method_kind, name, repo, detected)
+    }
+
+    /// Resolves `auto` to the default method, checking that the method works
+    /// with the other settings.
+    pub(crate) fn requested_method(
+        &self,
+        method_kind: CloneMethodKind,
+    ) -> Result<CloneMethodKind, Error> {
+        Ok(match method_kind {
+            CloneMethodKind::Auto if self.vendor || self.reproducible || self.saves_archive() => {
+                CloneMethodKind::Crate
             }
+            CloneMethodKind::Auto => self.default_method.clone(),
+            CloneMethodKind::Crate => CloneMethodKind::Crate,
+            _ if self.vendor => fail!(
+                InvalidInput,
+                "Vendoring only works with the `crate` method."
+            ),
+            _ if self.saves_archive() => fail!(
+                InvalidInput,
+                "Only the `crate` method downloads a `.crate` file to keep."
+            ),
+            method_kind => method_kind,
+        })
+    }
+
+    /// Finishes selecting the method for the `method_kind` of
+    /// [`Cloner::requested_method`], given the package's repository and the
+    /// result of detecting the repository returned by [`repo_to_detect`].
+    pub(crate) fn selected_method(
+        &self,
+        method_kind: CloneMethodKind,
+        name: &str,
+        repo: Option<String>,
+        detected: Option<Result<(CloneMethodKind, String), Error>>,
+    ) -> Result<(CloneMethodKind, String), Error> {
+        debug!("Repository of `{}` is {:?}", name, repo);
+        let (method, repo) = match method_kind {
+            CloneMethodKind::Auto => match detected {
+                Some(detected) => detected?,
+                None => (CloneMethodKind::Crate, "".to_string()),
+            },
             CloneMethodKind::Crate => (method_kind, "".to_string()),
             _ => {
-                if repo.is_none() {
-                    bail!("Could not find repository path in crates.io.");
+                let Some(repo) = repo else {
+                    fail!(Repository, "Could not find repository path in crates.io.");
+                };
+                match detected {
+                    // A deep link can't be cloned, use the repository it is in.
+                    Some(Ok((CloneMethodKind::Git, url)))
+                        if matches!(method_kind, CloneMethodKind::Git)
+                            && repo_subdir(&repo).is_some() =>
+                    {
+                        (method_kind, url)
+                    }
+                    _ => (method_kind, repo),
                 }
-                (method_kind, repo.unwrap())
             }
         };
+        debug!("Using method `{}` for `{}`", method.command(), name);
+        self.check_method(&method)?;
+        Ok((method, repo))
+    }
+
+    /// Checks that the options which only apply to some methods are not
+    /// used with another method.
+    fn check_method(&self, method: &CloneMethodKind) -> Result<(), Error> {
+        if self.strip_prefix && !matches!(method, CloneMethodKind::Crate) {
+            fail!(
+                InvalidInput,
+                "Strip prefix only works with the `crate` method, not `{}`.",
+                method.command()
+            );
+        }
+        if self.extract_filter.is_some() && !matches!(method, CloneMethodKind::Crate) {
+            fail!(
+                InvalidInput,
+                "An extract filter only works with the `crate` method, not `{}`.",
+                method.command()
+            );
+        }
+        if self.reproducible && !matches!(method, CloneMethodKind::Crate) {
+            fail!(
+                InvalidInput,
+                "Reproducible extraction only works with the `crate` method, not `{}`.",
+                method.command()
+            );
+        }
+        if self.pijul_channel.is_some() && !matches!(method, CloneMethodKind::Pijul) {
+            fail!(
+                InvalidInput,
+                "A pijul channel only works with the `pijul` method, not `{}`.",
+                method.command()
+            );
+        }
+        Ok(())
+    }
+
+    /// Clones a repository with a VCS method.
+    fn clone_vcs(
+        &self,
+        name: &str,
+        method: &CloneMethodKind,
+        repo: &str,
+        version_req: Option<&str>,
+        extra: &[&str],
+        pkg_info: &CrateResponse,
+    ) -> Result<ClonedPackage, Error> {
+        if let (Some(checkout), Some(version_req)) = (&self.checkout, version_req) {
+            fail!(
+                InvalidInput,
+                "Cannot use {} together with a version `{}`.",
+                checkout.flag(),
+                version_req
+            );
+        }
+        match (method, version_req) {
+            (CloneMethodKind::Git | CloneMethodKind::GithubArchive, _) | (_, None) => {}
+            (_, Some(version_req)) => fail!(
+                InvalidInput,
+                "Specifying a version `{}` only works with the `crate` and `git` methods.",
+                version_req
+            ),
+        }
+        self.check_program(method)?;
+        let dest_arg = match &self.dest_name {
+            Some(_) if extra.iter().any(|arg| !arg.starts_with('-')) => fail!(
+                InvalidInput,
+                "Cannot use a destination name together with a directory argument."
+            ),
+            Some(dest) if matches!(method, CloneMethodKind::Fossil) => {
+                Some(format!("{}.fossil", dest))
+            }
+            Some(dest) => Some(dest.clone()),
+            None => None,
+        };
+        let mut extra = extra.to_vec();
+        extra.extend(dest_arg.as_deref());
+        let extra = &extra[..];
+        if let CloneMethodKind::GithubArchive = method {
+            return self.clone_github_archive(name, repo, version_req, extra, pkg_info);
+        }
+        self.check_repo(method, repo)?;
+        let target = self.vcs_destination(name, method, repo, extra)?;
+        let dest = match method {
+            CloneMethodKind::Fossil if self.fossil_open => target.with_extension(""),
+            _ => target.clone(),
+        };
+        // A deep link into a monorepo names the directory of the package.
+        let package_dir = match method {
+            CloneMethodKind::Git => get_repo(pkg_info)
+                .as_deref()
+                .and_then(repo_subdir)
+                .map(|subdir| dest.join(subdir)),
+            _ => None,
+        };
+        self.emit(CloneEvent::Destination {
+            name: name.to_string(),
+            path: package_dir.clone().unwrap_or_else(|| dest.clone()),
+        });
         match method {
-            CloneMethodKind::Crate => {
-                if !extra.is_empty() {
-                    bail!("Got extra arguments, crate downloads take no extra arguments.");
+            // Where a custom command writes to is up to the command.
+            CloneMethodKind::Custom { .. } => {}
+            CloneMethodKind::Fossil if dest != target => {
+                self.prepare_destination(&target)?;
+                self.prepare_destination(&dest)?;
+            }
+            _ => self.prepare_destination(&dest)?,
+        }
+        // With a version, git checks out the commit it was published from.
+        let (version, commit) = match (method, version_req) {
+            (CloneMethodKind::Git, Some(version_req)) if self.dry_run => {
+                info!(
+                    "Would check out the commit `{} {}` was published from",
+                    name, version_req
+                );
+                (None, None)
+            }
+            (CloneMethodKind::Git, Some(version_req)) => {
+                let (version, commit) = self.version_commit(name, repo, version_req, pkg_info)?;
+                (Some(version), commit)
+            }
+            _ => (None, None),
+        };
+        let mut options = self.checkout_options(method)?;
+        if self.shallow {
+            match (method, &self.git_store) {
+                (CloneMethodKind::Git, None) => options.push("--depth=1".to_string()),
+                (CloneMethodKind::Git, Some(_)) => {
+                    warn!("Shallow clones are not supported with a git store, cloning the full history.")
+                }
+                _ => warn!(
+                    "Shallow clones are not supported with the `{}` method, cloning the full history.",
+                    method.command()
+                ),
+            }
+        }
+        if self.recurse_submodules {
+            match method {
+                CloneMethodKind::Git => {
+                    options.push("--recurse-submodules".to_string());
+                    if self.shallow {
+                        options.push("--shallow-submodules".to_string());
+                    }
+                }
+                _ => warn!(
+                    "Submodules are not supported with the `{}` method, ignoring them.",
+                    method.command()
+                ),
+            }
+        }
+        match (method, &self.git_store) {
+            (CloneMethodKind::Git, Some(store)) => {
+                let git_ref = commit
+                    .as_deref()
+                    .or(self.checkout.as_ref().map(Checkout::name));
+                self.clone_worktree(store, repo, extra, git_ref)?
+            }
+            (CloneMethodKind::Git, None) => {
+                let commit = match &self.checkout {
+                    Some(Checkout::Rev(rev)) => Some(rev.as_str()),
+                    _ => commit.as_deref(),
+                };
+                self.clone_git(repo, &options, extra, commit)?
+            }
+            (
+                CloneMethodKind::Custom {
+                    command,
+                    args_template,
+                },
+                _,
+            ) => self.run_custom(command, args_template, repo, extra)?,
+            (CloneMethodKind::Fossil, _) => self.clone_fossil(name, repo, extra)?,
+            (CloneMethodKind::Pijul, _) => self.clone_pijul(repo, options, extra)?,
+            _ => self.run_clone(method.command(), &options, repo, extra)?,
+        }
+        let mut path = package_dir.clone().unwrap_or_else(|| dest.clone());
+        if self.dry_run {
+            if let Some(dir) = &package_dir {
+                info!("Package `{}` would be in `{}`", name, dir.display());
+            }
+        } else if !matches!(method, CloneMethodKind::Custom { .. }) {
+            // The package may be a member of a workspace.
+            match find_package(&dest, name) {
+                Some(dir) => {
+                    info!("Package `{}` is in `{}`", name, dir.display());
+                    self.emit(CloneEvent::Package {
+                        name: name.to_string(),
+                        path: dir.clone(),
+                    });
+                    path = dir;
                 }
-                self.clone_crate(name, version_req, &pkg_info)?;
-            }
-            CloneMethodKind::Git
-            | CloneMethodKind::Mercurial
-            | CloneMethodKind::Pijul
-            | CloneMethodKind::Fossil => {
-                if let Some(version_req) = version_req {
-                    bail!(
-                        "Specifying a version `{}` only works with the `crate` method.",
-                        version_req
-                    );
+                None => {
+                    let message = match &package_dir {
+                        Some(dir) => format!(
+                            "Package `{}` was not found in `{}`, the directory `{}` \
+                             from the repository URL may be outdated.",
+                            name,
+                            dest.display(),
+                            dir.display()
+                        ),
+                        None => format!(
+                            "Package `{}` was not found in `{}`, \
+                             the repository may be wrong or outdated.",
+                            name,
+                            dest.display()
+                        ),
+                    };
+                    if self.strict {
+                        fail!(Repository, "{}", message);
+                    }
+                    warn!("{}", message);
                 }
-                self.run_clone(method.command(), &repo, extra)?;
             }
-            CloneMethodKind::Auto => unreachable!(),
         }
+        Ok(ClonedPackage {
+            name: name.to_string(),
+            version,
+            method: method.clone(),
+            source: repo.to_string(),
+            path,
+            checksum: None,
+            summary: None,
+            archive: None,
+        })
+    }
 
-        Ok(())
+    /// Detects the VCS method of a repository URL, and the URL to clone it
+    /// with.
+    ///
+    /// Web URLs of known hosts, such as a GitHub project page or a deep link
+    /// into a monorepo, are converted into clone URLs, using SSH with
+    /// [`Cloner::set_prefer_ssh`]. Bitbucket repositories are looked up with
+    /// its API.
+    pub fn detect_repo(&self, url: &str) -> Result<(CloneMethodKind, String), CloneError> {
+        Ok(self.detect_repo_inner(url)?)
+    }
+
+    /// Detects the VCS method and clone URL of a package's repository, from
+    /// its package info in the registry.
+    ///
+    /// With [`CloneMethodKind::Auto`], the method is detected from the
+    /// repository URL, as with [`Cloner::detect_repo`]. With a VCS method,
+    /// this is the URL that method would clone.
+    pub fn detect_package_repo(
+        &self,
+        name: &str,
+        method_kind: CloneMethodKind,
+    ) -> Result<(CloneMethodKind, String), CloneError> {
+        Ok(self.detect_package_repo_inner(name, method_kind)?)
+    }
+
+    fn detect_package_repo_inner(
+        &self,
+        name: &str,
+        method_kind: CloneMethodKind,
+    ) -> Result<(CloneMethodKind, String), Error> {
+        let pkg_info = self.get_pkg_info(name)?;
+        let name = found_name(name, &pkg_info);
+        match method_kind {
+            CloneMethodKind::Crate => fail!(
+                InvalidInput,
+                "The `crate` method has no repository, use a VCS method."
+            ),
+            CloneMethodKind::Auto => match get_repo(&pkg_info) {
+                Some(repo) => self.detect_repo_inner(&repo),
+                None => fail!(Repository, "Could not find repository path in crates.io."),
+            },
+            method_kind => self.select_method(method_kind, name, None, &pkg_info),
+        }
+    }
+
+    fn detect_repo_inner(&self, repo: &str) -> Result<(CloneMethodKind, String), Error> {
+        match self.match_repo(repo)? {
+            RepoMatch::Found(method, url) => Ok((method, url)),
+            RepoMatch::GitHub(owner, name) => {
+                let moved = self.github_redirect(owner, name);
+                Ok(self.github_repo(owner, name, moved))
+            }
+            RepoMatch::Bitbucket(user, name) => self.bitbucket(user, name),
+        }
     }
 
-    fn detect_repo(&self, repo: &str) -> Result<(CloneMethodKind, String), Error> {
+    /// Determines the VCS of a repository URL, or which host's API tells it.
+    pub(crate) fn match_repo<'a>(&self, repo: &'a str) -> Result<RepoMatch<'a>, Error> {
         if repo.ends_with(".git") {
-            return Ok((CloneMethodKind::Git, repo.to_string()));
+            let known_host =
+                Regex::new(r"^https?://(?:www\.)?(?:github\.com|gitlab\.com|bitbucket\.org)/")
+                    .unwrap()
+                    .is_match(repo);
+            if known_host {
+                return Ok(RepoMatch::Found(CloneMethodKind::Git, self.git_url(repo)));
+            }
+            return Ok(RepoMatch::Found(CloneMethodKind::Git, repo.to_string()));
         }
         if let Some(c) = Regex::new(r"https?://(?:www\.)?github\.com/([^/]+)/([^/]+)")
             .unwrap()
             .captures(repo)
         {
-            return Ok((
-                CloneMethodKind::Git,
-                format!(
-                    "{}/{}/{}.git",
-                    self.github_url,
-                    c.get(1).unwrap().as_str(),
-                    c.get(2).unwrap().as_str()
-                ),
+            return Ok(RepoMatch::GitHub(
+                c.get(1).unwrap().as_str(),
+                c.get(2).unwrap().as_str(),
             ));
         }
-        if let Some(c) = Regex::new(r"https?://(?:www\.)?gitlab\.com/([^/]+)/([^/]+)")
+        if let Some(c) = Regex::new(r"https?://(?:www\.)?gitlab\.com/([^#?]+)")
             .unwrap()
             .captures(repo)
         {
-            return Ok((
-                CloneMethodKind::Git,
+            if let Some(path) = project_path(c.get(1).unwrap().as_str()) {
+                return Ok(RepoMatch::Found(
+                    CloneMethodKind::Git,
+                    self.git_url(&format!("{}/{}.git", self.gitlab_url, path)),
+                ));
+            }
+        }
+        if let Some(c) = Regex::new(r"https?://(?:www\.)?bitbucket\.(?:org|com)/([^/]+)/([^/]+)")
+            .unwrap()
+            .captures(repo)
+        {
+            return Ok(RepoMatch::Bitbucket(
+                c.get(1).unwrap().as_str(),
+                c.get(2).unwrap().as_str(),
+            ));
+        }
+        for host in &self.gitea_hosts {
+            let re = format!(r"https?://{}/([^/]+)/([^/#?]+)", regex::escape(host));
+            if let Some(c) = Regex::new(&re).unwrap().captures(repo) {
+                return Ok(RepoMatch::Found(
+                    CloneMethodKind::Git,
+                    format!(
+                        "https://{}/{}/{}.git",
+                        host,
+                        c.get(1).unwrap().as_str(),
+                        c.get(2).unwrap().as_str()
+                    ),
+                ));
+            }
+        }
+        for host in &self.git_hosts {
+            let re = format!(r"(https?)://{}/([^#?]+)", regex::escape(host));
+            if let Some(c) = Regex::new(&re).unwrap().captures(repo) {
+                if let Some(path) = project_path(c.get(2).unwrap().as_str()) {
+                    return Ok(RepoMatch::Found(
+                        CloneMethodKind::Git,
+                        format!("{}://{}/{}.git", c.get(1).unwrap().as_str(), host, path),
+                    ));
+                }
+            }
+        }
+        if let Some(c) = Regex::new(r"https?://(git|hg)\.sr\.ht/(~[^/]+)/([^/#?]+)")
+            .unwrap()
+            .captures(repo)
+        {
+            let kind = match c.get(1).unwrap().as_str() {
+                "git" => CloneMethodKind::Git,
+                _ => CloneMethodKind::Mercurial,
+            };
+            return Ok(RepoMatch::Found(
+                kind,
                 format!(
-                    "{}/{}/{}.git",
-                    self.gitlab_url,
+                    "https://{}.sr.ht/{}/{}",
                     c.get(1).unwrap().as_str(),
-                    c.get(2).unwrap().as_str()
+                    c.get(2).unwrap().as_str(),
+                    c.get(3).unwrap().as_str()
                 ),
             ));
         }
-        if let Some(c) = Regex::new(r"https?://(?:www\.)?bitbucket\.(?:org|com)/([^/]+)/([^/]+)")
+        if Regex::new(r"^(?:svn(?:\+ssh)?://|https?://svn\.)")
+            .unwrap()
+            .is_match(repo)
+        {
+            return Ok(RepoMatch::Found(
+                CloneMethodKind::Subversion,
+                repo.to_string(),
+            ));
+        }
+        if let Some(c) = Regex::new(r"^https?://(?:code\.)?launchpad\.net/([^#?]+)")
             .unwrap()
             .captures(repo)
         {
-            let user = c.get(1).unwrap().as_str();
-            let name = c.get(2).unwrap().as_str();
-            return self.bitbucket(user, name);
+            let path = c.get(1).unwrap().as_str().trim_end_matches('/');
+            return Ok(RepoMatch::Found(
+                CloneMethodKind::Bazaar,
+                format!("lp:{}", path),
+            ));
+        }
+        if repo.starts_with("https://hub.darcs.net/") {
+            return Ok(RepoMatch::Found(CloneMethodKind::Darcs, repo.to_string()));
         }
         if repo.starts_with("https://nest.pijul.com/") {
-            return Ok((CloneMethodKind::Pijul, repo.to_string()));
+            return Ok(RepoMatch::Found(CloneMethodKind::Pijul, repo.to_string()));
         }
-        bail!(
+        fail!(
+            Repository,
             "Could not determine the VCS from repo `{}`, \
              use the `--method` option to specify how to download.",
             repo
         );
     }
 
+    /// Returns the URL to clone a GitHub, GitLab, or Bitbucket repository
+    /// with git, rewritten to SSH if [`Cloner::set_prefer_ssh`] is set.
+    fn git_url(&self, url: &str) -> String {
+        if !self.prefer_ssh {
+            return url.to_string();
+        }
+        match Regex::new(r"^https?://(?:www\.)?([^/]+)/(.+)$")
+            .unwrap()
+            .captures(url)
+        {
+            Some(c) => format!(
+                "git@{}:{}",
+                c.get(1).unwrap().as_str(),
+                c.get(2).unwrap().as_str()
+            ),
+            None => url.to_string(),
+        }
+    }
+
+    /// Returns the git URL of a GitHub repository, at the `owner/name` it
+    /// `moved` to, if any.
+    pub(crate) fn github_repo(
+        &self,
+        owner: &str,
+        name: &str,
+        moved: Option<String>,
+    ) -> (CloneMethodKind, String) {
+        let path = moved.unwrap_or_else(|| format!("{}/{}", owner, name));
+        (
+            CloneMethodKind::Git,
+            self.git_url(&format!("{}/{}.git", self.github_url, path)),
+        )
+    }
+
+    /// Returns the new `owner/name` of a GitHub repository that was renamed
+    /// or transferred, which GitHub redirects to.
+    ///
+    /// Any failure is ignored, leaving it to the VCS to report.
+    fn github_redirect(&self, owner: &str, name: &str) -> Option<String> {
+        let url = self.github_redirect_url(owner, name)?;
+        // Not retried, a repository that can't be reached is cloned as is.
+        let response = match self.http_client().and_then(|c| Ok(c.head(&url).send()?)) {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                debug!("HEAD {} -> {}", url, response.status());
+                return None;
+            }
+            Err(e) => {
+                debug!("Failed to check `{}` for a redirect: {:#}", url, e);
+                return None;
+            }
+        };
+        self.github_moved(owner, name, response.url().as_str())
+    }
+
+    /// Returns the URL to check for a redirect of a GitHub repository, if
+    /// redirects are followed.
+    pub(crate) fn github_redirect_url(&self, owner: &str, name: &str) -> Option<String> {
+        if !self.follow_redirects || self.dry_run || !self.github_url.starts_with("http") {
+            return None;
+        }
+        Some(format!("{}/{}/{}", self.github_url, owner, name))
+    }
+
+    /// Returns the new `owner/name` of a GitHub repository, given the URL its
+    /// redirect ended at, if it moved.
+    pub(crate) fn github_moved(&self, owner: &str, name: &str, final_url: &str) -> Option<String> {
+        // A redirect to another host, such as a login page, is not a move.
+        let moved = final_url.strip_prefix(&self.github_url)?;
+        let mut segments = moved
+            .trim_start_matches('/')
+            .split(['/', '?', '#'])
+            .filter(|s| !s.is_empty());
+        let path = format!("{}/{}", segments.next()?, segments.next()?);
+        if path.eq_ignore_ascii_case(&format!("{}/{}", owner, name)) {
+            return None;
+        }
+        info!(
+            "GitHub repository `{}/{}` has moved to `{}`",
+            owner, name, path
+        );
+        Some(path)
+    }
+
     fn bitbucket(&self, user: &str, name: &str) -> Result<(CloneMethodKind, String), Error> {
         // Determine if it is git or hg.
-        let api_url = &format!("{}/{}/{}", self.bitbutcket_url, user, name);
-        let repo_info =
-            reqwest_get(api_url).context("Failed to fetch repo info from bitbucket.")?;
-        let code = repo_info.status();
-        if !code.is_success() {
-            bail!(
-                "Failed to get repo info from bitbucket API `{}`: `{}`",
-                api_url,
-                code
-            );
-        }
+        let api_url = &self.bitbucket_api_url(user, name);
+        let repo_info = self
+            .reqwest_get(api_url)
+            .context("Failed to fetch repo info from bitbucket.")?;
+        check_bitbucket_status(api_url, repo_info.status())?;
         let repo_info: Value = repo_info
             .json()
             .context("Failed to convert to bitbucket json.")?;
-        let method = repo_info["scm"]
-            .as_str()
-            .expect("Could not get `scm` from bitbucket.");
+        self.bitbucket_clone(&repo_info)
+    }
+
+    /// The Bitbucket API URL of a repository.
+    pub(crate) fn bitbucket_api_url(&self, user: &str, name: &str) -> String {
+        format!("{}/{}/{}", self.bitbutcket_url, user, name)
+    }
+
+    /// Returns the method and URL to clone a repository with, from its info
+    /// in the Bitbucket API.
+    pub(crate) fn bitbucket_clone(
+        &self,
+        repo_info: &Value,
+    ) -> Result<(CloneMethodKind, String), Error> {
+        let Some(method) = repo_info["scm"].as_str() else {
+            fail!(Repository, "Could not get `scm` from bitbucket.");
+        };
         let method = match method {
             "git" => CloneMethodKind::Git,
             "hg" => CloneMethodKind::Mercurial,
-            _ => bail!("Unexpected bitbucket scm: `{}`", method),
+            _ => fail!(Repository, "Unexpected bitbucket scm: `{}`", method),
+        };
+        let Some(clones) = repo_info["links"]["clone"].as_array() else {
+            fail!(Repository, "Could not get `clone` from bitbucket.");
+        };
+        let link = match method {
+            CloneMethodKind::Git if self.prefer_ssh => "ssh",
+            _ => "https",
         };
-        let clones = repo_info["links"]["clone"]
-            .as_array()
-            .expect("Could not get `clone` from bitbucket.");
         let href = clones
             .iter()
-            .find(|c| {
-                c["name"]
-                    .as_str()
-                    .expect("Could not get clone `name` from bitbucket.")
-                    == "https"
-            })
-            .expect("Could not find `https` clone in bitbucket.")["href"]
-            .as_str()
-            .expect("Could not get clone `href` from bitbucket.");
+            .find(|c| c["name"].as_str() == Some(link))
+            .and_then(|c| c["href"].as_str());
+        let Some(href) = href else {
+            fail!(Repository, "Could not find `{}` clone in bitbucket.", link);
+        };
         Ok((method, href.to_string()))
     }
 
     /// Grab package info from crates.io.
-    fn get_pkg_info(&self, name: &str) -> Result<Value, Error> {
-        let url = format!("{}/api/v1/crates/{}", self.registry_url, name);
-        debug!("GET {url}");
-        let pkg_info = reqwest_get(&url).context("Failed to fetch package info from crates.io.")?;
-        let code = pkg_info.status();
-        match code {
-            StatusCode::OK => {}
-            StatusCode::NOT_FOUND => bail!("Package `{}` not found on crates.io.", name),
-            _ => bail!("Failed to get package info from crates.io: `{}`", code),
-        }
-        let pkg_info: Value = pkg_info.json().context("Failed to convert to json.")?;
+    ///
+    /// If the package is not found, the error suggests similar names. With
+    /// [`Cloner::set_fuzzy_name`], the package with `-` and `_` swapped is
+    /// returned instead, see [`found_name`].
+    fn get_pkg_info(&self, name: &str) -> Result<CrateResponse, Error> {
+        match self.fetch_pkg_info(name) {
+            Err(e) if message_kind(&e) == Some(Kind::NotFound) => {
+                match self.use_variant(name, self.find_name_variant(name)) {
+                    Ok(pkg_info) => Ok(pkg_info),
+                    Err(variant) => Err(not_found_error(e, self.similar_names(name, variant))),
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Returns the package info of the name variant found by
+    /// [`Cloner::find_name_variant`] if [`Cloner::set_fuzzy_name`] is set,
+    /// otherwise the name of the variant to suggest.
+    pub(crate) fn use_variant(
+        &self,
+        name: &str,
+        variant: Option<CrateResponse>,
+    ) -> Result<CrateResponse, Option<String>> {
+        match variant {
+            Some(pkg_info) if self.fuzzy_name => {
+                warn!(
+                    "Package `{}` not found, using `{}` instead",
+                    name, pkg_info.krate.name
+                );
+                Ok(pkg_info)
+            }
+            variant => Err(variant.map(|p| p.krate.name)),
+        }
+    }
+
+    fn fetch_pkg_info(&self, name: &str) -> Result<CrateResponse, Error> {
+        if let Some(dir) = &self.local_registry {
+            return self.get_local_pkg_info(dir, name);
+        }
+        if let Some(index_url) = self.sparse_index_url() {
+            return self.get_sparse_pkg_info(index_url, name);
+        }
+        self.resolve_git_index()?;
+        let url = self.pkg_info_url(name);
+        let pkg_info = self
+            .registry_get(&url)
+            .context("Failed to fetch package info from crates.io.")?;
+        check_pkg_info_status(name, pkg_info.status())?;
+        let pkg_info = pkg_info.json().context("Failed to convert to json.")?;
         Ok(pkg_info)
     }
 
+    /// Returns the index URL if the registry is a sparse index.
+    fn sparse_index_url(&self) -> Option<&str> {
+        self.registry_url().strip_prefix("sparse+")
+    }
+
+    /// A wrapper around `reqwest::blocking::get` that provides a User Agent
+    /// and the proxy settings. The User Agent is required by crates.io
+    fn reqwest_get(&self, url: &str) -> Result<reqwest::blocking::Response, Error> {
+        self.http_get(url, None)
+    }
+
+    /// Sends a GET request, with the registry token if the URL is part of the
+    /// registry.
+    ///
+    /// If the registry can't be reached or fails with a server error, the
+    /// request is sent to each mirror in turn.
+    fn registry_get(&self, url: &str) -> Result<reqwest::blocking::Response, Error> {
+        let urls = self.mirror_urls(url);
+        let (last, urls) = urls.split_last().unwrap();
+        for url in urls {
+            match self.http_get(url, self.registry_token_for(url)) {
+                Ok(response) if response.status().is_server_error() => {
+                    self.log_mirror_fallback(url, response.status())
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if http::is_transient(&e) => self.log_mirror_fallback(url, e),
+                Err(e) => return Err(e),
+            }
+        }
+        self.http_get(last, self.registry_token_for(last))
+    }
+
+    fn http_get(
+        &self,
+        url: &str,
+        token: Option<&str>,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        let client = self.http_client()?;
+        let mut attempt = 1;
+        loop {
+            let mut request = client.get(url);
+            if let Some(token) = token {
+                request = request.header(reqwest::header::AUTHORIZATION, token);
+            }
+            let retry = attempt <= self.retries;
+            let (reason, retry_after) = match send_request(url, request) {
+                Ok(response) if retry && http::is_transient_status(response.status()) => (
+                    response.status().to_string(),
+                    http::retry_after(response.headers()),
+                ),
+                Ok(response) => return Ok(response),
+                Err(e) if retry && http::is_transient_request(&e) => (e.to_string(), None),
+                Err(e) => return Err(e.into()),
+            };
+            self.wait_to_retry(url, attempt, reason, retry_after);
+            attempt += 1;
+        }
+    }
+
+    fn http_client(&self) -> Result<reqwest::blocking::Client, Error> {
+        if let Some(client) = &self.http_client {
+            return Ok(client.clone());
+        }
+        Ok(client_builder!(self, reqwest::blocking::Client).build()?)
+    }
+
+    fn pkg_info_url(&self, name: &str) -> String {
+        format!("{}/api/v1/crates/{}", self.registry_url(), name)
+    }
+
     /// Download a crate from crates.io.
     fn clone_crate(
         &self,
         name: &str,
-        version_req: Option<String>,
-        pkg_info: &Value,
-    ) -> Result<(), Error> {
-        // Determine which version to download.
-        let versions = pkg_info["versions"]
-            .as_array()
-            .expect("Could not find `versions` array on crates.io.");
-        let versions = versions.iter().map(|crate_version| {
-            let num = crate_version["num"]
-                .as_str()
-                .expect("Could not get `num` from version.");
-            let v = semver::Version::parse(num).expect("Could not parse crate `num`.");
-            (crate_version, v)
+        version_req: Option<&str>,
+        pkg_info: &CrateResponse,
+    ) -> Result<ClonedPackage, Error> {
+        let pending = match self.prepare_crate(name, version_req, pkg_info)? {
+            CrateStep::Done(package) => return Ok(package),
+            CrateStep::Download(pending) => pending,
+        };
+        info!("Downloading `{}`", pending.url());
+        match self.download_crate(name, pending.base(), pending.url(), pending.checksum()) {
+            Ok(part) => self.finish_crate(name, pending, &part, None),
+            Err(e) => self.crate_download_failed(name, pending, e),
+        }
+    }
+
+    /// Selects the version of a crate to download and its destination,
+    /// finishing without a download if this is a dry run or the `.crate`
+    /// file is cached.
+    pub(crate) fn prepare_crate(
+        &self,
+        name: &str,
+        version_req: Option<&str>,
+        pkg_info: &CrateResponse,
+    ) -> Result<CrateStep, Error> {
+        self.check_vendor()?;
+        let download = self.select_download(pkg_info, version_req)?;
+        let archive = self.archive_dest(name, &download.version)?;
+        let dest = match &archive {
+            Some(archive) if !self.extract => archive.clone(),
+            _ => self.crate_dest(name, &download.version)?,
+        };
+        self.emit_download(name, &download.version, &dest);
+        let cached = self.cached_crate(&download);
+        if self.dry_run {
+            match &cached {
+                Some(path) if self.extract => {
+                    info!("Would extract `{}` to `{}`", path.display(), dest.display())
+                }
+                Some(path) => info!("Would copy `{}` to `{}`", path.display(), dest.display()),
+                None => info!("Would download `{}` to `{}`", download.url, dest.display()),
+            }
+            if let (Some(archive), true) = (&archive, self.extract) {
+                info!("Would save the `.crate` file to `{}`", archive.display());
+            }
+            let mut package = download.into_package(name, dest);
+            package.archive = archive;
+            return Ok(CrateStep::Done(package));
+        }
+        self.check_cancelled()?;
+        if let Some(path) = cached {
+            info!("Using cached `{}`", path.display());
+            if let Some(archive) = &archive {
+                info!("Copying `{}` to `{}`", path.display(), archive.display());
+                layout::create_parent(archive)?;
+                fs::copy(&path, archive)
+                    .with_context(|| format!("Failed to copy `{}`.", path.display()))?;
+            }
+            let mut package = if self.extract {
+                let file = fs::File::open(&path)
+                    .with_context(|| format!("Failed to open `{}`.", path.display()))?;
+                self.extract_crate(name, download, file)?
+            } else {
+                download.into_package(name, dest)
+            };
+            package.archive = archive;
+            return Ok(CrateStep::Done(package));
+        }
+        let base = format!("{}-{}", name.to_lowercase(), download.version);
+        Ok(CrateStep::Download(PendingCrate {
+            download,
+            archive,
+            dest,
+            base,
+        }))
+    }
+
+    /// Rebuilds the package from docs.rs if a `.crate` download failed and
+    /// [`Cloner::set_docs_rs_fallback`] is set, otherwise returns the error.
+    pub(crate) fn crate_download_failed(
+        &self,
+        name: &str,
+        pending: PendingCrate,
+        e: Error,
+    ) -> Result<ClonedPackage, Error> {
+        if self.uses_docs_rs() && self.check_cancelled().is_ok() {
+            warn!("{:#}", e);
+            return self.clone_docs_rs(name, pending.download);
+        }
+        Err(e)
+    }
+
+    /// Caches a downloaded `.crate` file at `part`, and extracts or saves it.
+    ///
+    /// `extracted` is the result of extracting the file while it was
+    /// downloaded, if it was.
+    pub(crate) fn finish_crate(
+        &self,
+        name: &str,
+        pending: PendingCrate,
+        part: &Path,
+        extracted: Option<Result<ClonedPackage, Error>>,
+    ) -> Result<ClonedPackage, Error> {
+        let PendingCrate {
+            download,
+            archive,
+            dest,
+            ..
+        } = pending;
+        self.cache_crate(&download, part);
+        let result = if let Some(extracted) = extracted {
+            extracted
+        } else if self.extract {
+            let file = fs::File::open(part)
+                .with_context(|| format!("Failed to open `{}`.", part.display()))?;
+            self.extract_crate(name, download, file)
+        } else {
+            Ok(download.into_package(name, dest))
+        };
+        match (result, archive) {
+            (Ok(mut package), Some(archive)) => {
+                info!("Saving `{}`", archive.display());
+                layout::create_parent(&archive)?;
+                fs::rename(part, &archive)
+                    .with_context(|| format!("Failed to save `{}`.", archive.display()))?;
+                package.archive = Some(archive);
+                Ok(package)
+            }
+            (result, _) => {
+                // Keep the download to resume if cancelled, otherwise it is
+                // either extracted or likely corrupt.
+                if self.check_cancelled().is_ok() {
+                    let _ = fs::remove_file(part);
+                }
+                result
+            }
+        }
+    }
+
+    /// Determines which version to download, and from where.
+    fn select_download(
+        &self,
+        pkg_info: &CrateResponse,
+        version_req: Option<&str>,
+    ) -> Result<CrateDownload, Error> {
+        let last = select_version(
+            pkg_info,
+            version_req,
+            self.include_prereleases,
+            self.include_yanked,
+        )?;
+        // Versions from a sparse index have an absolute URL.
+        let url = match (&last.dl_url, &last.dl_path) {
+            (Some(dl_url), _) => dl_url.clone(),
+            (None, Some(dl_path)) => format!("{}{}", self.registry_url(), dl_path),
+            (None, None) => fail!(
+                Http,
+                "No download path for `{} {}` in the package info.",
+                pkg_info.krate.name,
+                last.num
+            ),
+        };
+        Ok(CrateDownload {
+            name: pkg_info.krate.name.clone(),
+            version: last.num.clone(),
+            url,
+            checksum: last.checksum.clone(),
+        })
+    }
+
+    /// Returns the directory a crate is extracted to, which must not exist yet.
+    ///
+    /// With [`Cloner::set_strip_prefix`], this is the output directory, and
+    /// each extracted file is checked instead.
+    fn crate_dest(&self, name: &str, version: &str) -> Result<PathBuf, Error> {
+        if self.strip_prefix {
+            if self.dest_name.is_some() {
+                fail!(
+                    InvalidInput,
+                    "Cannot use both a destination name and strip prefix."
+                );
+            }
+            return Ok(self.out_dir()?);
+        }
+        let dest = self.out_dir()?.join(match &self.dest_name {
+            Some(dest) => PathBuf::from(dest),
+            None => self.package_dir(name, version),
         });
-        let mut versions: Vec<_> = if let Some(version_req) = version_req {
-            let req = semver::VersionReq::parse(&version_req)?;
-            versions
-                .filter(|(_crate_version, ver)| req.matches(ver))
-                .collect()
+        self.prepare_destination(&dest)?;
+        Ok(dest)
+    }
+
+    /// Returns the path a VCS clones into, which is the directory given in
+    /// the extra arguments or else the tool's default.
+    ///
+    /// For fossil, this is the repository file.
+    fn vcs_destination(
+        &self,
+        name: &str,
+        method: &CloneMethodKind,
+        repo: &str,
+        extra: &[&str],
+    ) -> Result<PathBuf, Error> {
+        let mut positional = extra.iter().filter(|arg| !arg.starts_with('-'));
+        // `git worktree add` takes the directory first, clone commands last.
+        let dir = match (method, &self.git_store) {
+            (CloneMethodKind::Git, Some(_)) => positional.next(),
+            _ => positional.next_back(),
+        };
+        Ok(self.out_dir()?.join(match (dir, method) {
+            (Some(dir), _) => dir.to_string(),
+            (None, CloneMethodKind::Fossil) => format!("{}.fossil", name),
+            (None, _) => worktree_name(repo),
+        }))
+    }
+
+    /// Checks that a destination does not exist yet, or removes it when
+    /// forced with [`Cloner::set_force`].
+    fn prepare_destination(&self, dest: &Path) -> Result<(), Error> {
+        if fs::symlink_metadata(dest).is_err() {
+            return Ok(());
+        }
+        if !self.force {
+            fail!(
+                DestinationExists,
+                "Destination `{}` already exists, use --force to replace it.",
+                dest.display()
+            );
+        }
+        if self.dry_run {
+            info!("Would remove `{}`", dest.display());
+            return Ok(());
+        }
+        info!("Removing `{}`", dest.display());
+        let result = if dest.is_dir() {
+            fs::remove_dir_all(dest)
         } else {
-            versions.collect()
+            fs::remove_file(dest)
         };
-        // Find the largest version.
-        if versions.is_empty() {
-            bail!("Could not find any matching versions.");
-        }
-        versions.sort_unstable_by_key(|x| x.1.clone());
-        let last = versions.last().unwrap().0;
-        let dl_path = last["dl_path"]
-            .as_str()
-            .expect("Could not find `dl_path` in crate version info.");
-        let dl_path = format!("{}{}", self.registry_url, dl_path);
-        let version = last["num"]
-            .as_str()
-            .expect("Could not find `num` in crate version info.");
-        info!("Downloading `{}`", dl_path);
-        let mut response =
-            reqwest_get(&dl_path).context(format!("Failed to download `{}`", dl_path))?;
-        // TODO: This could be much better.
-        let mut body = Vec::new();
-        response.copy_to(&mut body)?;
-        let gz = GzDecoder::new(body.as_slice());
+        result.with_context(|| format!("Failed to remove `{}`.", dest.display()))
+    }
+
+    /// Extracts the contents of a `.crate` file into the output directory.
+    fn extract_crate(
+        &self,
+        name: &str,
+        download: CrateDownload,
+        body: impl Read,
+    ) -> Result<ClonedPackage, Error> {
+        self.check_cancelled()?;
+        self.check_vendor()?;
+        let dest = self.crate_dest(name, &download.version)?;
+        let out_dir = self.out_dir()?;
+        let base = format!("{}-{}", name.to_lowercase(), download.version);
+        let gz = GzDecoder::new(body);
         let mut tar = Archive::new(gz);
-        let base = format!("{}-{}", name.to_lowercase(), version);
 
+        // Extract into a temporary sibling directory, which is removed if
+        // anything fails, and move it into place once complete.
+        let tmp = tempfile::Builder::new()
+            .prefix(&format!(".{}.", base))
+            .tempdir_in(&out_dir)
+            .context(format!(
+                "Failed to create temporary directory in `{}`",
+                out_dir.display()
+            ))?;
+        let mut top = PathBuf::from(&base);
+        let mut count = ExtractCount::default();
         for entry in tar.entries()? {
-            let mut entry = entry.context("Failed to get tar entry.")?;
-            let entry_path = entry
+            self.check_cancelled()?;
+            let mut entry = entry.context(msg(Kind::Extract, "Failed to get tar entry."))?;
+            self.extract_limits.add_entry(&mut count)?;
+            let raw_path = entry
                 .path()
-                .context("Failed to read entry path.")?
+                .context(msg(Kind::Extract, "Failed to read entry path."))?
                 .into_owned();
-            info!("{}", entry_path.display());
+            let Some(entry_path) = self.extract_policies.entry_path(&raw_path)? else {
+                continue;
+            };
 
-            // Sanity check.
-            if !entry_path.starts_with(&base) {
-                bail!(
+            // Sanity check. The directory keeps the case of the package name,
+            // such as `Inflector-0.11.4`.
+            match entry_path.components().next() {
+                Some(c) if c.as_os_str().eq_ignore_ascii_case(&base) => {
+                    top = PathBuf::from(c.as_os_str());
+                }
+                _ => fail!(
+                    Extract,
                     "Expected path `{}` in tarball, got `{}`.",
                     base,
                     entry_path.display()
+                ),
+            }
+            if let Some(filter) = &self.extract_filter {
+                // Directories are created for the files in them.
+                let rel = entry_path.strip_prefix(&top).unwrap();
+                if entry.header().entry_type().is_dir() || !filter.is_match(rel) {
+                    continue;
+                }
+            }
+            let unpack = self
+                .extract_policies
+                .check_link(&entry, &entry_path, tmp.path())?;
+            let size = match &unpack {
+                Unpack::Entry => entry.size(),
+                Unpack::Skip => continue,
+                Unpack::Copy(source) => fs::metadata(source)?.len(),
+            };
+            self.extract_limits
+                .add_file(&mut count, &entry_path, size)?;
+            info!("{}", entry_path.display());
+            self.emit(CloneEvent::Extracting {
+                name: name.to_string(),
+                path: entry_path.clone(),
+            });
+
+            let unpack_msg = || {
+                msg(
+                    Kind::Extract,
+                    format!("failed to unpack entry at `{}`", entry_path.display()),
+                )
+            };
+            let dest = tmp.path().join(&entry_path);
+            match unpack {
+                Unpack::Copy(source) => {
+                    fs::create_dir_all(dest.parent().unwrap()).with_context(unpack_msg)?;
+                    fs::copy(&source, &dest).with_context(unpack_msg)?;
+                }
+                _ if entry_path == raw_path => {
+                    entry.unpack_in(tmp.path()).with_context(unpack_msg)?;
+                }
+                _ => {
+                    // A sanitized path, which `unpack_in` would skip.
+                    fs::create_dir_all(dest.parent().unwrap()).with_context(unpack_msg)?;
+                    entry.unpack(&dest).with_context(unpack_msg)?;
+                }
+            }
+        }
+        // Read to the end, so that a download streamed into the extraction
+        // is complete and verified before anything is moved into place.
+        let read_msg = || msg(Kind::Extract, "Failed to read the end of the archive.");
+        let mut gz = tar.into_inner();
+        io::copy(&mut gz, &mut io::sink()).with_context(read_msg)?;
+        io::copy(&mut gz.into_inner(), &mut io::sink()).with_context(read_msg)?;
+        if self.extract_filter.is_some() {
+            // The filter may have skipped every file.
+            fs::create_dir_all(tmp.path().join(&top))?;
+        }
+        if self.vendor {
+            vendor::write_checksums(&tmp.path().join(&top), download.checksum.as_deref())?;
+        }
+        self.move_extracted(name, &tmp.path().join(&top), &dest)?;
+        Ok(download.into_package(name, dest))
+    }
+
+    /// Moves a package extracted into `dir` to `dest`, or into it with
+    /// strip prefix.
+    fn move_extracted(&self, name: &str, dir: &Path, dest: &Path) -> Result<(), Error> {
+        if self.reproducible {
+            reproducible::normalize(dir)?;
+        }
+        if self.strip_prefix {
+            // Check every entry before moving any, so that nothing is left
+            // half-extracted.
+            let mut entries = Vec::new();
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let target = dest.join(entry.file_name());
+                self.prepare_destination(&target)?;
+                entries.push((entry.path(), target));
+            }
+            for (path, target) in entries {
+                fs::rename(&path, &target)
+                    .context(format!("Failed to move `{}`", target.display()))?;
+            }
+        } else {
+            layout::create_parent(dest)?;
+            fs::rename(dir, dest)
+                .context(format!("Failed to move package to `{}`", dest.display()))?;
+        }
+        self.emit(CloneEvent::Extracted {
+            name: name.to_string(),
+            path: dest.to_path_buf(),
+        });
+        Ok(())
+    }
+
+    /// Clones a pijul repository, selecting the channel if one is set or
+    /// found in the repository URL.
+    fn clone_pijul(
+        &self,
+        repo: &str,
+        mut options: Vec<String>,
+        extra: &[&str],
+    ) -> Result<(), Error> {
+        let (repo, url_channel) = split_pijul_channel(repo);
+        if let Some(channel) = self.pijul_channel.as_deref().or(url_channel) {
+            if matches!(self.checkout, Some(Checkout::Branch(_))) {
+                fail!(
+                    InvalidInput,
+                    "Cannot use both --branch and a pijul channel."
                 );
             }
+            options.push("--channel".to_string());
+            options.push(channel.to_string());
+        }
+        self.run_clone("pijul", &options, repo, extra)
+    }
 
-            entry.unpack_in(&self.out_dir()?).context(format!(
-                "failed to unpack entry at `{}`",
-                entry_path.display()
-            ))?;
+    /// Clones a fossil repository, and opens it into a checkout directory.
+    ///
+    /// The repository file is the last extra argument that is not an option,
+    /// defaulting to `<name>.fossil`. It is opened into a directory of the
+    /// same name without the `.fossil` extension.
+    fn clone_fossil(&self, name: &str, repo: &str, extra: &[&str]) -> Result<(), Error> {
+        let default_file = format!("{}.fossil", name);
+        let mut extra = extra.to_vec();
+        if !extra.iter().any(|arg| !arg.starts_with('-')) {
+            extra.push(&default_file);
+        }
+        if !self.fossil_open {
+            if let Some(checkout) = &self.checkout {
+                fail!(
+                    InvalidInput,
+                    "{} checks out with `fossil open`, it cannot be used with --no-fossil-open.",
+                    checkout.flag()
+                );
+            }
+        }
+        self.run_clone("fossil", &[], repo, &extra)?;
+        if !self.fossil_open {
+            return Ok(());
+        }
+        let out_dir = self.out_dir()?;
+        let file = out_dir.join(
+            extra
+                .iter()
+                .rev()
+                .find(|arg| !arg.starts_with('-'))
+                .unwrap(),
+        );
+        let workdir = file.with_extension("");
+        if workdir == file {
+            fail!(
+                InvalidInput,
+                "Cannot open fossil repository `{}`, it should have an extension such as `.fossil`.",
+                file.display()
+            );
+        }
+        let version = self.checkout.as_ref().map(Checkout::name);
+        info!(
+            "{}: fossil open {} {}--workdir {}",
+            self.running(),
+            file.display(),
+            version.map(|v| format!("{} ", v)).unwrap_or_default(),
+            workdir.display()
+        );
+        if self.dry_run {
+            return Ok(());
+        }
+        let mut args = vec![OsStr::new("open"), file.as_os_str()];
+        args.extend(version.map(OsStr::new));
+        args.extend([OsStr::new("--workdir"), workdir.as_os_str()]);
+        self.emit_running("fossil", &args);
+        let status = Command::new("fossil")
+            .args(&args)
+            .current_dir(&out_dir)
+            .status()
+            .context(msg(Kind::Vcs, "Failed to run `fossil`."))?;
+        if !status.success() {
+            fail!(Vcs, "`fossil open` did not finish successfully.");
+        }
+        Ok(())
+    }
+
+    /// Runs a [`CloneMethodKind::Custom`] command, filling in the
+    /// placeholders of its arguments.
+    fn run_custom(
+        &self,
+        command: &str,
+        args_template: &str,
+        repo: &str,
+        extra: &[&str],
+    ) -> Result<(), Error> {
+        let default_dest = worktree_name(repo);
+        let mut extra = extra.to_vec();
+        let dest = match extra.first() {
+            Some(dir) if args_template.contains("{dest}") && !dir.starts_with('-') => {
+                extra.remove(0)
+            }
+            _ => &default_dest,
+        };
+        let mut args: Vec<String> = args_template
+            .split_whitespace()
+            .map(|arg| arg.replace("{repo}", repo).replace("{dest}", dest))
+            .collect();
+        args.extend(extra.iter().map(|arg| arg.to_string()));
+        info!("{}: {} {}", self.running(), command, args.join(" "));
+        if self.dry_run {
+            return Ok(());
+        }
+        self.emit_running(command, &args);
+        let status = Command::new(command)
+            .args(&args)
+            .current_dir(&self.out_dir()?)
+            .status()
+            .context(msg(Kind::Vcs, format!("Failed to run `{}`.", command)))?;
+        if !status.success() {
+            fail!(Vcs, "`{}` did not finish successfully.", command);
         }
         Ok(())
     }
 
     /// Runs the clone process.
-    fn run_clone(&self, method: &str, repo: &str, extra: &[&str]) -> Result<(), Error> {
-        info!("Running: {} clone {} {}", method, repo, extra.join(" "));
+    ///
+    /// The `options` are placed before the repository, and `extra` after it.
+    fn run_clone(
+        &self,
+        method: &str,
+        options: &[String],
+        repo: &str,
+        extra: &[&str],
+    ) -> Result<(), Error> {
+        // Subversion has no clones, only checkouts, and Bazaar branches.
+        let subcommand = match method {
+            "svn" => "checkout",
+            "bzr" => "branch",
+            _ => "clone",
+        };
+        let mut args = vec![subcommand];
+        args.extend(options.iter().map(String::as_str));
+        args.push(repo);
+        args.extend(extra);
+        info!("{}: {} {}", self.running(), method, args.join(" "));
+        if self.dry_run {
+            return Ok(());
+        }
+        self.emit_running(method, &args);
         let status = Command::new(method)
-            .arg("clone")
-            .arg(repo)
-            .args(extra)
+            .args(&args)
             .current_dir(&self.out_dir()?)
             .status()
-            .context(format!("Failed to run `{}`.", method))?;
+            .context(msg(Kind::Vcs, format!("Failed to run `{}`.", method)))?;
         if !status.success() {
-            bail!("`{} clone` did not finish successfully.", method);
+            fail!(
+                Vcs,
+                "`{} {}` did not finish successfully.",
+                method,
+                subcommand
+            );
         }
         Ok(())
     }
@@ -463,7 +2909,7 @@ impl Cloner {
 
 /// A helper function for cloning a crate into the current working directory.
 ///
-/// - `method_name` - Method to fetch crate. Options are "crate", "git", "hg", "pijul", "fossil", "auto"
+/// - `method_name` - Method to fetch crate. Options are "crate", "git", "hg", "pijul", "fossil", "svn", "darcs", "bzr", "github-archive", "auto"
 /// - `spec` - The name of the crate to clone
 /// - `version` - The semantic version (semver) of the spec crate to clone
 /// - `extra` - Additional arguments passed to clone command.
@@ -473,11 +2919,12 @@ pub fn clone(
     spec: &str,
     version: Option<&str>,
     extra: &[&str],
-) -> Result<(), Error> {
-    Cloner::new().clone(
-        CloneMethodKind::from(method_name).unwrap(),
-        spec,
-        version,
-        extra,
-    )
+) -> Result<ClonedPackage, CloneError> {
+    let method_kind = CloneMethodKind::from(method_name).ok_or_else(|| {
+        anyhow::Error::from(msg(
+            Kind::InvalidInput,
+            format!("Unknown clone method `{}`.", method_name),
+        ))
+    })?;
+    Cloner::new().clone(method_kind, spec, version, extra)
 }
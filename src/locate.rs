@@ -0,0 +1,47 @@
+//! Finding a package within a cloned repository.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How many directories deep a package is looked for.
+const MAX_DEPTH: usize = 5;
+
+/// Finds the directory of the package named `name` in a cloned repository,
+/// by reading the `Cargo.toml` files in it.
+///
+/// Directories are searched breadth-first, so the package closest to the
+/// root is found. Hidden directories and `target` are skipped.
+pub(crate) fn find_package(root: &Path, name: &str) -> Option<PathBuf> {
+    let mut queue = VecDeque::from([(root.to_path_buf(), 0)]);
+    while let Some((dir, depth)) = queue.pop_front() {
+        if manifest_name(&dir.join("Cargo.toml")).as_deref() == Some(name) {
+            return Some(dir);
+        }
+        if depth == MAX_DEPTH {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut subdirs: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .filter(|entry| {
+                let file_name = entry.file_name();
+                let file_name = file_name.to_string_lossy();
+                !file_name.starts_with('.') && file_name != "target"
+            })
+            .map(|entry| entry.path())
+            .collect();
+        subdirs.sort();
+        queue.extend(subdirs.into_iter().map(|subdir| (subdir, depth + 1)));
+    }
+    None
+}
+
+/// Reads the package name from a `Cargo.toml`.
+fn manifest_name(path: &Path) -> Option<String> {
+    let manifest: toml::Table = fs::read_to_string(path).ok()?.parse().ok()?;
+    Some(manifest.get("package")?.get("name")?.as_str()?.to_string())
+}
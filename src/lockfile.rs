@@ -0,0 +1,273 @@
+//! Reading package versions from a `Cargo.lock` file.
+
+use crate::error::{msg, Kind};
+use crate::{CloneError, CloneMethodKind, CloneRequest, Cloner};
+use anyhow::{Context, Error};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lockfile sources that refer to crates.io.
+const CRATES_IO_SOURCES: &[&str] = &[
+    "registry+https://github.com/rust-lang/crates.io-index",
+    "sparse+https://index.crates.io/",
+];
+
+/// A package listed in a `Cargo.lock` file.
+#[derive(Debug, Clone)]
+pub struct LockedPackage {
+    /// The package name.
+    pub name: String,
+    /// The exact version.
+    pub version: String,
+    /// Where the package comes from, such as
+    /// `registry+https://github.com/rust-lang/crates.io-index`.
+    ///
+    /// This is `None` for path dependencies and workspace members.
+    pub source: Option<String>,
+}
+
+impl LockedPackage {
+    /// Returns whether the package comes from crates.io.
+    pub fn is_crates_io(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_some_and(|s| CRATES_IO_SOURCES.contains(&s))
+    }
+}
+
+/// Reads all of the packages listed in a `Cargo.lock` file.
+pub fn read_lockfile(path: &Path) -> Result<Vec<LockedPackage>, CloneError> {
+    Ok(parse_lockfile(path)?)
+}
+
+pub(crate) fn parse_lockfile(path: &Path) -> Result<Vec<LockedPackage>, Error> {
+    let contents = fs::read_to_string(path).with_context(|| {
+        msg(
+            Kind::InvalidInput,
+            format!("Failed to read lockfile `{}`.", path.display()),
+        )
+    })?;
+    let lock: toml::Table = contents.parse().with_context(|| {
+        msg(
+            Kind::InvalidInput,
+            format!("Failed to parse lockfile `{}`.", path.display()),
+        )
+    })?;
+    let Some(packages) = lock.get("package").and_then(|p| p.as_array()) else {
+        return Ok(Vec::new());
+    };
+    packages
+        .iter()
+        .map(|pkg| {
+            let field = |key| pkg.get(key).and_then(|v| v.as_str()).map(str::to_string);
+            Ok(LockedPackage {
+                name: field("name")
+                    .with_context(|| format!("Package without a name in `{}`.", path.display()))?,
+                version: field("version").with_context(|| {
+                    format!("Package without a version in `{}`.", path.display())
+                })?,
+                source: field("source"),
+            })
+        })
+        .collect()
+}
+
+impl Cloner {
+    /// Sets a `Cargo.lock` file to pick the version to clone from.
+    ///
+    /// When set, the package is cloned at the exact version pinned in the
+    /// lockfile. If the lockfile has multiple versions of the package, a
+    /// version requirement can be given to pick one of them.
+    pub fn set_lockfile(&mut self, value: impl Into<PathBuf>) -> &mut Self {
+        self.lockfile = Some(value.into());
+        self
+    }
+
+    /// Sets the `Cargo.lock` file of the current project, to download the
+    /// version it pins when no version is given.
+    ///
+    /// Unlike [`Cloner::set_lockfile`], this only applies to `.crate`
+    /// downloads, and packages that are not pinned to a registry version in
+    /// the lockfile are resolved as usual. VCS methods, and branches, tags,
+    /// or revisions, clone the repository as usual, and
+    /// [`CloneMethodKind::Auto`] still prefers the repository. If the
+    /// lockfile has multiple versions of the package, the newest is used.
+    pub fn set_project_lockfile(&mut self, value: impl Into<PathBuf>) -> &mut Self {
+        self.project_lockfile = Some(value.into());
+        self
+    }
+
+    /// Creates a request for every registry package pinned in a `Cargo.lock`
+    /// file, to clone its exact version with the `crate` method.
+    ///
+    /// Local packages and git dependencies are skipped. When using crates.io,
+    /// packages from other registries are skipped too. Pass the requests to
+    /// [`Cloner::clone_many`] to clone them.
+    pub fn lockfile_requests(&self, lockfile: &Path) -> Result<Vec<CloneRequest>, CloneError> {
+        Ok(self.registry_requests(parse_lockfile(lockfile)?))
+    }
+
+    /// Creates a request for every registry package, skipping local packages
+    /// and packages from other sources.
+    pub(crate) fn registry_requests(&self, packages: Vec<LockedPackage>) -> Vec<CloneRequest> {
+        let mut requests = Vec::new();
+        for pkg in packages {
+            match &pkg.source {
+                None => debug!("Skipping local package `{} {}`", pkg.name, pkg.version),
+                Some(source) if source.starts_with("git+") => {
+                    warn!(
+                        "Skipping `{} {}` from git source `{}`",
+                        pkg.name, pkg.version, source
+                    )
+                }
+                Some(source)
+                    if !pkg.is_crates_io()
+                        && self.registry_url() == crate::DEFAULT_REGISTRY_URL =>
+                {
+                    warn!(
+                        "Skipping `{} {}` from registry `{}`",
+                        pkg.name, pkg.version, source
+                    )
+                }
+                Some(_) => requests.push(
+                    CloneRequest::new(format!("{}@={}", pkg.name, pkg.version))
+                        .method(CloneMethodKind::Crate),
+                ),
+            }
+        }
+        requests
+    }
+
+    /// Finds the version of a package pinned in the lockfile, returned as an
+    /// exact version requirement.
+    pub(crate) fn locked_version_req(
+        &self,
+        lockfile: &Path,
+        name: &str,
+        version_req: Option<&str>,
+    ) -> Result<String, Error> {
+        let req = version_req
+            .map(semver::VersionReq::parse)
+            .transpose()?
+            .unwrap_or(semver::VersionReq::STAR);
+        let packages: Vec<_> = parse_lockfile(lockfile)?
+            .into_iter()
+            .filter(|pkg| pkg.name == name)
+            .filter(|pkg| semver::Version::parse(&pkg.version).is_ok_and(|v| req.matches(&v)))
+            .collect();
+        let pkg = match packages.as_slice() {
+            [] => fail!(
+                Version,
+                "Package `{}` matching `{}` not found in `{}`.",
+                name,
+                req,
+                lockfile.display()
+            ),
+            [pkg] => pkg,
+            _ => fail!(
+                Version,
+                "Multiple versions of `{}` found in `{}`: {}\n\
+                 Specify a version to pick one.",
+                name,
+                lockfile.display(),
+                packages
+                    .iter()
+                    .map(|p| p.version.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        match &pkg.source {
+            None => fail!(
+                InvalidInput,
+                "Package `{} {}` is a local package in `{}`.",
+                name,
+                pkg.version,
+                lockfile.display()
+            ),
+            Some(source) if source.starts_with("git+") => fail!(
+                InvalidInput,
+                "Package `{} {}` comes from git source `{}`, not a registry.",
+                name,
+                pkg.version,
+                source
+            ),
+            Some(source)
+                if !pkg.is_crates_io() && self.registry_url() == crate::DEFAULT_REGISTRY_URL =>
+            {
+                fail!(
+                    InvalidInput,
+                    "Package `{} {}` comes from registry `{}`.\n\
+                     Set the registry URL to clone from it.",
+                    name,
+                    pkg.version,
+                    source
+                )
+            }
+            _ => {}
+        }
+        debug!(
+            "Using version {} of `{}` pinned in `{}`",
+            pkg.version,
+            name,
+            lockfile.display()
+        );
+        Ok(format!("={}", pkg.version))
+    }
+
+    /// Returns the version requirement of a `.crate` download, which is the
+    /// version pinned in the project's lockfile when none is given.
+    ///
+    /// VCS methods and checkouts clone the repository as usual.
+    pub(crate) fn crate_version_req(
+        &self,
+        name: &str,
+        version_req: Option<&str>,
+    ) -> Option<String> {
+        match (version_req, &self.project_lockfile) {
+            (Some(version_req), _) => Some(version_req.to_string()),
+            (None, Some(lockfile)) => self.project_version_req(lockfile, name),
+            (None, None) => None,
+        }
+    }
+
+    /// Finds the newest registry version of a package pinned in the
+    /// project's lockfile, returned as an exact version requirement.
+    fn project_version_req(&self, lockfile: &Path, name: &str) -> Option<String> {
+        let packages = match parse_lockfile(lockfile) {
+            Ok(packages) => packages,
+            Err(e) => {
+                warn!("Ignoring the project lockfile: {:#}", e);
+                return None;
+            }
+        };
+        let mut versions: Vec<_> = packages
+            .iter()
+            .filter(|pkg| pkg.name == name)
+            .filter(|pkg| match &pkg.source {
+                Some(source) if source.starts_with("git+") => false,
+                Some(_) => pkg.is_crates_io() || self.registry_url() != crate::DEFAULT_REGISTRY_URL,
+                None => false,
+            })
+            .filter_map(|pkg| semver::Version::parse(&pkg.version).ok())
+            .collect();
+        versions.sort_unstable();
+        let version = versions.pop()?;
+        if !versions.is_empty() {
+            warn!(
+                "Multiple versions of `{}` found in `{}`, using {}.\n\
+                 Specify a version to pick another one.",
+                name,
+                lockfile.display(),
+                version
+            );
+        }
+        info!(
+            "Using version {} of `{}` from `{}`",
+            version,
+            name,
+            lockfile.display()
+        );
+        Some(format!("={}", version))
+    }
+}
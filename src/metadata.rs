@@ -0,0 +1,121 @@
+//! Reading the resolved dependencies of a project with `cargo metadata`.
+
+use crate::{CloneError, CloneRequest, Cloner, LockedPackage};
+use anyhow::{Context, Error};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// The output of `cargo metadata --format-version 1`.
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<String>,
+    resolve: Option<Resolve>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    id: String,
+    name: String,
+    version: String,
+    source: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Resolve {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    id: String,
+    deps: Vec<NodeDep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeDep {
+    pkg: String,
+}
+
+impl Cloner {
+    /// Creates a request for every registry package a project depends on, to
+    /// clone its resolved version with the `crate` method.
+    ///
+    /// The dependencies are resolved by running `cargo metadata` for the
+    /// `Cargo.toml` at `manifest`, which may update its `Cargo.lock`. With
+    /// `transitive`, the whole dependency tree is included, otherwise only
+    /// the direct dependencies of the workspace members. Local packages and
+    /// git dependencies are skipped, as with [`Cloner::lockfile_requests`].
+    pub fn dependency_requests(
+        &self,
+        manifest: &Path,
+        transitive: bool,
+    ) -> Result<Vec<CloneRequest>, CloneError> {
+        Ok(self.dependency_requests_inner(manifest, transitive)?)
+    }
+
+    fn dependency_requests_inner(
+        &self,
+        manifest: &Path,
+        transitive: bool,
+    ) -> Result<Vec<CloneRequest>, Error> {
+        let metadata = cargo_metadata(manifest)?;
+        let Some(resolve) = metadata.resolve else {
+            fail!(
+                Other,
+                "`cargo metadata` did not resolve the dependencies of `{}`.",
+                manifest.display()
+            );
+        };
+        let ids: BTreeSet<&str> = if transitive {
+            resolve.nodes.iter().map(|node| node.id.as_str()).collect()
+        } else {
+            resolve
+                .nodes
+                .iter()
+                .filter(|node| metadata.workspace_members.contains(&node.id))
+                .flat_map(|node| node.deps.iter().map(|dep| dep.pkg.as_str()))
+                .collect()
+        };
+        let packages: HashMap<&str, &Package> = metadata
+            .packages
+            .iter()
+            .map(|pkg| (pkg.id.as_str(), pkg))
+            .collect();
+        let mut locked: Vec<LockedPackage> = ids
+            .into_iter()
+            .filter(|id| !metadata.workspace_members.iter().any(|member| member == id))
+            .filter_map(|id| packages.get(id))
+            .map(|pkg| LockedPackage {
+                name: pkg.name.clone(),
+                version: pkg.version.clone(),
+                source: pkg.source.clone(),
+            })
+            .collect();
+        locked.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        Ok(self.registry_requests(locked))
+    }
+}
+
+/// Runs `cargo metadata` for a `Cargo.toml`.
+fn cargo_metadata(manifest: &Path) -> Result<Metadata, Error> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    info!("Resolving the dependencies of `{}`", manifest.display());
+    let output = Command::new(cargo)
+        .args(["metadata", "--format-version", "1", "--manifest-path"])
+        .arg(manifest)
+        .output()
+        .context("Failed to run `cargo metadata`.")?;
+    if !output.status.success() {
+        fail!(
+            InvalidInput,
+            "`cargo metadata` did not finish successfully: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    serde_json::from_slice(&output.stdout)
+        .context("Failed to parse the output of `cargo metadata`.")
+}
@@ -0,0 +1,69 @@
+//! Opening a cloned package in an editor.
+
+use crate::{CloneError, Cloner};
+use anyhow::{bail, Context, Error};
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+impl Cloner {
+    /// Sets the command that [`Cloner::open`] runs, such as `code --wait`.
+    ///
+    /// Defaults to `$VISUAL`, or else `$EDITOR`.
+    pub fn set_open_command(&mut self, value: impl Into<String>) -> &mut Self {
+        self.open_command = Some(value.into());
+        self
+    }
+
+    /// Opens a directory, such as a cloned package, in an editor, and waits
+    /// for it to exit.
+    ///
+    /// The command is set with [`Cloner::set_open_command`], or taken from
+    /// the `VISUAL` or `EDITOR` environment variables. The path is added as
+    /// its last argument, and it is run in that directory.
+    pub fn open(&self, path: impl AsRef<Path>) -> Result<(), CloneError> {
+        Ok(self.open_inner(path.as_ref())?)
+    }
+
+    fn open_inner(&self, path: &Path) -> Result<(), Error> {
+        let command = self
+            .open_command
+            .clone()
+            .or_else(|| env::var("VISUAL").ok())
+            .or_else(|| env::var("EDITOR").ok())
+            .filter(|command| !command.trim().is_empty());
+        let Some(command) = command else {
+            fail!(
+                InvalidInput,
+                "No editor to open `{}` with, set `VISUAL` or `EDITOR`, \
+                 or the `open` config key.",
+                path.display()
+            );
+        };
+        // The command may have arguments of its own, like `code --wait`.
+        let mut args = command.split_whitespace();
+        // UNWRAP: The command is not empty.
+        let program = args.next().unwrap();
+        let args: Vec<&str> = args.collect();
+        info!(
+            "{}: {} {} {}",
+            self.running(),
+            program,
+            args.join(" "),
+            path.display()
+        );
+        if self.dry_run {
+            return Ok(());
+        }
+        let status = Command::new(program)
+            .args(&args)
+            .arg(path)
+            .current_dir(path)
+            .status()
+            .with_context(|| format!("Failed to run `{}`.", program))?;
+        if !status.success() {
+            bail!("`{}` did not finish successfully.", program);
+        }
+        Ok(())
+    }
+}
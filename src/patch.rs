@@ -0,0 +1,132 @@
+//! Adding cloned packages to the `[patch.crates-io]` table of a project.
+
+use crate::error::{msg, Kind};
+use crate::{CloneError, ClonedPackage, Cloner};
+use anyhow::{Context, Error};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Finds the `Cargo.toml` that patches apply to for the project enclosing
+/// `dir`, which is the root of its workspace.
+///
+/// Returns `None` if `dir` is not in a cargo project.
+pub fn find_workspace_manifest(dir: &Path) -> Option<PathBuf> {
+    let mut manifests = dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .filter(|path| path.is_file());
+    let nearest = manifests.next()?;
+    if is_workspace_root(&nearest) {
+        return Some(nearest);
+    }
+    Some(
+        manifests
+            .find(|path| is_workspace_root(path))
+            .unwrap_or(nearest),
+    )
+}
+
+/// Returns whether a `Cargo.toml` has a `[workspace]` table.
+fn is_workspace_root(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Table>().ok())
+        .is_some_and(|manifest| manifest.contains_key("workspace"))
+}
+
+impl Cloner {
+    /// Adds a cloned package to the `[patch.crates-io]` table of a
+    /// `Cargo.toml`, so the project builds with the clone instead of the
+    /// published package.
+    ///
+    /// The path is written relative to the manifest when the clone is inside
+    /// its directory. The table is created if needed, and the rest of the
+    /// file is left as is. It fails if the package is already patched.
+    pub fn add_patch(&self, manifest: &Path, package: &ClonedPackage) -> Result<(), CloneError> {
+        Ok(self.add_patch_inner(manifest, package)?)
+    }
+
+    fn add_patch_inner(&self, manifest: &Path, package: &ClonedPackage) -> Result<(), Error> {
+        let contents = fs::read_to_string(manifest)
+            .with_context(|| format!("Failed to read `{}`.", manifest.display()))?;
+        let table: toml::Table = contents.parse().with_context(|| {
+            msg(
+                Kind::InvalidInput,
+                format!("Failed to parse `{}`.", manifest.display()),
+            )
+        })?;
+        let root = manifest.parent().unwrap_or(Path::new(""));
+        let path = package.path.strip_prefix(root).unwrap_or(&package.path);
+        let path = path.to_string_lossy().replace('\\', "/");
+        let patches = table
+            .get("patch")
+            .and_then(|patch| patch.get("crates-io"))
+            .and_then(|patches| patches.as_table());
+        if let Some(patch) = patches.and_then(|patches| patches.get(&package.name)) {
+            // Cloning again, such as with `--force`, keeps the same patch.
+            if patch.get("path").and_then(|p| p.as_str()) == Some(path.as_str()) {
+                info!(
+                    "`{}` is already patched in `{}`",
+                    package.name,
+                    manifest.display()
+                );
+                return Ok(());
+            }
+            fail!(
+                InvalidInput,
+                "`{}` is already patched in `{}`.",
+                package.name,
+                manifest.display()
+            );
+        }
+        let entry = format!(
+            "{} = {{ path = {} }}\n",
+            package.name,
+            toml::Value::String(path)
+        );
+        let header = Regex::new(
+            r#"(?m)^\[\s*patch\s*\.\s*(crates-io|"crates-io")\s*\][ \t]*(#[^\r\n]*)?\r?$"#,
+        )
+        .unwrap();
+        let contents = match header.find(&contents) {
+            // Add the entry after the header line.
+            Some(m) => {
+                let mut contents = contents.clone();
+                if m.end() == contents.len() {
+                    contents.push('\n');
+                }
+                contents.insert_str(m.end() + 1, &entry);
+                contents
+            }
+            None if patches.is_some() => fail!(
+                InvalidInput,
+                "Could not find the `[patch.crates-io]` table in `{}`, \
+                 add `{}` to it.",
+                manifest.display(),
+                entry.trim_end()
+            ),
+            None => {
+                let mut contents = contents.clone();
+                if !contents.is_empty() && !contents.ends_with('\n') {
+                    contents.push('\n');
+                }
+                contents.push_str("\n[patch.crates-io]\n");
+                contents.push_str(&entry);
+                contents
+            }
+        };
+        if self.dry_run {
+            info!(
+                "Would add `{}` to `{}`",
+                entry.trim_end(),
+                manifest.display()
+            );
+            return Ok(());
+        }
+        info!("Adding `{}` to `{}`", entry.trim_end(), manifest.display());
+        fs::write(manifest, contents)
+            .with_context(|| format!("Failed to write `{}`.", manifest.display()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+//! Checking that the programs run by VCS methods are installed.
+
+use crate::{CloneMethodKind, Cloner};
+use anyhow::Error;
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// How to install the program of each VCS method.
+const INSTALL_HINTS: &[(&str, &str)] = &[
+    ("git", "Install git from https://git-scm.com/downloads"),
+    (
+        "hg",
+        "Install Mercurial from https://www.mercurial-scm.org/install",
+    ),
+    ("pijul", "Install pijul with `cargo install pijul`"),
+    (
+        "fossil",
+        "Install fossil from https://fossil-scm.org/home/uv/download.html",
+    ),
+    (
+        "svn",
+        "Install Subversion from https://subversion.apache.org/packages.html",
+    ),
+    ("darcs", "Install darcs from https://darcs.net/Binaries"),
+    (
+        "bzr",
+        "Install Breezy, which provides `bzr`, from https://www.breezy-vcs.org",
+    ),
+];
+
+impl Cloner {
+    /// Returns the methods that can be used on this system.
+    ///
+    /// The `crate`, `github-archive`, and `auto` methods are always
+    /// available, while a VCS method needs its program on `PATH`. With the
+    /// `gix` feature, git repositories can be cloned without `git`. Custom
+    /// methods are not included.
+    pub fn available_methods(&self) -> Vec<CloneMethodKind> {
+        let all = [
+            CloneMethodKind::Crate,
+            CloneMethodKind::Git,
+            CloneMethodKind::Mercurial,
+            CloneMethodKind::Pijul,
+            CloneMethodKind::Fossil,
+            CloneMethodKind::Subversion,
+            CloneMethodKind::Darcs,
+            CloneMethodKind::Bazaar,
+            CloneMethodKind::GithubArchive,
+            CloneMethodKind::Auto,
+        ];
+        all.into_iter()
+            .filter(|method| method_program(method).is_none_or(|p| find_program(p).is_some()))
+            .collect()
+    }
+
+    /// Fails if the program a method runs is not installed, with a hint on
+    /// how to install it.
+    pub(crate) fn check_program(&self, method: &CloneMethodKind) -> Result<(), Error> {
+        match method_program(method) {
+            Some(program) => self.require_program(program, method),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails if `program`, which `method` runs, is not installed.
+    pub(crate) fn require_program(
+        &self,
+        program: &str,
+        method: &CloneMethodKind,
+    ) -> Result<(), Error> {
+        if self.dry_run || find_program(program).is_some() {
+            return Ok(());
+        }
+        let hint = INSTALL_HINTS
+            .iter()
+            .find(|(name, _)| *name == program)
+            .map_or("Install it", |(_, hint)| hint);
+        fail!(
+            Vcs,
+            "`{}` is needed for the `{}` method, but was not found on PATH. \
+             {}, or use another method such as `crate`.",
+            program,
+            method.command(),
+            hint
+        );
+    }
+}
+
+/// Returns the program a method runs, if it runs one.
+fn method_program(method: &CloneMethodKind) -> Option<&str> {
+    match method {
+        CloneMethodKind::Crate | CloneMethodKind::GithubArchive | CloneMethodKind::Auto => None,
+        // gix clones without the binary.
+        CloneMethodKind::Git if cfg!(feature = "gix") => None,
+        CloneMethodKind::Custom { command, .. } => Some(command),
+        method => Some(method.command()),
+    }
+}
+
+/// Finds a program by name in the directories of `PATH`, or checks a path to
+/// one.
+fn find_program(program: &str) -> Option<PathBuf> {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return is_executable(path).then(|| path.to_path_buf());
+    }
+    // Windows looks for the program with each of the extensions in PATHEXT.
+    let mut names = vec![OsString::from(program)];
+    if cfg!(windows) {
+        let exts = env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+        names.extend(
+            exts.split(';')
+                .map(|ext| format!("{}{}", program, ext).into()),
+        );
+    }
+    env::split_paths(&env::var_os("PATH")?)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|path| is_executable(path))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
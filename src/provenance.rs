@@ -0,0 +1,75 @@
+//! Recording where a cloned package came from in a `.cargo-clone.json` file.
+
+use crate::{reproducible, ClonedPackage, Cloner};
+use anyhow::{Context, Error};
+use serde::Deserialize;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// The name of the file written into every cloned package.
+pub const METADATA_FILE: &str = ".cargo-clone.json";
+
+/// The parts of a `.cargo-clone.json` that are read back.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Provenance {
+    pub(crate) name: String,
+    pub(crate) version: Option<String>,
+    pub(crate) method: String,
+    pub(crate) source: String,
+}
+
+impl Cloner {
+    /// Sets whether a `.cargo-clone.json` file is written into every cloned
+    /// package. Defaults to `true`.
+    ///
+    /// It records the name, version, method, and source URL of the package,
+    /// the checksum of the `.crate` file, and when it was cloned. With
+    /// [`Cloner::set_reproducible`], the time is left out. Nothing is written
+    /// for a `.crate` file that is not extracted.
+    pub fn set_metadata_file(&mut self, value: bool) -> &mut Self {
+        self.metadata_file = value;
+        self
+    }
+
+    /// Writes the `.cargo-clone.json` of a cloned package, if enabled.
+    pub(crate) fn write_metadata_file(&self, package: &ClonedPackage) -> Result<(), Error> {
+        if !self.metadata_file || self.dry_run || !package.path.is_dir() {
+            return Ok(());
+        }
+        let mut value = json!({
+            "name": package.name,
+            "version": package.version,
+            "method": package.method.command(),
+            "source": package.source,
+            "checksum": package.checksum,
+        });
+        if !self.reproducible {
+            value["timestamp"] = humantime::format_rfc3339_seconds(SystemTime::now())
+                .to_string()
+                .into();
+        }
+        let path = package.path.join(METADATA_FILE);
+        let text = format!("{}\n", serde_json::to_string_pretty(&value)?);
+        fs::write(&path, text).with_context(|| format!("Failed to write `{}`.", path.display()))?;
+        if self.reproducible {
+            // Adding the file changed the time of the directory.
+            reproducible::normalize(&package.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the `.cargo-clone.json` of a package in `dir`, if it has one.
+pub(crate) fn read_metadata_file(dir: &Path) -> Result<Option<Provenance>, Error> {
+    let path = dir.join(METADATA_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read `{}`.", path.display()))?;
+    let provenance = serde_json::from_str(&text)
+        .with_context(|| format!("Failed to parse `{}`.", path.display()))?;
+    Ok(Some(provenance))
+}
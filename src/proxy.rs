@@ -0,0 +1,80 @@
+//! Proxy settings for HTTP requests.
+
+use crate::error::{msg, Kind};
+use crate::registry::{load_configs, lookup};
+use crate::Cloner;
+use anyhow::{Context, Error};
+use reqwest::{NoProxy, Proxy};
+use std::env;
+use std::sync::OnceLock;
+
+impl Cloner {
+    /// Sets the proxy used for HTTP and HTTPS requests, such as
+    /// `http://proxy.example.com:8080`.
+    ///
+    /// If not set, cargo's proxy setting is used: the `CARGO_HTTP_PROXY`
+    /// environment variable, or `http.proxy` in the `.cargo/config.toml`
+    /// files. Otherwise, the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY`, and
+    /// `NO_PROXY` environment variables are used.
+    ///
+    /// This only applies to the requests made to the registry and other
+    /// APIs. VCS commands use their own proxy configuration.
+    pub fn set_proxy(&mut self, value: impl Into<String>) -> &mut Self {
+        self.proxy = Some(value.into());
+        self
+    }
+
+    /// Sets a comma-separated list of hosts that bypass the proxy, in the
+    /// same form as the `NO_PROXY` environment variable, which is used if not
+    /// set.
+    pub fn set_no_proxy(&mut self, value: impl Into<String>) -> &mut Self {
+        self.no_proxy = Some(value.into());
+        self
+    }
+
+    /// Returns the proxy to use, if one is configured.
+    ///
+    /// `None` leaves reqwest to use the proxy environment variables.
+    pub(crate) fn proxy(&self) -> Result<Option<Proxy>, Error> {
+        let url = match &self.proxy {
+            Some(url) => url.clone(),
+            None => match cargo_proxy()? {
+                Some(url) => url,
+                None => return Ok(None),
+            },
+        };
+        // Like cargo, a proxy without a scheme is an HTTP proxy.
+        let url = if url.contains("://") {
+            url
+        } else {
+            format!("http://{}", url)
+        };
+        debug!("Using proxy {}", url);
+        let no_proxy = match &self.no_proxy {
+            Some(list) => NoProxy::from_string(list),
+            None => NoProxy::from_env(),
+        };
+        let proxy = Proxy::all(&url)
+            .context(msg(Kind::InvalidInput, format!("Invalid proxy `{}`.", url)))?;
+        Ok(Some(proxy.no_proxy(no_proxy)))
+    }
+}
+
+/// Cargo's proxy setting, which is only looked up once.
+static CARGO_PROXY: OnceLock<Option<String>> = OnceLock::new();
+
+/// Finds cargo's `http.proxy` setting.
+fn cargo_proxy() -> Result<Option<String>, Error> {
+    if let Some(proxy) = CARGO_PROXY.get() {
+        return Ok(proxy.clone());
+    }
+    let proxy = match env::var("CARGO_HTTP_PROXY") {
+        Ok(proxy) => Some(proxy),
+        Err(_) => {
+            let cwd = env::current_dir().context("Failed to get the current directory.")?;
+            lookup(&load_configs(&cwd)?, &["http", "proxy"])
+        }
+    };
+    let proxy = proxy.filter(|proxy| !proxy.is_empty());
+    Ok(CARGO_PROXY.get_or_init(|| proxy).clone())
+}
@@ -0,0 +1,440 @@
+//! Alternative registries defined in cargo's configuration.
+//!
+//! Registries are looked up the same way cargo does: from the
+//! `CARGO_REGISTRIES_<NAME>_INDEX` and `CARGO_REGISTRIES_<NAME>_TOKEN`
+//! environment variables, the `[registries]` table of the `.cargo/config.toml`
+//! files from the current directory up to the root and in `$CARGO_HOME`, and
+//! `$CARGO_HOME/credentials.toml` for tokens.
+//!
+//! Cargo's source replacement (`[source.<name>] replace-with = "…"`) is
+//! followed the same way. This also has the mirrors used when a registry is
+//! unavailable.
+
+use crate::store::run_git;
+use crate::{CloneError, Cloner, DEFAULT_REGISTRY_URL};
+use anyhow::{Context, Error};
+use serde_json::Value;
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use toml::Table;
+
+impl Cloner {
+    /// Uses a registry defined in cargo's configuration, like cargo's
+    /// `--registry` flag.
+    ///
+    /// The index URL and token of the registry are resolved the same way
+    /// cargo does. The name `crates-io` selects crates.io. A sparse index
+    /// (`sparse+https://…`) is read directly, see
+    /// [`Cloner::set_registry_url`]. For a git index, the index is fetched to
+    /// find the registry's web API, which is then used like crates.io's.
+    ///
+    /// If the registry is replaced with cargo's source replacement
+    /// (`[source.<name>] replace-with = "…"`), the replacement is used
+    /// instead, which can be another registry, a `registry` index URL, or a
+    /// `local-registry` directory, see [`Cloner::set_local_registry`].
+    ///
+    /// The token is only sent with requests to the registry itself.
+    pub fn set_registry_name(&mut self, name: &str) -> Result<&mut Self, CloneError> {
+        Ok(self
+            .set_registry_name_inner(name)
+            .with_context(|| format!("Failed to load registry `{}`.", name))?)
+    }
+
+    fn set_registry_name_inner(&mut self, name: &str) -> Result<&mut Self, Error> {
+        let cwd = env::current_dir().context("Failed to get the current directory.")?;
+        let configs = load_configs(&cwd)?;
+        let source = replace_source(&configs, name)?;
+        self.use_source(&configs, source)
+    }
+
+    /// Uses the replacement of crates.io from cargo's source replacement, if
+    /// there is one.
+    pub(crate) fn replace_crates_io(&mut self) -> Result<&mut Self, Error> {
+        let cwd = env::current_dir().context("Failed to get the current directory.")?;
+        let configs = load_configs(&cwd)?;
+        match replace_source(&configs, "crates-io")? {
+            Source::Registry(name) if name == "crates-io" => Ok(self),
+            // Such as the `directory` source of `cargo vendor`, which only
+            // has the project's dependencies.
+            Source::Unsupported(kind, name) => {
+                warn!(
+                    "Ignoring the replacement of crates.io with the {} source `{}`, \
+                     which is not supported.",
+                    kind, name
+                );
+                Ok(self)
+            }
+            source => self.use_source(&configs, source),
+        }
+    }
+
+    fn use_source(&mut self, configs: &[CargoConfig], source: Source) -> Result<&mut Self, Error> {
+        self.local_registry = None;
+        self.git_index = None;
+        let name = match source {
+            Source::Registry(name) => name,
+            Source::Index(index) => {
+                self.set_index(index);
+                self.registry_token = None;
+                return Ok(self);
+            }
+            Source::Local(dir) => {
+                self.registry_url = DEFAULT_REGISTRY_URL.to_string();
+                self.registry_token = None;
+                self.local_registry = Some(dir);
+                return Ok(self);
+            }
+            Source::Unsupported(kind, name) => fail!(
+                InvalidInput,
+                "The registry is replaced with the {} source `{}`, which is not supported.",
+                kind,
+                name
+            ),
+        };
+        if name == "crates-io" {
+            self.registry_url = DEFAULT_REGISTRY_URL.to_string();
+            self.registry_token = env::var("CARGO_REGISTRY_TOKEN")
+                .ok()
+                .or_else(|| lookup(configs, &["registry", "token"]));
+            return Ok(self);
+        }
+
+        let env_name = name.to_uppercase().replace('-', "_");
+        let index = match env::var(format!("CARGO_REGISTRIES_{}_INDEX", env_name)) {
+            Ok(index) => index,
+            Err(_) => match lookup(configs, &["registries", &name, "index"]) {
+                Some(index) => index,
+                None => fail!(
+                    InvalidInput,
+                    "Registry `{}` is not defined in the cargo config.",
+                    name
+                ),
+            },
+        };
+        debug!("Index of registry `{}` is {}", name, index);
+        self.set_index(index);
+        self.registry_token = env::var(format!("CARGO_REGISTRIES_{}_TOKEN", env_name))
+            .ok()
+            .or_else(|| lookup(configs, &["registries", &name, "token"]));
+        Ok(self)
+    }
+
+    /// Uses a cargo index URL. The web API of a git index is only looked up
+    /// when it is needed, see [`Cloner::resolve_git_index`].
+    fn set_index(&mut self, index: String) {
+        if !index.starts_with("sparse+") {
+            self.git_index = Some(index.clone());
+            self.git_index_api = Arc::default();
+        }
+        self.registry_url = index;
+        self.sparse_dl = Arc::default();
+    }
+
+    /// Looks up the web API of the git index in use, if any, so that
+    /// [`Cloner::registry_url`] returns it.
+    ///
+    /// The index is cloned into the cache directory once, and reused by
+    /// later runs.
+    pub(crate) fn resolve_git_index(&self) -> Result<(), Error> {
+        let Some(index) = &self.git_index else {
+            return Ok(());
+        };
+        if self.git_index_api.get().is_none() {
+            let api = git_index_api(index, self.cache_dir.as_deref())
+                .with_context(|| format!("Failed to read registry index `{}`.", index))?;
+            debug!("Web API of registry index `{}` is {}", index, api);
+            let _ = self.git_index_api.set(api);
+        }
+        Ok(())
+    }
+
+    /// Returns the URL of the registry's web API or sparse index.
+    ///
+    /// For a git index, this is the index URL until its web API has been
+    /// looked up with [`Cloner::resolve_git_index`].
+    pub(crate) fn registry_url(&self) -> &str {
+        match (&self.git_index, self.git_index_api.get()) {
+            (Some(_), Some(api)) => api,
+            _ => &self.registry_url,
+        }
+    }
+
+    /// Reads packages from a local registry, a directory with an `index`
+    /// directory and the `.crate` files, as made by `cargo local-registry`.
+    ///
+    /// Package information comes from the index, which has no repository
+    /// information, like a sparse index. This is cleared by
+    /// [`Cloner::set_registry_url`].
+    pub fn set_local_registry(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.local_registry = Some(path.into());
+        self
+    }
+
+    /// Sets the token sent with requests to the registry's API, index, and
+    /// downloads, in the `Authorization` header.
+    ///
+    /// This replaces any token found with [`Cloner::set_registry_name`].
+    pub fn set_token(&mut self, value: impl Into<String>) -> &mut Self {
+        self.registry_token = Some(value.into());
+        self
+    }
+
+    /// Adds a mirror of the registry, used when the registry or the mirrors
+    /// added before it can't be reached or fail with a server error (`5xx`).
+    ///
+    /// A mirror serves the same paths as the registry, and is the same kind
+    /// of registry: the crates.io web API, or a sparse index starting with
+    /// `sparse+`. This applies to package information and to downloads from
+    /// the registry's URL, and the registry token is not sent to mirrors.
+    pub fn add_registry_mirror(&mut self, url: impl Into<String>) -> &mut Self {
+        self.registry_mirrors.push(url.into());
+        self
+    }
+
+    /// Returns the token to send with a request, if the URL is part of the
+    /// registry.
+    pub(crate) fn registry_token_for(&self, url: &str) -> Option<&str> {
+        let is_registry = registry_path(self.registry_url(), url).is_some();
+        self.registry_token.as_deref().filter(|_| is_registry)
+    }
+
+    /// Returns the URL, followed by the same URL on each mirror if it is part
+    /// of the registry.
+    pub(crate) fn mirror_urls(&self, url: &str) -> Vec<String> {
+        let mut urls = vec![url.to_string()];
+        if let Some(path) = registry_path(self.registry_url(), url) {
+            urls.extend(
+                self.registry_mirrors
+                    .iter()
+                    .map(|mirror| format!("{}{}", registry_base(mirror), path)),
+            );
+        }
+        urls
+    }
+
+    /// Logs that a request to the registry or a mirror failed, and the next
+    /// mirror is tried.
+    pub(crate) fn log_mirror_fallback(&self, url: &str, reason: impl Display) {
+        warn!(
+            "Request to `{}` failed, trying the next mirror: {:#}",
+            url, reason
+        );
+    }
+}
+
+/// Returns the URL of a registry's API or sparse index, without `sparse+` and
+/// a trailing slash.
+fn registry_base(registry_url: &str) -> &str {
+    registry_url
+        .strip_prefix("sparse+")
+        .unwrap_or(registry_url)
+        .trim_end_matches('/')
+}
+
+/// Returns the path of a URL within the registry, if it is part of it.
+fn registry_path<'a>(registry_url: &str, url: &'a str) -> Option<&'a str> {
+    url.strip_prefix(registry_base(registry_url))
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// A source of packages in cargo's configuration.
+enum Source {
+    /// A registry from the `[registries]` table, or `crates-io`.
+    Registry(String),
+    /// The index URL of a `registry` source.
+    Index(String),
+    /// The directory of a `local-registry` source.
+    Local(PathBuf),
+    /// A kind of source that isn't supported, such as `directory`, and its
+    /// name.
+    Unsupported(&'static str, String),
+}
+
+/// Follows the `replace-with` keys of cargo's `[source]` tables, starting
+/// from the source `name`.
+fn replace_source(configs: &[CargoConfig], name: &str) -> Result<Source, Error> {
+    let mut current = name.to_string();
+    let mut seen = Vec::new();
+    while let Some(next) = lookup(configs, &["source", &current, "replace-with"]) {
+        seen.push(current);
+        if seen.contains(&next) {
+            fail!(
+                InvalidInput,
+                "The source replacement of `{}` has a cycle at `{}`.",
+                name,
+                next
+            );
+        }
+        current = next;
+    }
+    if current == name {
+        return Ok(Source::Registry(current));
+    }
+    debug!("Source `{}` is replaced with `{}`", name, current);
+    if let Some(index) = lookup(configs, &["source", &current, "registry"]) {
+        return Ok(Source::Index(index));
+    }
+    if let Some((config, dir)) = lookup_entry(configs, &["source", &current, "local-registry"]) {
+        // Relative paths are relative to the directory containing `.cargo`.
+        let base = config
+            .parent()
+            .and_then(Path::parent)
+            .unwrap_or(Path::new(""));
+        return Ok(Source::Local(base.join(dir)));
+    }
+    for kind in ["directory", "git"] {
+        if lookup(configs, &["source", &current, kind]).is_some() {
+            return Ok(Source::Unsupported(kind, current));
+        }
+    }
+    Ok(Source::Registry(current))
+}
+
+/// Returns cargo's home directory, `$CARGO_HOME` or `~/.cargo`.
+pub(crate) fn cargo_home() -> Option<PathBuf> {
+    env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cargo")))
+}
+
+/// A cargo config file and its contents.
+pub(crate) type CargoConfig = (PathBuf, Table);
+
+/// Loads the cargo config files, closest first, followed by the credentials.
+pub(crate) fn load_configs(cwd: &Path) -> Result<Vec<CargoConfig>, Error> {
+    let cargo_home = cargo_home();
+    let mut paths = Vec::new();
+    for dir in cwd.ancestors() {
+        paths.push(dir.join(".cargo"));
+    }
+    if let Some(cargo_home) = &cargo_home {
+        if !paths.contains(cargo_home) {
+            paths.push(cargo_home.clone());
+        }
+    }
+    let mut configs = Vec::new();
+    for dir in paths {
+        for file in ["config.toml", "config"] {
+            let path = dir.join(file);
+            if let Some(table) = read_table(&path)? {
+                configs.push((path, table));
+                break;
+            }
+        }
+    }
+    if let Some(cargo_home) = &cargo_home {
+        for file in ["credentials.toml", "credentials"] {
+            let path = cargo_home.join(file);
+            if let Some(table) = read_table(&path)? {
+                configs.push((path, table));
+                break;
+            }
+        }
+    }
+    Ok(configs)
+}
+
+/// Reads a TOML file, returning `None` if it doesn't exist.
+pub(crate) fn read_table(path: &Path) -> Result<Option<Table>, Error> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    debug!("Reading cargo config `{}`", path.display());
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read `{}`.", path.display()))?;
+    let table = text
+        .parse()
+        .with_context(|| format!("Failed to parse `{}`.", path.display()))?;
+    Ok(Some(table))
+}
+
+/// Finds a string by its key path in the first config that has it.
+pub(crate) fn lookup(configs: &[CargoConfig], keys: &[&str]) -> Option<String> {
+    Some(lookup_entry(configs, keys)?.1)
+}
+
+/// Finds a string by its key path in the first config that has it, along
+/// with the path of that config.
+fn lookup_entry<'a>(configs: &'a [CargoConfig], keys: &[&str]) -> Option<(&'a Path, String)> {
+    configs.iter().find_map(|(path, config)| {
+        let (last, tables) = keys.split_last()?;
+        let mut table = config;
+        for key in tables {
+            table = table.get(*key)?.as_table()?;
+        }
+        Some((path.as_path(), table.get(*last)?.as_str()?.to_string()))
+    })
+}
+
+/// Fetches a git index to read the URL of the registry's web API.
+///
+/// With a cache directory, the index is cloned into its `index` directory,
+/// and an existing clone is reused. Otherwise, it is cloned into a temporary
+/// directory.
+fn git_index_api(index: &str, cache_dir: Option<&Path>) -> Result<String, Error> {
+    let Some(cache_dir) = cache_dir else {
+        let tmp = tempfile::tempdir().context("Failed to create temporary directory.")?;
+        clone_index(index, tmp.path())?;
+        return read_index_api(index, tmp.path());
+    };
+    let dir = cache_dir.join("index").join(index_dir_name(index));
+    if !dir.join("config.json").is_file() {
+        let parent = cache_dir.join("index");
+        fs::create_dir_all(&parent)
+            .with_context(|| format!("Failed to create `{}`.", parent.display()))?;
+        // Cloned next to it first, so that a concurrent run never sees a
+        // partial clone.
+        let tmp = tempfile::tempdir_in(&parent).context("Failed to create temporary directory.")?;
+        clone_index(index, tmp.path())?;
+        let _ = fs::remove_dir_all(&dir);
+        if let Err(e) = fs::rename(tmp.path(), &dir) {
+            // Another run may have cloned it in the meantime.
+            debug!("Failed to move the index to `{}`: {}", dir.display(), e);
+            return read_index_api(index, tmp.path());
+        }
+    }
+    read_index_api(index, &dir)
+}
+
+/// Returns the name of the directory a git index is cached in, from its URL.
+fn index_dir_name(index: &str) -> String {
+    let url = index.split_once("://").map_or(index, |(_, rest)| rest);
+    url.trim_end_matches('/')
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '_' | '-' => c,
+            _ => '-',
+        })
+        .collect()
+}
+
+/// Clones a git index into `dir`.
+fn clone_index(index: &str, dir: &Path) -> Result<(), Error> {
+    info!("Fetching registry index `{}`", index);
+    run_git(
+        Command::new("git")
+            .args(["clone", "--depth=1", "--quiet", index])
+            .arg(dir),
+    )
+}
+
+/// Reads the URL of the registry's web API from a clone of its index.
+fn read_index_api(index: &str, dir: &Path) -> Result<String, Error> {
+    let config_path = dir.join("config.json");
+    let config: Value = serde_json::from_str(
+        &fs::read_to_string(&config_path).context("Failed to read the index config.json.")?,
+    )
+    .context("Failed to parse the index config.json.")?;
+    match config["api"].as_str() {
+        Some(api) => Ok(api.trim_end_matches('/').to_string()),
+        None => fail!(
+            InvalidInput,
+            "Registry index `{}` has no web API, which is needed for a git index.",
+            index
+        ),
+    }
+}
@@ -0,0 +1,85 @@
+//! Checking that a repository exists before running a VCS to clone it.
+
+use crate::{CloneMethodKind, Cloner};
+use anyhow::Error;
+use reqwest::StatusCode;
+
+/// Parts of the path of a page that asks to log in.
+const LOGIN_PATHS: &[&str] = &["login", "signin", "sign_in", "sign-in"];
+
+impl Cloner {
+    /// Sets whether the repository URL is requested before running a VCS,
+    /// to check that it exists.
+    ///
+    /// Defaults to `true`. Only `http` and `https` URLs are checked. A
+    /// repository that is not found, needs authentication, or redirects to a
+    /// login page fails with [`CloneError::Repository`], instead of the VCS
+    /// asking for credentials.
+    ///
+    /// [`CloneError::Repository`]: crate::CloneError::Repository
+    pub fn set_check_repo(&mut self, value: bool) -> &mut Self {
+        self.check_repo = value;
+        self
+    }
+
+    /// Fails if the repository at `repo` appears to be private or deleted.
+    ///
+    /// Other failures are left to the VCS to report.
+    pub(crate) fn check_repo(&self, method: &CloneMethodKind, repo: &str) -> Result<(), Error> {
+        if !self.check_repo || self.dry_run {
+            return Ok(());
+        }
+        let Some(url) = probe_url(method, repo) else {
+            return Ok(());
+        };
+        self.check_cancelled()?;
+        let response = match self.http_get(&url, None) {
+            Ok(response) => response,
+            Err(e) => {
+                debug!("Failed to check `{}`: {:#}", repo, e);
+                return Ok(());
+            }
+        };
+        let status = response.status();
+        let reason = match status {
+            StatusCode::NOT_FOUND
+            | StatusCode::GONE
+            | StatusCode::UNAUTHORIZED
+            | StatusCode::FORBIDDEN => status.to_string(),
+            _ if response.url().as_str() != url && is_login_page(response.url()) => {
+                format!("redirected to `{}`", response.url())
+            }
+            _ => return Ok(()),
+        };
+        fail!(
+            Repository,
+            "The repository `{}` appears to be private or deleted ({}). \
+             Check the repository URL, or clone the `.crate` file with `--method crate`.",
+            repo,
+            reason
+        );
+    }
+}
+
+/// Returns the URL to request to check a repository, or `None` if it can't
+/// be checked over HTTP.
+fn probe_url(method: &CloneMethodKind, repo: &str) -> Option<String> {
+    if !repo.starts_with("http://") && !repo.starts_with("https://") {
+        return None;
+    }
+    let repo = repo.trim_end_matches('/');
+    match method {
+        // What a custom command does with the URL is up to the command.
+        CloneMethodKind::Custom { .. } => None,
+        // The first request of a git clone over HTTP.
+        CloneMethodKind::Git => Some(format!("{}/info/refs?service=git-upload-pack", repo)),
+        CloneMethodKind::Mercurial => Some(format!("{}?cmd=capabilities", repo)),
+        _ => Some(repo.to_string()),
+    }
+}
+
+/// Checks if a page that a repository redirected to asks to log in.
+fn is_login_page(url: &reqwest::Url) -> bool {
+    let path = url.path().to_lowercase();
+    LOGIN_PATHS.iter().any(|part| path.contains(part))
+}
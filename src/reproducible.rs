@@ -0,0 +1,57 @@
+//! Extracting packages so that every extraction has the same timestamps and
+//! permissions.
+
+use crate::Cloner;
+use anyhow::{Context, Error};
+use filetime::FileTime;
+use std::fs;
+use std::path::Path;
+
+/// The modification time of every extracted file, 1980-01-01 00:00:00 UTC,
+/// the earliest time a zip file can store.
+const MTIME: i64 = 315_532_800;
+
+impl Cloner {
+    /// Sets whether extracted packages are normalized so that extracting the
+    /// same `.crate` file is identical on every machine.
+    ///
+    /// Every file and directory gets the same modification time. On Unix,
+    /// directories and executable files get mode `0755`, and other files
+    /// `0644`, regardless of the umask. Packages are always downloaded with
+    /// the `crate` method.
+    pub fn set_reproducible(&mut self, value: bool) -> &mut Self {
+        self.reproducible = value;
+        self
+    }
+}
+
+/// Sets the timestamps and permissions of `path`, and everything under it if
+/// it is a directory.
+pub(crate) fn normalize(path: &Path) -> Result<(), Error> {
+    let metadata = fs::symlink_metadata(path)
+        .with_context(|| format!("Failed to read `{}`.", path.display()))?;
+    let mtime = FileTime::from_unix_time(MTIME, 0);
+    if metadata.is_symlink() {
+        return filetime::set_symlink_file_times(path, mtime, mtime)
+            .with_context(|| format!("Failed to set the time of `{}`.", path.display()));
+    }
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            normalize(&entry?.path())?;
+        }
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = if metadata.is_dir() || metadata.permissions().mode() & 0o111 != 0 {
+            0o755
+        } else {
+            0o644
+        };
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .with_context(|| format!("Failed to set the permissions of `{}`.", path.display()))?;
+    }
+    // A directory's time is set last, since changing its entries updates it.
+    filetime::set_file_mtime(path, mtime)
+        .with_context(|| format!("Failed to set the time of `{}`.", path.display()))
+}
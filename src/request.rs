@@ -0,0 +1,158 @@
+//! Options for cloning a single package, for [`Cloner::execute`].
+
+use crate::checkout::Checkout;
+use crate::{CloneError, CloneMethodKind, ClonedPackage, Cloner};
+
+/// A package to clone with [`Cloner::execute`] or [`Cloner::clone_many`].
+///
+/// The options set on the request replace those of the `Cloner` for this
+/// package only, such as
+/// `CloneRequest::new("serde").method(CloneMethodKind::Git).tag("v1.0.200")`.
+#[derive(Debug, Clone)]
+pub struct CloneRequest {
+    method: CloneMethodKind,
+    spec: String,
+    version: Option<String>,
+    extra: Vec<String>,
+    dest_name: Option<String>,
+    checkout: Option<Checkout>,
+    shallow: Option<bool>,
+    recurse_submodules: Option<bool>,
+    extract_filter: Option<(Vec<String>, Vec<String>)>,
+}
+
+impl CloneRequest {
+    /// Creates a request to clone a package with the `auto` method.
+    ///
+    /// `spec` is the name of the crate, optionally with a version, such as
+    /// `serde@1`.
+    pub fn new(spec: impl Into<String>) -> CloneRequest {
+        CloneRequest {
+            method: CloneMethodKind::Auto,
+            spec: spec.into(),
+            version: None,
+            extra: Vec::new(),
+            dest_name: None,
+            checkout: None,
+            shallow: None,
+            recurse_submodules: None,
+            extract_filter: None,
+        }
+    }
+
+    /// Sets the method to fetch the package with.
+    pub fn method(mut self, value: CloneMethodKind) -> CloneRequest {
+        self.method = value;
+        self
+    }
+
+    /// Sets the semantic version (semver) of the crate to clone.
+    pub fn version(mut self, value: impl Into<String>) -> CloneRequest {
+        self.version = Some(value.into());
+        self
+    }
+
+    /// Sets additional arguments passed to the clone command.
+    pub fn extra<I, S>(mut self, args: I) -> CloneRequest
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extra = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the name of the destination directory, like
+    /// [`Cloner::set_dest_name`].
+    pub fn dest_name(mut self, value: impl Into<String>) -> CloneRequest {
+        self.dest_name = Some(value.into());
+        self
+    }
+
+    /// Sets the branch to check out, like [`Cloner::set_branch`].
+    pub fn branch(mut self, value: impl Into<String>) -> CloneRequest {
+        self.checkout = Some(Checkout::Branch(value.into()));
+        self
+    }
+
+    /// Sets the tag to check out, like [`Cloner::set_tag`].
+    pub fn tag(mut self, value: impl Into<String>) -> CloneRequest {
+        self.checkout = Some(Checkout::Tag(value.into()));
+        self
+    }
+
+    /// Sets the revision to check out, like [`Cloner::set_rev`].
+    pub fn rev(mut self, value: impl Into<String>) -> CloneRequest {
+        self.checkout = Some(Checkout::Rev(value.into()));
+        self
+    }
+
+    /// Sets whether to clone only the latest commit, like
+    /// [`Cloner::set_shallow`].
+    pub fn shallow(mut self, value: bool) -> CloneRequest {
+        self.shallow = Some(value);
+        self
+    }
+
+    /// Sets whether to clone submodules, like
+    /// [`Cloner::set_recurse_submodules`].
+    pub fn recurse_submodules(mut self, value: bool) -> CloneRequest {
+        self.recurse_submodules = Some(value);
+        self
+    }
+
+    /// Sets which files are extracted from a `.crate` file, like
+    /// [`Cloner::set_extract_filter`].
+    ///
+    /// Invalid globs are reported when the request is executed.
+    pub fn extract_filter(mut self, include: &[&str], exclude: &[&str]) -> CloneRequest {
+        let globs = |globs: &[&str]| globs.iter().map(|glob| glob.to_string()).collect();
+        self.extract_filter = Some((globs(include), globs(exclude)));
+        self
+    }
+
+    /// The name of the crate, optionally with a version.
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    /// Returns a copy of `cloner` with the options of this request.
+    fn apply(&self, cloner: &Cloner) -> Result<Cloner, CloneError> {
+        let mut cloner = Clone::clone(cloner);
+        if let Some(dest_name) = &self.dest_name {
+            cloner.set_dest_name(dest_name);
+        }
+        if let Some(checkout) = &self.checkout {
+            cloner.checkout = Some(checkout.clone());
+        }
+        if let Some(shallow) = self.shallow {
+            cloner.set_shallow(shallow);
+        }
+        if let Some(recurse_submodules) = self.recurse_submodules {
+            cloner.set_recurse_submodules(recurse_submodules);
+        }
+        if let Some((include, exclude)) = &self.extract_filter {
+            let include: Vec<&str> = include.iter().map(String::as_str).collect();
+            let exclude: Vec<&str> = exclude.iter().map(String::as_str).collect();
+            cloner.set_extract_filter(&include, &exclude)?;
+        }
+        Ok(cloner)
+    }
+}
+
+impl Cloner {
+    /// Clones a package as described by a [`CloneRequest`].
+    ///
+    /// This is the same as [`Cloner::clone`], with the options of the
+    /// request replacing those of the `Cloner`.
+    pub fn execute(&self, request: &CloneRequest) -> Result<ClonedPackage, CloneError> {
+        let cloner = request.apply(self)?;
+        let extra: Vec<&str> = request.extra.iter().map(String::as_str).collect();
+        cloner.clone(
+            request.method.clone(),
+            &request.spec,
+            request.version.as_deref(),
+            &extra,
+        )
+    }
+}
@@ -0,0 +1,274 @@
+//! Listing packages with the registry's search API, by owner or by query.
+
+use crate::{CloneError, Cloner, CrateResponse};
+use anyhow::{Context, Error};
+use reqwest::{StatusCode, Url};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// How many packages are requested per page.
+const PER_PAGE: usize = 100;
+
+/// How many names are suggested for a package that was not found.
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Debug, Deserialize)]
+struct UserResponse {
+    user: Owner,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamResponse {
+    team: Owner,
+}
+
+#[derive(Debug, Deserialize)]
+struct Owner {
+    id: u64,
+}
+
+/// How many search results are checked for names similar to a package that
+/// was not found.
+const SUGGESTION_SEARCH_LIMIT: usize = 10;
+
+/// A page of `/api/v1/crates`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct CratesPage {
+    crates: Vec<OwnedCrate>,
+    meta: PageMeta,
+}
+
+#[derive(Debug, Deserialize)]
+struct OwnedCrate {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageMeta {
+    /// The query string of the next page, such as `?page=2&user_id=1`.
+    next_page: Option<String>,
+}
+
+impl Cloner {
+    /// Lists the names of the packages owned by a user or team.
+    ///
+    /// `owner` is the login of a user, such as `dtolnay`, or of a team, such
+    /// as `github:rust-lang:libs`. Like [`Cloner::search_crates`], this needs
+    /// the registry's web API.
+    pub fn owned_crates(&self, owner: &str) -> Result<Vec<String>, CloneError> {
+        Ok(self.owned_crates_inner(owner)?)
+    }
+
+    fn owned_crates_inner(&self, owner: &str) -> Result<Vec<String>, Error> {
+        self.check_search_api()?;
+        let (kind, param) = if owner.contains(':') {
+            ("teams", "team_id")
+        } else {
+            ("users", "user_id")
+        };
+        let url = format!("{}/api/v1/{}/{}", self.registry_url(), kind, owner);
+        let response = self
+            .registry_get(&url)
+            .with_context(|| format!("Failed to fetch owner `{}`.", owner))?;
+        match response.status() {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => fail!(NotFound, "Owner `{}` not found on crates.io.", owner),
+            code => fail!(
+                Http,
+                "Failed to get owner `{}` from crates.io: `{}`",
+                owner,
+                code
+            ),
+        }
+        let id = if kind == "teams" {
+            response.json::<TeamResponse>().map(|r| r.team.id)
+        } else {
+            response.json::<UserResponse>().map(|r| r.user.id)
+        }
+        .context("Failed to convert to json.")?;
+
+        let url = format!(
+            "{}/api/v1/crates?{}={}&per_page={}",
+            self.registry_url(),
+            param,
+            id,
+            PER_PAGE
+        );
+        let names = self.list_crates(url, None, &format!("the packages of `{}`", owner))?;
+        debug!("`{}` owns {} packages", owner, names.len());
+        Ok(names)
+    }
+
+    /// Searches the registry for packages, returning the names of up to
+    /// `limit` best matches.
+    ///
+    /// This needs the registry's web API, so it does not work with a sparse
+    /// or local registry.
+    pub fn search_crates(&self, query: &str, limit: usize) -> Result<Vec<String>, CloneError> {
+        Ok(self.search_crates_inner(query, limit)?)
+    }
+
+    pub(crate) fn search_crates_inner(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<String>, Error> {
+        self.check_search_api()?;
+        self.list_crates(
+            self.search_url(query, limit)?,
+            Some(limit),
+            &format!("the search results for `{}`", query),
+        )
+    }
+
+    /// The URL of the first page of search results for `query`.
+    fn search_url(&self, query: &str, limit: usize) -> Result<String, Error> {
+        let per_page = limit.clamp(1, PER_PAGE).to_string();
+        let url = Url::parse_with_params(
+            &format!("{}/api/v1/crates", self.registry_url()),
+            [("q", query), ("per_page", per_page.as_str())],
+        )?;
+        Ok(url.to_string())
+    }
+
+    /// Fails if the registry has no web API to search.
+    fn check_search_api(&self) -> Result<(), Error> {
+        if self.sparse_index_url().is_some() || self.local_registry.is_some() {
+            fail!(
+                InvalidInput,
+                "Searching for packages needs the registry's web API."
+            );
+        }
+        self.resolve_git_index()
+    }
+
+    /// Fetches the pages of `/api/v1/crates`, starting at `url`, returning
+    /// the names of up to `limit` packages.
+    ///
+    /// `what` describes the listing in errors.
+    fn list_crates(
+        &self,
+        mut url: String,
+        limit: Option<usize>,
+        what: &str,
+    ) -> Result<Vec<String>, Error> {
+        let mut names = Vec::new();
+        loop {
+            let response = self
+                .registry_get(&url)
+                .with_context(|| format!("Failed to fetch {}.", what))?;
+            let code = response.status();
+            if !code.is_success() {
+                fail!(Http, "Failed to get {} from crates.io: `{}`", what, code);
+            }
+            let page: CratesPage = response.json().context("Failed to convert to json.")?;
+            if page.crates.is_empty() {
+                break;
+            }
+            names.extend(page.crates.into_iter().map(|c| c.name));
+            if limit.is_some_and(|limit| names.len() >= limit) {
+                break;
+            }
+            match page.meta.next_page {
+                Some(next) => url = format!("{}/api/v1/crates{}", self.registry_url(), next),
+                None => break,
+            }
+        }
+        if let Some(limit) = limit {
+            names.truncate(limit);
+        }
+        Ok(names)
+    }
+
+    /// Looks up `name` with `-` and `_` swapped, returning the package info
+    /// of the first variant that exists.
+    pub(crate) fn find_name_variant(&self, name: &str) -> Option<CrateResponse> {
+        for variant in name_variants(name) {
+            match self.fetch_pkg_info(&variant) {
+                Ok(pkg_info) => return Some(pkg_info),
+                Err(e) => debug!("Variant `{}` not found: {:#}", variant, e),
+            }
+        }
+        None
+    }
+
+    /// Finds the names of existing packages similar to `name`, to suggest
+    /// when it is not found.
+    ///
+    /// `variant` is the name found by [`Cloner::find_name_variant`], which
+    /// comes first, then close matches from the search API, if the registry
+    /// has one. Errors are ignored, as this is only used for the error
+    /// message of a missing package.
+    pub(crate) fn similar_names(&self, name: &str, variant: Option<String>) -> Vec<String> {
+        let mut results = Vec::new();
+        if self.check_search_api().is_ok() {
+            match self.search_crates_inner(name, SUGGESTION_SEARCH_LIMIT) {
+                Ok(names) => results = names,
+                Err(e) => debug!("Failed to search for `{}`: {:#}", name, e),
+            }
+        }
+        closest_names(name, variant, results)
+    }
+
+    /// The URL to search for names similar to `name`, if the registry has a
+    /// search API.
+    #[cfg(feature = "async")]
+    pub(crate) fn suggestion_search_url(&self, name: &str) -> Option<String> {
+        self.check_search_api().ok()?;
+        self.search_url(name, SUGGESTION_SEARCH_LIMIT).ok()
+    }
+}
+
+#[cfg(feature = "async")]
+impl CratesPage {
+    /// The names of the packages on the page.
+    pub(crate) fn names(self) -> Vec<String> {
+        self.crates.into_iter().map(|c| c.name).collect()
+    }
+}
+
+/// Returns `name` with `-` and `_` swapped, each way that changes it.
+pub(crate) fn name_variants(name: &str) -> impl Iterator<Item = String> + '_ {
+    [name.replace('-', "_"), name.replace('_', "-")]
+        .into_iter()
+        .filter(move |variant| variant != name)
+}
+
+/// Picks the names to suggest for a package that was not found: the
+/// `variant` first, then the search `results` close to `name`.
+pub(crate) fn closest_names(
+    name: &str,
+    variant: Option<String>,
+    results: Vec<String>,
+) -> Vec<String> {
+    let mut names: Vec<String> = variant.into_iter().collect();
+    let max = (name.len() / 3).max(1);
+    names.extend(results.into_iter().filter(|result| {
+        result != name && edit_distance(&normalize(result), &normalize(name)) <= max
+    }));
+    let mut seen = HashSet::new();
+    names.retain(|n| seen.insert(n.clone()));
+    names.truncate(MAX_SUGGESTIONS);
+    names
+}
+
+/// Normalizes a package name for comparison, as the registry treats `-` and
+/// `_` and letter case the same when checking for conflicting names.
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace('-', "_")
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur.push((prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
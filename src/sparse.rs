@@ -0,0 +1,261 @@
+//! Package info from a sparse or local registry index.
+//!
+//! A registry URL starting with `sparse+` (such as
+//! `sparse+https://index.crates.io/`) is read with cargo's sparse index
+//! protocol instead of the crates.io web API, and a local registry's index is
+//! read from disk. The index entries are converted into the same types as the
+//! web API's package info.
+
+use crate::error::{msg, Kind};
+use crate::{check_auth_status, Cloner, CrateData, CrateResponse, CrateVersion, Dependency};
+use anyhow::{Context, Error};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+impl Cloner {
+    /// Grab package info from a sparse index.
+    ///
+    /// The index has no repository or homepage information, so these are
+    /// always missing. Each version has an absolute `dl_url` instead of a
+    /// `dl_path`, and the `deps` of the version.
+    pub(crate) fn get_sparse_pkg_info(
+        &self,
+        index_url: &str,
+        name: &str,
+    ) -> Result<CrateResponse, Error> {
+        let index_url = index_url.trim_end_matches('/');
+        let dl = self.sparse_dl(index_url)?;
+        let url = index_file_url(index_url, name);
+        let response = self
+            .registry_get(&url)
+            .context("Failed to fetch package info from the index.")?;
+        check_index_status(name, index_url, &url, response.status())?;
+        let text = response.text().context("Failed to read index entry.")?;
+        sparse_pkg_info(name, &text, &url, dl)
+    }
+
+    /// Returns the download URL template from the `config.json` of a sparse
+    /// index, which is only fetched once.
+    fn sparse_dl(&self, index_url: &str) -> Result<&str, Error> {
+        if let Some(dl) = self.sparse_dl.get() {
+            return Ok(dl);
+        }
+        let config_url = sparse_config_url(index_url);
+        let config = self
+            .fetch_json(&config_url)
+            .context(format!("Failed to fetch registry config `{}`.", config_url))?;
+        self.set_sparse_config(&config_url, config)
+    }
+
+    /// Caches the download URL template from the `config` of a sparse index,
+    /// fetched from `config_url`, and returns it.
+    pub(crate) fn set_sparse_config(
+        &self,
+        config_url: &str,
+        config: Option<Value>,
+    ) -> Result<&str, Error> {
+        let config = config.ok_or_else(|| {
+            msg(
+                Kind::Http,
+                format!("Registry config `{}` not found.", config_url),
+            )
+        })?;
+        let dl = config["dl"].as_str().ok_or_else(|| {
+            msg(
+                Kind::Http,
+                format!("No `dl` in registry config `{}`.", config_url),
+            )
+        })?;
+        Ok(self.sparse_dl.get_or_init(|| dl.to_string()))
+    }
+
+    /// Grab package info from a local registry.
+    ///
+    /// The `.crate` files are next to the index, and each version has a
+    /// `file://` URL to one.
+    pub(crate) fn get_local_pkg_info(
+        &self,
+        dir: &Path,
+        name: &str,
+    ) -> Result<CrateResponse, Error> {
+        let path = dir.join("index").join(index_path(&name.to_lowercase()));
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => fail!(
+                NotFound,
+                "Package `{}` not found in local registry `{}`.",
+                name,
+                dir.display()
+            ),
+            Err(e) => {
+                return Err(Error::new(e).context(format!("Failed to read `{}`.", path.display())))
+            }
+        };
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        index_pkg_info(name, &text, &path.display().to_string(), |name, vers, _| {
+            let file = dir.join(format!("{}-{}.crate", name, vers));
+            format!("file://{}", file.display())
+        })
+    }
+
+    /// Fetches a JSON document, returning `None` if it doesn't exist.
+    fn fetch_json(&self, url: &str) -> Result<Option<Value>, Error> {
+        let response = self.registry_get(url)?;
+        if !json_found(url, response.status())? {
+            return Ok(None);
+        }
+        Ok(Some(response.json().context("Failed to convert to json.")?))
+    }
+}
+
+/// Checks the status of a request for a JSON document, returning whether it
+/// exists.
+pub(crate) fn json_found(url: &str, code: StatusCode) -> Result<bool, Error> {
+    check_auth_status(code)?;
+    match code {
+        StatusCode::OK => Ok(true),
+        StatusCode::NOT_FOUND => Ok(false),
+        code => fail!(Http, "Failed to fetch `{}`: `{}`", url, code),
+    }
+}
+
+/// The URL of the `config.json` of a sparse index.
+pub(crate) fn sparse_config_url(index_url: &str) -> String {
+    format!("{}/config.json", index_url.trim_end_matches('/'))
+}
+
+/// The URL of a package's file in a sparse index.
+pub(crate) fn index_file_url(index_url: &str, name: &str) -> String {
+    format!(
+        "{}/{}",
+        index_url.trim_end_matches('/'),
+        index_path(&name.to_lowercase())
+    )
+}
+
+/// Checks the status of a request for a package's file in a sparse index.
+pub(crate) fn check_index_status(
+    name: &str,
+    index_url: &str,
+    url: &str,
+    code: StatusCode,
+) -> Result<(), Error> {
+    check_auth_status(code)?;
+    match code {
+        StatusCode::OK => Ok(()),
+        StatusCode::NOT_FOUND | StatusCode::GONE | StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS => {
+            fail!(
+                NotFound,
+                "Package `{}` not found in `{}`.",
+                name,
+                index_url.trim_end_matches('/')
+            )
+        }
+        code => fail!(
+            Http,
+            "Failed to get package info from `{}`: `{}`",
+            url,
+            code
+        ),
+    }
+}
+
+/// Converts a package's file from a sparse index, fetched from `url`, into
+/// package info, with download URLs from the `dl` template.
+pub(crate) fn sparse_pkg_info(
+    name: &str,
+    text: &str,
+    url: &str,
+    dl: &str,
+) -> Result<CrateResponse, Error> {
+    index_pkg_info(name, text, url, |name, vers, cksum| {
+        download_url(dl, name, vers, cksum)
+    })
+}
+
+/// A line of an index file, describing one version.
+#[derive(Deserialize)]
+struct IndexEntry {
+    name: String,
+    vers: String,
+    cksum: Option<String>,
+    #[serde(default)]
+    yanked: bool,
+    #[serde(default)]
+    deps: Vec<Dependency>,
+}
+
+/// Converts the lines of an index file into package info.
+///
+/// `dl_url` returns the download URL of a version from its name, version,
+/// and checksum.
+fn index_pkg_info(
+    name: &str,
+    text: &str,
+    source: &str,
+    dl_url: impl Fn(&str, &str, &str) -> String,
+) -> Result<CrateResponse, Error> {
+    let mut versions = Vec::new();
+    let mut crate_name = name.to_string();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let entry: IndexEntry = match serde_json::from_str(line) {
+            Ok(entry) => entry,
+            Err(e) => fail!(Http, "Invalid index entry in `{}`: {}: {}", source, e, line),
+        };
+        versions.push(CrateVersion {
+            dl_url: Some(dl_url(
+                &entry.name,
+                &entry.vers,
+                entry.cksum.as_deref().unwrap_or_default(),
+            )),
+            num: entry.vers,
+            checksum: entry.cksum,
+            yanked: entry.yanked,
+            deps: Some(entry.deps),
+            ..CrateVersion::default()
+        });
+        crate_name = entry.name;
+    }
+    Ok(CrateResponse {
+        krate: CrateData {
+            name: crate_name,
+            ..CrateData::default()
+        },
+        versions,
+    })
+}
+
+/// The path of a package's file in the index, such as `se/rd/serde`.
+fn index_path(name: &str) -> String {
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+/// Builds the download URL from the `dl` template in the registry config.
+fn download_url(dl: &str, name: &str, version: &str, cksum: &str) -> String {
+    const MARKERS: &[&str] = &[
+        "{crate}",
+        "{version}",
+        "{prefix}",
+        "{lowerprefix}",
+        "{sha256-checksum}",
+    ];
+    if !MARKERS.iter().any(|m| dl.contains(m)) {
+        return format!("{}/{}/{}/download", dl.trim_end_matches('/'), name, version);
+    }
+    let prefix = index_path(name);
+    let prefix = prefix.rsplit_once('/').unwrap().0;
+    dl.replace("{crate}", name)
+        .replace("{version}", version)
+        .replace("{prefix}", prefix)
+        .replace("{lowerprefix}", &prefix.to_lowercase())
+        .replace("{sha256-checksum}", cksum)
+}
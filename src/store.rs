@@ -0,0 +1,148 @@
+//! A shared store of bare git repositories, checked out with worktrees.
+
+use crate::error::{msg, Kind};
+use crate::Cloner;
+use anyhow::{Context, Error};
+use regex::Regex;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+impl Cloner {
+    /// Sets a directory where bare git repositories are kept.
+    ///
+    /// When set, a git clone fetches into a bare repository in the store
+    /// (cloning it the first time) and then uses `git worktree add` to
+    /// check out into the output directory. This allows multiple checkouts
+    /// of the same repository to share one copy of the history.
+    ///
+    /// The extra arguments are passed to `git worktree add`, for example a
+    /// directory name and a ref to check out. If there are no extra
+    /// arguments, the default branch is checked out into a directory named
+    /// after the repository.
+    pub fn set_git_store(&mut self, value: impl Into<PathBuf>) -> &mut Self {
+        self.git_store = Some(value.into());
+        self
+    }
+
+    /// Clones a git repository by adding a worktree of a bare repository in
+    /// the store.
+    pub(crate) fn clone_worktree(
+        &self,
+        store: &Path,
+        repo: &str,
+        extra: &[&str],
+        commit: Option<&str>,
+    ) -> Result<(), Error> {
+        let bare = store.join(store_path(repo)?);
+        if self.dry_run {
+            let action = if bare.exists() { "fetch" } else { "clone" };
+            info!("Would {} `{}` into `{}`", action, repo, bare.display());
+        } else if bare.exists() {
+            info!("Fetching `{}` into `{}`", repo, bare.display());
+            run_git(
+                Command::new("git")
+                    .arg("--git-dir")
+                    .arg(&bare)
+                    .args(["fetch", "--prune", repo])
+                    .args(["+refs/heads/*:refs/heads/*", "+refs/tags/*:refs/tags/*"]),
+            )?;
+        } else {
+            info!("Cloning `{}` into `{}`", repo, bare.display());
+            run_git(
+                Command::new("git")
+                    .args(["clone", "--bare", repo])
+                    .arg(&bare),
+            )?;
+        }
+
+        let default_dir = worktree_name(repo);
+        let mut extra = extra.to_vec();
+        if extra.is_empty() {
+            extra.push(&default_dir);
+        }
+        if let Some(commit) = commit {
+            extra.push(commit);
+        }
+        info!(
+            "{}: git worktree add --detach {}",
+            self.running(),
+            extra.join(" ")
+        );
+        if self.dry_run {
+            return Ok(());
+        }
+        let out_dir = self.out_dir()?;
+        if self.force {
+            // Forget worktrees whose directory was removed, so that the
+            // directory can be added again.
+            run_git(
+                Command::new("git")
+                    .arg("--git-dir")
+                    .arg(&bare)
+                    .args(["worktree", "prune"]),
+            )?;
+        }
+        run_git(
+            Command::new("git")
+                .arg("--git-dir")
+                .arg(&bare)
+                .args(["worktree", "add", "--detach"])
+                .args(&extra)
+                .current_dir(&out_dir),
+        )?;
+        if self.recurse_submodules {
+            if let Some(dir) = extra.iter().find(|arg| !arg.starts_with('-')) {
+                update_submodules(&out_dir.join(dir))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks out the submodules of a working tree, recursively.
+pub(crate) fn update_submodules(dir: &Path) -> Result<(), Error> {
+    info!("Updating submodules in `{}`", dir.display());
+    run_git(Command::new("git").arg("-C").arg(dir).args([
+        "submodule",
+        "update",
+        "--init",
+        "--recursive",
+    ]))
+}
+
+pub(crate) fn run_git(cmd: &mut Command) -> Result<(), Error> {
+    let status = cmd
+        .status()
+        .context(msg(Kind::Vcs, "Failed to run `git`."))?;
+    if !status.success() {
+        fail!(Vcs, "`git` did not finish successfully.");
+    }
+    Ok(())
+}
+
+/// Determines the path of the bare repository within the store, such as
+/// `github.com/owner/name.git`.
+fn store_path(repo: &str) -> Result<PathBuf, Error> {
+    let re = Regex::new(r"^(?:[a-zA-Z][a-zA-Z0-9+.-]*://)?(?:[^@/]+@)?(.*?)(?:\.git)?/*$").unwrap();
+    let rest = re.captures(repo).unwrap().get(1).unwrap().as_str();
+    let rest = rest.replace(':', "/");
+    let path: PathBuf = format!("{}.git", rest.trim_start_matches('/')).into();
+    if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        fail!(
+            Repository,
+            "Cannot determine a store path for repository `{}`.",
+            repo
+        );
+    }
+    Ok(path)
+}
+
+/// The directory name `git clone` would use for a repository.
+pub(crate) fn worktree_name(repo: &str) -> String {
+    let name = repo
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap();
+    name.strip_suffix(".git").unwrap_or(name).to_string()
+}
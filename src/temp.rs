@@ -0,0 +1,75 @@
+//! Cloning into a temporary directory.
+
+use crate::{CloneError, CloneRequest, ClonedPackage, Cloner};
+use anyhow::Context;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A package cloned into a temporary directory, from [`Cloner::clone_temp`].
+///
+/// The directory is removed when this is dropped, unless it is kept with
+/// [`TempClone::keep`].
+#[derive(Debug)]
+pub struct TempClone {
+    package: ClonedPackage,
+    dir: TempDir,
+}
+
+impl TempClone {
+    /// The cloned package.
+    pub fn package(&self) -> &ClonedPackage {
+        &self.package
+    }
+
+    /// The directory of the package, within the temporary directory.
+    pub fn path(&self) -> &Path {
+        &self.package.path
+    }
+
+    /// The temporary directory.
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Keeps the temporary directory instead of removing it, returning the
+    /// cloned package.
+    pub fn keep(self) -> ClonedPackage {
+        let _ = self.dir.keep();
+        self.package
+    }
+}
+
+impl Cloner {
+    /// Sets whether [`Cloner::clone_temp`] keeps the temporary directory when
+    /// the clone fails.
+    ///
+    /// Defaults to `false`, removing it.
+    pub fn set_keep_temp(&mut self, value: bool) -> &mut Self {
+        self.keep_temp = value;
+        self
+    }
+
+    /// Clones a package as described by a [`CloneRequest`] into a new
+    /// temporary directory, instead of the output directory.
+    ///
+    /// The directory is created in the system's temporary directory, and
+    /// is removed when the returned [`TempClone`] is dropped.
+    pub fn clone_temp(&self, request: &CloneRequest) -> Result<TempClone, CloneError> {
+        let dir = tempfile::Builder::new()
+            .prefix("cargo-clone-")
+            .tempdir()
+            .context("Failed to create a temporary directory.")?;
+        let mut cloner = Clone::clone(self);
+        cloner.set_out_dir(dir.path());
+        match cloner.execute(request) {
+            Ok(package) => Ok(TempClone { package, dir }),
+            Err(e) => {
+                if self.keep_temp {
+                    let path = dir.keep();
+                    warn!("Keeping the temporary directory `{}`", path.display());
+                }
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! TLS settings for HTTP requests, with the `native-tls` or `rustls`
+//! feature.
+
+use crate::error::{msg, Kind};
+use crate::{CloneError, Cloner};
+use anyhow::{Context, Error};
+use reqwest::{Certificate, Identity};
+use std::fs;
+use std::path::Path;
+
+impl Cloner {
+    /// Adds a PEM-encoded certificate authority to trust, in addition to the
+    /// system's, such as the private CA of an internal registry.
+    ///
+    /// This only applies to the requests made to the registry and other
+    /// APIs. VCS commands use their own TLS configuration.
+    pub fn add_root_certificate(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<&mut Self, CloneError> {
+        let cert = read_certificate(path.as_ref())?;
+        self.root_certificates.push(cert);
+        Ok(self)
+    }
+
+    /// Sets the client certificate to authenticate with, from a PEM-encoded
+    /// certificate and a PEM-encoded PKCS #8 private key.
+    pub fn set_client_certificate(
+        &mut self,
+        cert: impl AsRef<Path>,
+        key: impl AsRef<Path>,
+    ) -> Result<&mut Self, CloneError> {
+        let (cert, key) = (cert.as_ref(), key.as_ref());
+        let identity = identity(&read(cert)?, &read(key)?).context(msg(
+            Kind::InvalidInput,
+            format!(
+                "Invalid client certificate `{}` or key `{}`.",
+                cert.display(),
+                key.display()
+            ),
+        ))?;
+        self.client_identity = Some(identity);
+        Ok(self)
+    }
+
+    /// Sets whether servers with invalid certificates are accepted, such as
+    /// expired or self-signed ones.
+    ///
+    /// This is dangerous, since anyone can then intercept the requests,
+    /// including any registry token. Prefer [`Cloner::add_root_certificate`].
+    pub fn set_danger_accept_invalid_certs(&mut self, value: bool) -> &mut Self {
+        self.accept_invalid_certs = value;
+        self
+    }
+}
+
+fn read_certificate(path: &Path) -> Result<Certificate, Error> {
+    let pem = read(path)?;
+    // rustls doesn't check the contents until a client is built.
+    if !String::from_utf8_lossy(&pem).contains("-----BEGIN CERTIFICATE-----") {
+        fail!(
+            InvalidInput,
+            "No PEM-encoded certificate found in `{}`.",
+            path.display()
+        );
+    }
+    Certificate::from_pem(&pem).context(msg(
+        Kind::InvalidInput,
+        format!("Invalid certificate `{}`.", path.display()),
+    ))
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, Error> {
+    fs::read(path).with_context(|| format!("Failed to read `{}`.", path.display()))
+}
+
+#[cfg(feature = "native-tls")]
+fn identity(cert: &[u8], key: &[u8]) -> reqwest::Result<Identity> {
+    Identity::from_pkcs8_pem(cert, key)
+}
+
+#[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+fn identity(cert: &[u8], key: &[u8]) -> reqwest::Result<Identity> {
+    Identity::from_pem(&[cert, key].concat())
+}
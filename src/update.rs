@@ -0,0 +1,272 @@
+//! Updating a package that was cloned before.
+
+use crate::error::{msg, Kind};
+use crate::provenance::read_metadata_file;
+use crate::{CloneError, CloneMethodKind, ClonedPackage, Cloner, METADATA_FILE};
+use anyhow::{Context, Error};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// The directories that mark a working copy of each VCS method.
+const VCS_DIRS: &[(&str, &str)] = &[
+    (".git", "git"),
+    (".hg", "hg"),
+    (".pijul", "pijul"),
+    (".fslckout", "fossil"),
+    ("_FOSSIL_", "fossil"),
+    (".svn", "svn"),
+    ("_darcs", "darcs"),
+    (".bzr", "bzr"),
+];
+
+/// How the package in a directory was cloned.
+enum Origin {
+    /// A working copy of a VCS method.
+    Vcs {
+        method: CloneMethodKind,
+        name: Option<String>,
+        source: String,
+    },
+    /// An extracted `.crate` file.
+    Crate { name: String, version: String },
+}
+
+impl Cloner {
+    /// Updates a package that was cloned into `dir` before.
+    ///
+    /// How it was cloned is read from its `.cargo-clone.json`, or otherwise
+    /// detected from the directory. A VCS working copy is pulled. An
+    /// extracted `.crate` file is replaced with the newest version matching
+    /// `version`, or the newest version. A directory with the default
+    /// `<name>-<version>` name is replaced by one for the new version, any
+    /// other directory is replaced in place. A yanked version is kept while
+    /// it matches `version`.
+    ///
+    /// Returns `None` if an extracted package is already the newest version.
+    pub fn update(
+        &self,
+        dir: impl AsRef<Path>,
+        version: Option<&str>,
+    ) -> Result<Option<ClonedPackage>, CloneError> {
+        Ok(self.update_inner(dir.as_ref(), version)?)
+    }
+
+    fn update_inner(
+        &self,
+        dir: &Path,
+        version: Option<&str>,
+    ) -> Result<Option<ClonedPackage>, Error> {
+        if !dir.is_dir() {
+            fail!(InvalidInput, "`{}` is not a directory.", dir.display());
+        }
+        let dir = dir
+            .canonicalize()
+            .with_context(|| format!("Failed to read `{}`.", dir.display()))?;
+        match detect_origin(&dir)? {
+            Origin::Crate {
+                name,
+                version: current,
+            } => self.update_crate(&dir, &name, &current, version),
+            Origin::Vcs {
+                method,
+                name,
+                source,
+            } => {
+                if let Some(version) = version {
+                    fail!(
+                        InvalidInput,
+                        "Updating to a version `{}` only works with an extracted `.crate` file.",
+                        version
+                    );
+                }
+                self.pull(&dir, &method)?;
+                let name = name.unwrap_or_else(|| {
+                    let file_name = dir.file_name().unwrap_or_default();
+                    file_name.to_string_lossy().into_owned()
+                });
+                let package = ClonedPackage {
+                    name,
+                    version: None,
+                    method,
+                    source,
+                    path: dir,
+                    checksum: None,
+                    summary: None,
+                    archive: None,
+                };
+                Ok(Some(self.finish_clone(package, None)?))
+            }
+        }
+    }
+
+    /// Replaces an extracted `.crate` file with the newest matching version.
+    fn update_crate(
+        &self,
+        dir: &Path,
+        name: &str,
+        current: &str,
+        version_req: Option<&str>,
+    ) -> Result<Option<ClonedPackage>, Error> {
+        let pkg_info = self.get_pkg_info(name)?;
+        let download = self.select_download(&pkg_info, version_req)?;
+        // Like cargo with a locked version, a yanked version is kept while it
+        // matches, instead of going back to an older version.
+        let keep_yanked = pkg_info
+            .versions
+            .iter()
+            .any(|v| v.num == current && v.yanked)
+            && match (
+                semver::Version::parse(current),
+                semver::Version::parse(&download.version),
+            ) {
+                (Ok(current), Ok(selected)) => {
+                    current > selected
+                        && version_req
+                            .map(semver::VersionReq::parse)
+                            .transpose()?
+                            .is_none_or(|req| req.matches(&current))
+                }
+                _ => false,
+            };
+        if download.version == current || keep_yanked {
+            info!("`{} {}` is up to date", name, current);
+            return Ok(None);
+        }
+        info!(
+            "Updating `{}` from {} to {}",
+            name, current, download.version
+        );
+        let file_name = dir.file_name().unwrap_or_default().to_string_lossy();
+        let default_name = file_name.eq_ignore_ascii_case(&format!("{}-{}", name, current));
+        let mut cloner = Clone::clone(self);
+        // UNWRAP: A canonical path to a package has a parent.
+        cloner.set_out_dir(dir.parent().unwrap());
+        cloner.strip_prefix = false;
+        cloner.vendor = dir.join(".cargo-checksum.json").exists();
+        if !default_name {
+            cloner.dest_name = Some(file_name.into_owned());
+            cloner.force = true;
+        }
+        let version = format!("={}", download.version);
+        let package = cloner.clone_inner(CloneMethodKind::Crate, name, Some(&version), &[])?;
+        if default_name {
+            if self.dry_run {
+                info!("Would remove `{}`", dir.display());
+            } else {
+                info!("Removing `{}`", dir.display());
+                fs::remove_dir_all(dir)
+                    .with_context(|| format!("Failed to remove `{}`.", dir.display()))?;
+            }
+        }
+        Ok(Some(package))
+    }
+
+    /// Pulls the changes of a VCS working copy.
+    fn pull(&self, dir: &Path, method: &CloneMethodKind) -> Result<(), Error> {
+        let args: &[&str] = match method {
+            CloneMethodKind::Git => &["pull", "--ff-only"],
+            CloneMethodKind::Mercurial => &["pull", "--update"],
+            CloneMethodKind::Pijul | CloneMethodKind::Darcs => &["pull", "-a"],
+            CloneMethodKind::Fossil | CloneMethodKind::Subversion => &["update"],
+            CloneMethodKind::Bazaar => &["pull"],
+            _ => fail!(
+                InvalidInput,
+                "Cannot update a package cloned with the `{}` method.",
+                method.command()
+            ),
+        };
+        let command = method.command();
+        self.require_program(command, method)?;
+        info!(
+            "{}: {} {} in `{}`",
+            self.running(),
+            command,
+            args.join(" "),
+            dir.display()
+        );
+        if self.dry_run {
+            return Ok(());
+        }
+        self.check_cancelled()?;
+        self.emit_running(command, args);
+        let status = Command::new(command)
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .context(msg(Kind::Vcs, format!("Failed to run `{}`.", command)))?;
+        if !status.success() {
+            fail!(
+                Vcs,
+                "`{} {}` did not finish successfully.",
+                command,
+                args[0]
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Determines how the package in `dir` was cloned.
+fn detect_origin(dir: &Path) -> Result<Origin, Error> {
+    if let Some(provenance) = read_metadata_file(dir)? {
+        let method = CloneMethodKind::from(&provenance.method);
+        return match (method, provenance.version) {
+            (Some(CloneMethodKind::Crate), Some(version)) => Ok(Origin::Crate {
+                name: provenance.name,
+                version,
+            }),
+            (Some(CloneMethodKind::Crate | CloneMethodKind::GithubArchive), _) | (None, _) => {
+                fail!(
+                    InvalidInput,
+                    "Cannot update `{}`, it was cloned with the `{}` method.",
+                    dir.display(),
+                    provenance.method
+                )
+            }
+            (Some(method), _) => Ok(Origin::Vcs {
+                method,
+                name: Some(provenance.name),
+                source: provenance.source,
+            }),
+        };
+    }
+    let manifest = read_manifest(dir);
+    if let Some((_, method)) = VCS_DIRS.iter().find(|(d, _)| dir.join(d).exists()) {
+        return Ok(Origin::Vcs {
+            // UNWRAP: The names are all known methods.
+            method: CloneMethodKind::from(method).unwrap(),
+            name: manifest.map(|(name, _)| name),
+            source: String::new(),
+        });
+    }
+    // Cargo adds these files when it packages a `.crate` file.
+    let packaged = ["Cargo.toml.orig", ".cargo_vcs_info.json"]
+        .iter()
+        .any(|file| dir.join(file).exists());
+    match manifest {
+        Some((name, Some(version))) if packaged => Ok(Origin::Crate { name, version }),
+        _ => fail!(
+            InvalidInput,
+            "Cannot tell how `{}` was cloned, it has no `{}`, VCS directory, \
+             or extracted `.crate` file.",
+            dir.display(),
+            METADATA_FILE
+        ),
+    }
+}
+
+/// Reads the package name and version from the `Cargo.toml` in `dir`.
+fn read_manifest(dir: &Path) -> Option<(String, Option<String>)> {
+    let manifest: toml::Table = fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()?
+        .parse()
+        .ok()?;
+    let package = manifest.get("package")?;
+    let name = package.get("name")?.as_str()?.to_string();
+    let version = package
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    Some((name, version))
+}
@@ -0,0 +1,206 @@
+//! Checking out the commit a package version was published from.
+//!
+//! `cargo package` records the git commit in a `.cargo_vcs_info.json` file
+//! inside the `.crate` file. If it is missing, a tag named after the version
+//! is used instead.
+
+use crate::error::{msg, Kind};
+use crate::store::{run_git, update_submodules, worktree_name};
+use crate::{Cloner, CrateResponse};
+use anyhow::{Context, Error};
+use flate2::read::GzDecoder;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::Path;
+use std::process::Command;
+use tar::Archive;
+
+impl Cloner {
+    /// Finds the commit a version of a package was published from, or the
+    /// commit of a tag matching the version, returned along with the
+    /// selected version.
+    ///
+    /// The commit is `None`, with a warning, if neither is found.
+    pub(crate) fn version_commit(
+        &self,
+        name: &str,
+        repo: &str,
+        version_req: &str,
+        pkg_info: &CrateResponse,
+    ) -> Result<(String, Option<String>), Error> {
+        let (version, commit) = self.published_commit(name, version_req, pkg_info)?;
+        if commit.is_some() {
+            return Ok((version, commit));
+        }
+        let commit = version_tag_commit(repo, name, &version)?;
+        if commit.is_none() {
+            warn!(
+                "No tag matching version {} of `{}` found, using the default branch",
+                version, name
+            );
+        }
+        Ok((version, commit))
+    }
+
+    /// Finds the commit a version of a package was published from, returned
+    /// along with the selected version.
+    pub(crate) fn published_commit(
+        &self,
+        name: &str,
+        version_req: &str,
+        pkg_info: &CrateResponse,
+    ) -> Result<(String, Option<String>), Error> {
+        let download = self.select_download(pkg_info, Some(version_req))?;
+        info!(
+            "Reading the published commit of `{} {}`",
+            name, download.version
+        );
+        let base = format!("{}-{}", name.to_lowercase(), download.version);
+        let part = self.download_crate(name, &base, &download.url, download.checksum.as_deref())?;
+        let vcs_info = read_vcs_info(&part, &base);
+        if self.check_cancelled().is_ok() {
+            let _ = fs::remove_file(&part);
+        }
+        let sha1 = vcs_info?.and_then(|vcs_info| {
+            let sha1 = vcs_info["git"]["sha1"].as_str()?.to_string();
+            Some((vcs_info, sha1))
+        });
+        let Some((vcs_info, sha1)) = sha1 else {
+            info!(
+                "`{} {}` does not record the commit it was published from",
+                name, download.version
+            );
+            return Ok((download.version, None));
+        };
+        if vcs_info["git"]["dirty"].as_bool() == Some(true) {
+            warn!(
+                "`{} {}` was published with uncommitted changes, \
+                 the commit may not match the published source",
+                name, download.version
+            );
+        }
+        if let Some(path) = vcs_info["path_in_vcs"].as_str().filter(|p| !p.is_empty()) {
+            info!("The package is in `{}` of the repository", path);
+        }
+        Ok((download.version, Some(sha1)))
+    }
+
+    /// Clones a git repository, and checks out a commit if given.
+    pub(crate) fn clone_git(
+        &self,
+        repo: &str,
+        options: &[String],
+        extra: &[&str],
+        commit: Option<&str>,
+    ) -> Result<(), Error> {
+        #[cfg(feature = "gix")]
+        if self.clone_gix(repo, extra, commit)? {
+            return Ok(());
+        }
+        let Some(commit) = commit else {
+            return self.run_clone("git", options, repo, extra);
+        };
+        let default_dir = worktree_name(repo);
+        let mut extra = extra.to_vec();
+        if !extra.iter().any(|arg| !arg.starts_with('-')) {
+            extra.push(&default_dir);
+        }
+        self.run_clone("git", options, repo, &extra)?;
+        if self.dry_run {
+            info!("Would run: git checkout --detach {}", commit);
+            return Ok(());
+        }
+        let dir = self.out_dir()?.join(
+            extra
+                .iter()
+                .rev()
+                .find(|arg| !arg.starts_with('-'))
+                .unwrap(),
+        );
+        checkout_commit(&dir, commit, self.shallow)?;
+        if self.recurse_submodules {
+            update_submodules(&dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds the commit of a tag named `v1.2.3`, `1.2.3`, or `name-1.2.3`.
+fn version_tag_commit(repo: &str, name: &str, version: &str) -> Result<Option<String>, Error> {
+    info!("Looking for a tag matching version {}", version);
+    let output = Command::new("git")
+        .args(["ls-remote", "--tags", repo])
+        .output()
+        .context(msg(Kind::Vcs, "Failed to run `git`."))?;
+    if !output.status.success() {
+        fail!(Vcs, "`git ls-remote` did not finish successfully.");
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let tags: HashMap<&str, &str> = stdout
+        .lines()
+        .filter_map(|line| {
+            let (sha, tag) = line.split_once('\t')?;
+            Some((tag.strip_prefix("refs/tags/")?, sha))
+        })
+        .collect();
+    for tag in [
+        format!("v{}", version),
+        version.to_string(),
+        format!("{}-{}", name, version),
+    ] {
+        // Annotated tags are listed again, peeled to the commit.
+        if let Some(sha) = tags
+            .get(format!("{}^{{}}", tag).as_str())
+            .or_else(|| tags.get(tag.as_str()))
+        {
+            info!("Using tag `{}`", tag);
+            return Ok(Some(sha.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Checks out a commit in a clone, fetching it first if it isn't there, as
+/// in a shallow clone.
+fn checkout_commit(dir: &Path, commit: &str, shallow: bool) -> Result<(), Error> {
+    let git = || {
+        let mut cmd = Command::new("git");
+        cmd.arg("-C").arg(dir);
+        cmd
+    };
+    let present = git()
+        .args(["cat-file", "-e", &format!("{}^{{commit}}", commit)])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    let target = if present {
+        commit
+    } else {
+        info!("Fetching commit {}", commit);
+        let mut fetch = git();
+        fetch.args(["fetch", "--quiet"]);
+        if shallow {
+            fetch.arg("--depth=1");
+        }
+        run_git(fetch.args(["origin", commit]))?;
+        "FETCH_HEAD"
+    };
+    info!("Running: git checkout --detach {}", commit);
+    run_git(git().args(["checkout", "--quiet", "--detach", target]))
+}
+
+/// Reads `.cargo_vcs_info.json` from a `.crate` file.
+fn read_vcs_info(path: &Path, base: &str) -> Result<Option<Value>, Error> {
+    let file = File::open(path).with_context(|| format!("Failed to open `{}`.", path.display()))?;
+    let mut tar = Archive::new(GzDecoder::new(file));
+    let vcs_info_path = Path::new(base).join(".cargo_vcs_info.json");
+    for entry in tar.entries()? {
+        let entry = entry.context("Failed to get tar entry.")?;
+        if entry.path()? == vcs_info_path {
+            let vcs_info = serde_json::from_reader(entry)
+                .context("Failed to parse `.cargo_vcs_info.json`.")?;
+            return Ok(Some(vcs_info));
+        }
+    }
+    Ok(None)
+}
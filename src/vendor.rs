@@ -0,0 +1,79 @@
+//! Extracting packages in the layout of `cargo vendor`.
+
+use crate::cache::file_checksum;
+use crate::Cloner;
+use anyhow::{Context, Error};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+impl Cloner {
+    /// Sets whether packages are extracted in the layout of `cargo vendor`.
+    ///
+    /// Each package gets a `.cargo-checksum.json` with the checksums of its
+    /// files and of the `.crate` file, so the output directory can be used as
+    /// a `directory` source with source replacement. Packages are always
+    /// downloaded with the `crate` method, and cannot be filtered or have
+    /// their prefix stripped.
+    pub fn set_vendor(&mut self, value: bool) -> &mut Self {
+        self.vendor = value;
+        self
+    }
+
+    /// Checks that the settings work with vendoring, if it is enabled.
+    pub(crate) fn check_vendor(&self) -> Result<(), Error> {
+        if self.vendor && (self.strip_prefix || self.extract_filter.is_some()) {
+            fail!(
+                InvalidInput,
+                "Cannot vendor packages with an extract filter or strip prefix."
+            );
+        }
+        if self.vendor && self.saves_archive() {
+            fail!(
+                InvalidInput,
+                "Cannot vendor packages and keep their `.crate` files."
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Writes the `.cargo-checksum.json` of a package extracted into `dir`, with
+/// the checksum of its `.crate` file.
+pub(crate) fn write_checksums(dir: &Path, checksum: Option<&str>) -> Result<(), Error> {
+    let mut files = BTreeMap::new();
+    add_checksums(dir, dir, &mut files)?;
+    let value = json!({
+        "files": files,
+        "package": checksum,
+    });
+    let path = dir.join(".cargo-checksum.json");
+    fs::write(&path, value.to_string())
+        .with_context(|| format!("Failed to write `{}`.", path.display()))?;
+    Ok(())
+}
+
+/// Adds the checksum of every file under `dir` to `files`, keyed by its path
+/// relative to `root` with `/` separators.
+fn add_checksums(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<String, String>,
+) -> Result<(), Error> {
+    for entry in
+        fs::read_dir(dir).with_context(|| format!("Failed to read `{}`.", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            add_checksums(root, &path, files)?;
+            continue;
+        }
+        let rel = path.strip_prefix(root).unwrap();
+        let rel: Vec<_> = rel.iter().map(|c| c.to_string_lossy()).collect();
+        let checksum = file_checksum(&path)
+            .with_context(|| format!("Failed to read `{}`.", path.display()))?;
+        files.insert(rel.join("/"), checksum);
+    }
+    Ok(())
+}
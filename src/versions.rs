@@ -0,0 +1,235 @@
+//! Listing the published versions of a package.
+
+use crate::{
+    check_semver_req, found_name, parse_spec, select_version, CloneError, CloneRequest, Cloner,
+    CrateResponse,
+};
+use anyhow::{Context, Error};
+
+/// A published version of a package, from [`Cloner::list_versions`].
+#[derive(Debug, Clone)]
+pub struct VersionInfo {
+    /// The version number.
+    pub version: semver::Version,
+    /// Whether the version was yanked.
+    pub yanked: bool,
+    /// When the version was published, as an RFC 3339 timestamp such as
+    /// `2017-05-05T17:47:33.165838+00:00`.
+    ///
+    /// This is `None` for a sparse index, which has no publish dates.
+    pub created_at: Option<String>,
+}
+
+/// Which versions of a package [`Cloner::version_requests`] selects.
+#[derive(Debug, Clone)]
+pub enum VersionSelection {
+    /// The newest version matching each version requirement.
+    Requirements(Vec<String>),
+    /// Every published version that is not yanked, or every version with
+    /// [`Cloner::set_include_yanked`].
+    All,
+    /// The given number of newest versions that are not yanked, or of any
+    /// versions with [`Cloner::set_include_yanked`].
+    Last(usize),
+}
+
+/// The version of a package that would be downloaded, from
+/// [`Cloner::resolve_version`].
+#[derive(Debug, Clone)]
+pub struct ResolvedVersion {
+    /// The name of the package, with the capitalization it was published
+    /// with.
+    pub name: String,
+    /// The selected version.
+    pub version: semver::Version,
+    /// The URL of the `.crate` file.
+    pub download_url: String,
+    /// The SHA-256 checksum of the `.crate` file, if the registry reported
+    /// one.
+    pub checksum: Option<String>,
+}
+
+impl Cloner {
+    /// Selects the version of a package that [`Cloner::clone`] would download,
+    /// without downloading it.
+    ///
+    /// This follows the same rules, such as skipping pre-releases unless
+    /// [`Cloner::set_include_prereleases`] is set, and using the version
+    /// pinned by [`Cloner::set_lockfile`].
+    ///
+    /// - `spec` - The name of the crate, optionally with a version
+    ///   requirement such as `serde@1`
+    /// - `version` - A version requirement
+    ///
+    pub fn resolve_version(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+    ) -> Result<ResolvedVersion, CloneError> {
+        Ok(self.resolve_version_inner(spec, version)?)
+    }
+
+    fn resolve_version_inner(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+    ) -> Result<ResolvedVersion, Error> {
+        let (name, version_req) = self.parse_request(spec, version)?;
+        let pkg_info = self.get_pkg_info(name)?;
+        let version_req =
+            self.crate_version_req(found_name(name, &pkg_info), version_req.as_deref());
+        let download = self.select_download(&pkg_info, version_req.as_deref())?;
+        let version = semver::Version::parse(&download.version)
+            .with_context(|| format!("Invalid version `{}`.", download.version))?;
+        Ok(ResolvedVersion {
+            name: pkg_info.krate.name,
+            version,
+            download_url: download.url,
+            checksum: download.checksum,
+        })
+    }
+
+    /// Returns the published versions of a package, newest first.
+    ///
+    /// Yanked versions are included, see [`VersionInfo::yanked`].
+    ///
+    /// - `spec` - The name of the crate, optionally with a version
+    ///   requirement such as `serde@1` to only list the matching versions
+    /// - `version` - A version requirement to only list the matching versions
+    ///
+    pub fn list_versions(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<VersionInfo>, CloneError> {
+        Ok(self.list_versions_inner(spec, version)?)
+    }
+
+    fn list_versions_inner(
+        &self,
+        spec: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<VersionInfo>, Error> {
+        let (name, version_req) = parse_spec(spec, version)?;
+        let req = version_req
+            .as_deref()
+            .map(semver::VersionReq::parse)
+            .transpose()?;
+        let pkg_info = self.get_pkg_info(name)?;
+        let mut infos = Vec::new();
+        for crate_version in pkg_info.versions {
+            let version = match semver::Version::parse(&crate_version.num) {
+                Ok(version) => version,
+                Err(e) => {
+                    debug!("Skipping invalid version `{}`: {}", crate_version.num, e);
+                    continue;
+                }
+            };
+            if req.as_ref().is_some_and(|req| !req.matches(&version)) {
+                continue;
+            }
+            infos.push(VersionInfo {
+                version,
+                yanked: crate_version.yanked,
+                created_at: crate_version.created_at,
+            });
+        }
+        infos.sort_unstable_by(|a, b| b.version.cmp(&a.version));
+        Ok(infos)
+    }
+
+    /// Creates a request for each of several versions of a package, to
+    /// download them side by side into `<name>-<version>` directories.
+    ///
+    /// Pre-releases are only selected by [`VersionSelection::All`] and
+    /// [`VersionSelection::Last`] with [`Cloner::set_include_prereleases`].
+    /// The requests are ordered newest first, without duplicates.
+    ///
+    /// - `spec` - The name of the crate, optionally with a version
+    ///   requirement such as `serde@1` to only select the matching versions
+    /// - `selection` - Which versions to select
+    ///
+    pub fn version_requests(
+        &self,
+        spec: &str,
+        selection: &VersionSelection,
+    ) -> Result<Vec<CloneRequest>, CloneError> {
+        Ok(self.version_requests_inner(spec, selection)?)
+    }
+
+    fn version_requests_inner(
+        &self,
+        spec: &str,
+        selection: &VersionSelection,
+    ) -> Result<Vec<CloneRequest>, Error> {
+        let (name, spec_req) = parse_spec(spec, None)?;
+        let pkg_info = self.get_pkg_info(name)?;
+        let name = found_name(name, &pkg_info);
+        let versions = self.select_versions(name, spec_req.as_deref(), selection, &pkg_info)?;
+        Ok(versions
+            .into_iter()
+            .map(|version| {
+                CloneRequest::new(name)
+                    .version(format!("={}", version))
+                    .dest_name(
+                        self.package_dir(name, &version.to_string())
+                            .to_string_lossy(),
+                    )
+            })
+            .collect())
+    }
+
+    /// Selects versions of a package, newest first, without duplicates.
+    pub(crate) fn select_versions(
+        &self,
+        name: &str,
+        spec_req: Option<&str>,
+        selection: &VersionSelection,
+        pkg_info: &CrateResponse,
+    ) -> Result<Vec<semver::Version>, Error> {
+        let mut versions = match selection {
+            VersionSelection::Requirements(reqs) => {
+                if spec_req.is_some() {
+                    fail!(
+                        InvalidInput,
+                        "Cannot specify both a :version and --version."
+                    );
+                }
+                let mut versions = Vec::new();
+                for req in reqs {
+                    let req = check_semver_req(req)?;
+                    let version = select_version(
+                        pkg_info,
+                        Some(&req),
+                        self.include_prereleases,
+                        self.include_yanked,
+                    )?;
+                    versions.push(semver::Version::parse(&version.num)?);
+                }
+                versions.sort_unstable_by(|a, b| b.cmp(a));
+                versions
+            }
+            VersionSelection::All | VersionSelection::Last(_) => {
+                let req = spec_req.map(semver::VersionReq::parse).transpose()?;
+                let mut versions: Vec<_> = pkg_info
+                    .versions
+                    .iter()
+                    .filter(|v| self.include_yanked || !v.yanked)
+                    .filter_map(|v| semver::Version::parse(&v.num).ok())
+                    .filter(|v| self.include_prereleases || v.pre.is_empty())
+                    .filter(|v| req.as_ref().is_none_or(|req| req.matches(v)))
+                    .collect();
+                versions.sort_unstable_by(|a, b| b.cmp(a));
+                if let VersionSelection::Last(n) = selection {
+                    versions.truncate(*n);
+                }
+                versions
+            }
+        };
+        versions.dedup();
+        if versions.is_empty() {
+            fail!(Version, "No versions of `{}` to download.", name);
+        }
+        Ok(versions)
+    }
+}
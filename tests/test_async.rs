@@ -0,0 +1,119 @@
+#![cfg(feature = "async")]
+
+use cargo_clone::{CloneError, CloneMethodKind, Cloner};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clone_async_crate() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner
+        .clone_async(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .await
+        .unwrap();
+    assert!(td.path().join("bitflags-1.0.5/Cargo.toml").exists());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clone_async_unknown_crate() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let err = cloner
+        .clone_async(CloneMethodKind::Auto, "test", None, &[])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not found"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clone_async_cache() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner
+        .set_out_dir(td.path().join("out"))
+        .set_cache_dir(td.path().join("cache"))
+        .set_cargo_cache(false);
+    cloner
+        .clone_async(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .await
+        .unwrap();
+    assert!(td.path().join("out/bitflags-1.0.5/Cargo.toml").exists());
+    assert!(td.path().join("cache/bitflags-1.0.5.crate").exists());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clone_async_not_found_suggestions() {
+    let err = Cloner::new()
+        .clone_async(CloneMethodKind::Crate, "serde-json", None, &[])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CloneError::NotFound(_)));
+    assert!(err.to_string().contains("Did you mean `serde_json`?"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clone_async_sparse_registry() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner
+        .set_out_dir(td.path())
+        .set_registry_url("sparse+https://index.crates.io/");
+    cloner
+        .clone_async(CloneMethodKind::Auto, "bitflags", Some("=1.0.5"), &[])
+        .await
+        .unwrap();
+    assert!(td.path().join("bitflags-1.0.5/Cargo.toml").exists());
+    let err = cloner
+        .clone_async(CloneMethodKind::Crate, "test", None, &[])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CloneError::NotFound(_)));
+}
+
+// Not a `tokio::test`, since the blocking client can't be dropped in the
+// runtime.
+#[test]
+fn clone_async_only_uses_async_client() {
+    let td = tempfile::tempdir().unwrap();
+    // Any request with the blocking client fails.
+    let unreachable = reqwest::blocking::Client::builder()
+        .proxy(reqwest::Proxy::all("http://127.0.0.1:1").unwrap())
+        .build()
+        .unwrap();
+    let mut cloner = Cloner::new();
+    cloner
+        .set_out_dir(td.path())
+        .set_cargo_cache(false)
+        .set_retries(0)
+        .set_http_client(unreachable);
+    tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(cloner.clone_async(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[]))
+        .unwrap();
+    assert!(td.path().join("bitflags-1.0.5/Cargo.toml").exists());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn clone_async_stale_partial_download() {
+    let td = tempfile::tempdir().unwrap();
+    // Resuming this stops the extraction streamed from the download.
+    let part = td.path().join(".bitflags-1.0.4.crate.part");
+    std::fs::write(&part, [0; 100]).unwrap();
+    let mut cloner = Cloner::new();
+    cloner
+        .set_out_dir(td.path())
+        .set_cargo_cache(false)
+        .set_registry_url("sparse+https://index.crates.io/");
+    cloner
+        .clone_async(CloneMethodKind::Crate, "bitflags@=1.0.4", None, &[])
+        .await
+        .unwrap();
+    assert!(td.path().join("bitflags-1.0.4/Cargo.toml").exists());
+    // Neither the partial file nor a temporary directory is left behind.
+    let entries: Vec<_> = std::fs::read_dir(td.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(entries, ["bitflags-1.0.4"]);
+}
@@ -1,5 +1,13 @@
 use anyhow::Error;
-use cargo_clone::{CloneMethodKind, Cloner};
+use cargo_clone::{
+    CloneError, CloneEvent, CloneMethodKind, CloneRequest, Cloner, ExportFormat, Layout,
+    VersionSelection, METADATA_FILE,
+};
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 use tempfile::TempDir;
 
 fn clone(
@@ -71,7 +79,7 @@ fn extra_args_crate() {
 #[test]
 fn version_with_method() {
     assert_err(
-        clone("git", "bitflags", Some("1.2.3"), &[]),
+        clone("hg", "bitflags", Some("1.2.3"), &[]),
         "only works with",
     );
 }
@@ -82,6 +90,46 @@ fn at_style() {
     assert_downloaded(&td, "bitflags-1.0.5");
 }
 
+#[test]
+fn package_url() {
+    let td = clone(
+        "crate",
+        "https://crates.io/crates/bitflags/1.0.5",
+        None,
+        &[],
+    )
+    .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5");
+    let td = clone(
+        "crate",
+        "https://docs.rs/bitflags/1.0.5/bitflags/",
+        None,
+        &[],
+    )
+    .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5");
+    assert_err(
+        clone("crate", "https://github.com/bitflags/bitflags", None, &[]),
+        "is not a link to a package",
+    );
+}
+
+#[test]
+fn purl_spec() {
+    let td = clone(
+        "crate",
+        "pkg:cargo/bitflags@1.0.5?repository_url=x",
+        None,
+        &[],
+    )
+    .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5");
+    assert_err(
+        clone("crate", "pkg:npm/left-pad@1.3.0", None, &[]),
+        "not a package URL of a cargo package",
+    );
+}
+
 #[test]
 fn unknown_crate() {
     assert_err(clone("auto", "test", None, &[]), "not found");
@@ -98,6 +146,7 @@ fn clone_fossil() {
 
     let td = clone("fossil", "rs-graph", None, &["graph.fossil"]).unwrap();
     assert_downloaded(&td, "graph.fossil");
+    assert_downloaded(&td, "graph/Cargo.toml");
 }
 
 #[test]
@@ -105,3 +154,1602 @@ fn clone_git_args() {
     let td = clone("git", "bitflags", None, &["--depth=1", "bf"]).unwrap();
     assert_downloaded(&td, "bf");
 }
+
+#[test]
+fn clone_git_version() {
+    let td = clone("git", "bitflags", Some("1.3.2"), &["--depth=1", "bf"]).unwrap();
+    let head = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(td.path().join("bf"))
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&head.stdout).trim(),
+        "ed185cfb1c447c1b4bd6ac021c9ec3bb02c9e2f2"
+    );
+}
+
+#[test]
+fn audit_tree() {
+    let td = tempfile::tempdir().unwrap();
+    let report = td.path().join("NOTICES.txt");
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.audit_tree("bitflags@=1.0.5", None, &report).unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5");
+    let text = std::fs::read_to_string(&report).unwrap();
+    assert!(text.contains("bitflags 1.0.5"));
+    assert!(text.contains("==> LICENSE-MIT <=="));
+}
+
+#[test]
+fn export_json() {
+    let td = tempfile::tempdir().unwrap();
+    let export = td.path().join("clones.jsonl");
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_export(ExportFormat::Json, &export);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    let text = std::fs::read_to_string(&export).unwrap();
+    let record: serde_json::Value = serde_json::from_str(text.trim()).unwrap();
+    assert_eq!(record["name"], "bitflags");
+    assert_eq!(record["version"], "1.0.5");
+    assert!(record["checksum"].is_string());
+}
+
+#[test]
+fn config_override() {
+    let mut cloner = Cloner::new();
+    cloner
+        .apply_config_override("registry.url=\"https://example.com\"")
+        .unwrap();
+    cloner
+        .apply_config_override("registry.token=\"secret\"")
+        .unwrap();
+    let err = cloner.apply_config_override("bogus=1").unwrap_err();
+    assert!(format!("{:#}", err).contains("Unknown config key `bogus`"));
+    let err = cloner.apply_config_override("out-dir=1").unwrap_err();
+    assert!(format!("{:#}", err).contains("expected a string"));
+    cloner
+        .apply_config_override("hosts.gitea=[\"gitea.example.com\"]")
+        .unwrap();
+    cloner
+        .apply_config_override("hosts.docs-rs=\"https://docs.example.com\"")
+        .unwrap();
+    let err = cloner
+        .apply_config_override("hosts.gitea=\"gitea.example.com\"")
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("expected an array of strings"));
+    let err = cloner.apply_config_override("shallow=\"yes\"").unwrap_err();
+    assert!(format!("{:#}", err).contains("expected a boolean"));
+    let err = cloner.apply_config_override("method=\"cvs\"").unwrap_err();
+    assert!(format!("{:#}", err).contains("Unknown method `cvs`"));
+}
+
+#[test]
+fn default_method() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.apply_config_override("method=\"crate\"").unwrap();
+    // Without a version, auto detection would clone the repository.
+    cloner
+        .clone(CloneMethodKind::Auto, "bitflags", None, &[])
+        .unwrap();
+    let entry = std::fs::read_dir(td.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    let dir = entry.file_name().into_string().unwrap();
+    assert!(dir.starts_with("bitflags-"), "{}", dir);
+    assert_downloaded(&td, &format!("{}/Cargo.toml", dir));
+}
+
+#[test]
+fn from_env() {
+    let td = tempfile::tempdir().unwrap();
+    // No other test reads these variables.
+    std::env::set_var("CARGO_CLONE_OUT_DIR", td.path());
+    std::env::set_var("CARGO_CLONE_METHOD", "crate");
+    std::env::set_var("CARGO_CLONE_DOCS_RS_URL", "https://docs.example.com");
+    let cloner = Cloner::from_env();
+    std::env::remove_var("CARGO_CLONE_OUT_DIR");
+    std::env::remove_var("CARGO_CLONE_METHOD");
+    std::env::remove_var("CARGO_CLONE_DOCS_RS_URL");
+    cloner
+        .unwrap()
+        .clone(CloneMethodKind::Auto, "bitflags", None, &[])
+        .unwrap();
+    let entry = std::fs::read_dir(td.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert!(entry.file_name().to_str().unwrap().starts_with("bitflags-"));
+}
+
+#[test]
+fn destination_exists() {
+    let td = tempfile::tempdir().unwrap();
+    std::fs::create_dir(td.path().join("bitflags-1.0.5")).unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let err = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+    let err = cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &["bitflags-1.0.5"])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::DestinationExists(_)));
+
+    cloner.set_force(true);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5/Cargo.toml");
+}
+
+#[test]
+fn cancelled_extraction_is_removed() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_cancel_flag(Arc::new(AtomicBool::new(true)));
+    let err = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap_err();
+    assert!(err.to_string().contains("Interrupted"));
+    assert_eq!(std::fs::read_dir(td.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn git_store_worktrees() {
+    let td = tempfile::tempdir().unwrap();
+    let store = td.path().join("store");
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_git_store(&store);
+    cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &["a"])
+        .unwrap();
+    cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &["b", "1.0.0"])
+        .unwrap();
+    assert_downloaded(&td, "a/Cargo.toml");
+    assert_downloaded(&td, "b/Cargo.toml");
+    assert_downloaded(&td, "store/github.com/bitflags/bitflags.git/HEAD");
+}
+
+#[test]
+#[cfg(feature = "gix")]
+fn gix_clone() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_tag("1.0.0");
+    cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &["bf"])
+        .unwrap();
+    assert_downloaded(&td, "bf/Cargo.toml");
+    assert_downloaded(&td, "bf/.git/HEAD");
+}
+
+#[test]
+fn shallow_git() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_shallow(true);
+    cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags/Cargo.toml");
+    assert_downloaded(&td, "bitflags/.git/shallow");
+}
+
+#[test]
+fn stale_partial_download() {
+    let td = tempfile::tempdir().unwrap();
+    // Left behind by an earlier run, but not the start of the `.crate` file.
+    let part = td.path().join(".bitflags-1.0.4.crate.part");
+    std::fs::write(&part, [0; 100]).unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path()).set_cargo_cache(false);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.4", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.4/Cargo.toml");
+    assert!(!part.exists());
+}
+
+#[test]
+fn dry_run() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_dry_run(true);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &[])
+        .unwrap();
+    assert_eq!(std::fs::read_dir(td.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn events() {
+    let td = tempfile::tempdir().unwrap();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let collected = events.clone();
+    cloner.set_event_handler(move |event| {
+        let kind = match event {
+            CloneEvent::Resolving { requirement, .. } => {
+                format!("resolving {}", requirement.as_deref().unwrap_or("*"))
+            }
+            CloneEvent::Method { method, .. } => format!("method {}", method),
+            CloneEvent::Version { version, .. } => format!("version {}", version),
+            CloneEvent::Destination { .. } => "destination".to_string(),
+            CloneEvent::Extracted { .. } => "extracted".to_string(),
+            CloneEvent::Cloned { name } => format!("cloned {}", name),
+            _ => return,
+        };
+        collected.lock().unwrap().push(kind);
+    });
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "resolving =1.0.5",
+            "method crate",
+            "version 1.0.5",
+            "destination",
+            "extracted",
+            "cloned bitflags"
+        ]
+    );
+}
+
+#[test]
+fn resolve_version() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let resolved = cloner.resolve_version("bitflags@<0.3", None).unwrap();
+    assert_eq!(resolved.name, "bitflags");
+    assert_eq!(resolved.version, semver::Version::new(0, 2, 1));
+    assert!(resolved.download_url.contains("0.2.1"));
+    assert!(resolved.checksum.is_some());
+    // Nothing is downloaded.
+    assert_eq!(std::fs::read_dir(td.path()).unwrap().count(), 0);
+    assert!(matches!(
+        cloner
+            .resolve_version("bitflags", Some("=9.9.9"))
+            .unwrap_err(),
+        CloneError::Version(_)
+    ));
+}
+
+#[test]
+fn github_redirect() {
+    let mut cloner = Cloner::new();
+    let repo = "https://github.com/rust-lang-nursery/rand";
+    let (_, url) = cloner.detect_repo(repo).unwrap();
+    assert_eq!(url, "https://github.com/rust-lang-nursery/rand.git");
+    cloner.set_follow_redirects(true);
+    let (_, url) = cloner.detect_repo(repo).unwrap();
+    assert_eq!(url, "https://github.com/rust-random/rand.git");
+}
+
+#[test]
+fn detect_repo() {
+    let mut cloner = Cloner::new();
+    let (method, url) = cloner
+        .detect_repo("https://github.com/rust-lang/cargo/tree/master/crates/cargo-util")
+        .unwrap();
+    assert_eq!(method.command(), "git");
+    assert_eq!(url, "https://github.com/rust-lang/cargo.git");
+    let (method, url) = cloner.detect_repo("https://hg.sr.ht/~user/repo").unwrap();
+    assert_eq!(method.command(), "hg");
+    assert_eq!(url, "https://hg.sr.ht/~user/repo");
+    cloner.set_prefer_ssh(true);
+    let (_, url) = cloner
+        .detect_repo("https://gitlab.com/group/project")
+        .unwrap();
+    assert_eq!(url, "git@gitlab.com:group/project.git");
+    assert!(matches!(
+        cloner.detect_repo("https://example.com/repo").unwrap_err(),
+        CloneError::Repository(_)
+    ));
+}
+
+#[test]
+fn list_versions() {
+    let cloner = Cloner::new();
+    let versions = cloner.list_versions("bitflags@<0.3", None).unwrap();
+    let nums: Vec<String> = versions.iter().map(|v| v.version.to_string()).collect();
+    assert_eq!(nums, ["0.2.1", "0.2.0", "0.1.1", "0.1.0"]);
+    assert!(versions[1].yanked);
+    assert!(!versions[0].yanked);
+    assert!(versions[0].created_at.is_some());
+}
+
+#[test]
+fn version_requests() {
+    let mut cloner = Cloner::new();
+    cloner.set_dry_run(true);
+    let dirs = |spec, selection| -> Vec<String> {
+        cloner
+            .version_requests(spec, &selection)
+            .unwrap()
+            .into_iter()
+            .map(|request| {
+                let request = request.method(CloneMethodKind::Crate);
+                let package = cloner.execute(&request).unwrap();
+                let dir = package.path.file_name().unwrap();
+                dir.to_str().unwrap().to_string()
+            })
+            .collect()
+    };
+    // 0.2.0 is yanked.
+    assert_eq!(
+        dirs("bitflags@<0.3", VersionSelection::All),
+        ["bitflags-0.2.1", "bitflags-0.1.1", "bitflags-0.1.0"]
+    );
+    assert_eq!(
+        dirs("bitflags@<0.3", VersionSelection::Last(2)),
+        ["bitflags-0.2.1", "bitflags-0.1.1"]
+    );
+    let requirements = VersionSelection::Requirements(vec!["0.1.0".into(), "=0.1.0".into()]);
+    assert_eq!(dirs("bitflags", requirements), ["bitflags-0.1.0"]);
+}
+
+#[test]
+fn clone_history() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let dest = cloner
+        .clone_history("bitflags@<0.3", &VersionSelection::Last(2))
+        .unwrap();
+    assert_eq!(dest, td.path().join("bitflags"));
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&dest)
+            .args(args)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(
+        git(&["log", "--format=%s"]),
+        "bitflags 0.2.1\nbitflags 0.1.1\n"
+    );
+    assert_eq!(git(&["tag"]), "v0.1.1\nv0.2.1\n");
+    assert!(dest.join("src/lib.rs").exists());
+    // Only the repository is left in the output directory.
+    assert_eq!(std::fs::read_dir(td.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn layout() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path()).set_layout(Layout::Versioned);
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_eq!(package.path, td.path().join("bitflags").join("1.0.5"));
+    assert!(package.path.join("src/lib.rs").exists());
+    cloner.set_layout(Layout::Registry).set_dry_run(true);
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    let rel = package.path.strip_prefix(td.path()).unwrap();
+    assert_eq!(rel.components().count(), 3);
+    assert!(rel.ends_with("bitflags/1.0.5"));
+}
+
+#[test]
+fn crate_info() {
+    let info = Cloner::new().crate_info("bitflags").unwrap();
+    assert_eq!(info.krate.name, "bitflags");
+    assert!(info.krate.repository.unwrap().contains("bitflags"));
+    let version = info.versions.iter().find(|v| v.num == "1.0.5").unwrap();
+    assert!(version.checksum.is_some());
+    assert!(version.yanked);
+    assert!(matches!(
+        Cloner::new().crate_info("test").unwrap_err(),
+        CloneError::NotFound(_)
+    ));
+}
+
+#[test]
+fn prereleases() {
+    let version = Arc::new(Mutex::new(None));
+    let mut cloner = Cloner::new();
+    cloner.set_dry_run(true);
+    let selected = version.clone();
+    cloner.set_event_handler(move |event| {
+        if let CloneEvent::Version { version, .. } = event {
+            *selected.lock().unwrap() = Some(version.clone());
+        }
+    });
+    cloner
+        .clone(CloneMethodKind::Crate, "winit@<0.32", None, &[])
+        .unwrap();
+    assert!(!version.lock().unwrap().take().unwrap().contains('-'));
+    cloner.set_include_prereleases(true);
+    cloner
+        .clone(CloneMethodKind::Crate, "winit@<0.32", None, &[])
+        .unwrap();
+    assert!(version
+        .lock()
+        .unwrap()
+        .take()
+        .unwrap()
+        .starts_with("0.31.0-"));
+}
+
+#[test]
+fn yanked_versions() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    // 1.0.5 is yanked.
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@~1.0", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.4");
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5");
+    std::fs::remove_dir_all(td.path().join("bitflags-1.0.5")).unwrap();
+    cloner.set_include_yanked(true);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@~1.0", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5");
+}
+
+#[test]
+fn dest_name() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_dest_name("renamed");
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "renamed/Cargo.toml");
+    let err = cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &["dir"])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+    cloner.set_dest_name("renamed-git");
+    cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "renamed-git/.git");
+}
+
+#[test]
+fn strip_prefix() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_strip_prefix(true);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "Cargo.toml");
+    assert_downloaded(&td, "src/lib.rs");
+    let err = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::DestinationExists(_)));
+}
+
+#[test]
+fn crate_cache() {
+    let td = tempfile::tempdir().unwrap();
+    let cache = tempfile::tempdir().unwrap();
+    let cached = cache.path().join("bitflags-1.0.5.crate");
+    let mut cloner = Cloner::new();
+    std::fs::create_dir(td.path().join("a")).unwrap();
+    cloner.set_out_dir(td.path().join("a"));
+    cloner.set_cache_dir(cache.path());
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert!(cached.exists());
+    // A corrupt file in the cache is replaced.
+    std::fs::write(&cached, "not a crate").unwrap();
+    std::fs::create_dir(td.path().join("b")).unwrap();
+    cloner.set_out_dir(td.path().join("b"));
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "b/bitflags-1.0.5/Cargo.toml");
+    assert_ne!(std::fs::read(&cached).unwrap(), b"not a crate");
+    // The cached file is used.
+    std::fs::create_dir(td.path().join("c")).unwrap();
+    cloner.set_out_dir(td.path().join("c"));
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "c/bitflags-1.0.5/src/lib.rs");
+    // The file keeps the case of the published name, like cargo's cache.
+    cloner.set_cargo_cache(false);
+    cloner
+        .clone(CloneMethodKind::Crate, "inflector@=0.11.4", None, &[])
+        .unwrap();
+    assert!(cache.path().join("Inflector-0.11.4.crate").exists());
+}
+
+#[test]
+fn registry_mirrors() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_retries(0);
+    // Nothing listens on port 1.
+    cloner.set_registry_url("http://127.0.0.1:1");
+    cloner.add_registry_mirror(cargo_clone::DEFAULT_REGISTRY_URL);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5/Cargo.toml");
+}
+
+fn retry_after(value: &str) -> Option<Duration> {
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+    cargo_clone::retry_after(&headers)
+}
+
+#[test]
+fn retry_after_seconds() {
+    assert_eq!(retry_after("5"), Some(Duration::from_secs(5)));
+    // Long waits are capped.
+    assert_eq!(retry_after("86400"), Some(Duration::from_secs(30)));
+    assert_eq!(retry_after("soon"), None);
+    assert_eq!(cargo_clone::retry_after(&HeaderMap::new()), None);
+}
+
+#[test]
+fn retry_after_date() {
+    let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(10));
+    let delay = retry_after(&date).unwrap();
+    assert!(delay > Duration::from_secs(8) && delay <= Duration::from_secs(10));
+    let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(86400));
+    assert_eq!(retry_after(&date), Some(Duration::from_secs(30)));
+    assert_eq!(
+        retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+        Some(Duration::ZERO)
+    );
+}
+
+#[test]
+fn source_replacement() {
+    let td = tempfile::tempdir().unwrap();
+    // A local registry with a single version.
+    let registry = td.path().join("local");
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_cache_dir(&registry);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    std::fs::create_dir_all(registry.join("index/bi/tf")).unwrap();
+    std::fs::write(
+        registry.join("index/bi/tf/bitflags"),
+        r#"{"name":"bitflags","vers":"1.0.5","deps":[],"cksum":null,"yanked":false}"#,
+    )
+    .unwrap();
+    let cargo_home = td.path().join("cargo");
+    std::fs::create_dir(&cargo_home).unwrap();
+    std::fs::write(
+        cargo_home.join("config.toml"),
+        "[source.crates-io]\nreplace-with = \"mirror\"\n\
+         [source.mirror]\nlocal-registry = \"local\"\n",
+    )
+    .unwrap();
+
+    let old_home = std::env::var_os("CARGO_HOME");
+    std::env::set_var("CARGO_HOME", &cargo_home);
+    let mut cloner = Cloner::new();
+    let result = cloner.set_registry_name("crates-io").map(|_| ());
+    match old_home {
+        Some(home) => std::env::set_var("CARGO_HOME", home),
+        None => std::env::remove_var("CARGO_HOME"),
+    }
+    result.unwrap();
+    let out = td.path().join("out");
+    std::fs::create_dir(&out).unwrap();
+    cloner.set_out_dir(&out);
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "out/bitflags-1.0.5/src/lib.rs");
+}
+
+#[test]
+fn docs_rs_fallback() {
+    // A local registry without the `.crate` file.
+    let td = tempfile::tempdir().unwrap();
+    let registry = td.path().join("registry");
+    std::fs::create_dir_all(registry.join("index/bi/tf")).unwrap();
+    std::fs::write(
+        registry.join("index/bi/tf/bitflags"),
+        r#"{"name":"bitflags","vers":"1.0.5","deps":[],"cksum":null,"yanked":false}"#,
+    )
+    .unwrap();
+    let out = td.path().join("out");
+    std::fs::create_dir(&out).unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_local_registry(&registry);
+    cloner.set_out_dir(&out);
+    assert!(cloner
+        .clone(CloneMethodKind::Crate, "bitflags", None, &[])
+        .is_err());
+    cloner.set_docs_rs_fallback(true);
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags", None, &[])
+        .unwrap();
+    assert_eq!(
+        package.source,
+        "https://docs.rs/crate/bitflags/1.0.5/source/"
+    );
+    assert!(package.checksum.is_none());
+    assert_downloaded(&td, "out/bitflags-1.0.5/src/lib.rs");
+}
+
+#[test]
+fn cloned_package() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let package = cloner
+        .clone(CloneMethodKind::Auto, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_eq!(package.name, "bitflags");
+    assert_eq!(package.version.as_deref(), Some("1.0.5"));
+    assert_eq!(package.method.command(), "crate");
+    assert!(package.source.contains("bitflags"));
+    assert_eq!(package.path, td.path().join("bitflags-1.0.5"));
+    assert!(package.checksum.is_some());
+    let summary = package.summary.unwrap();
+    assert!(summary.description.is_some());
+    assert!(summary.license.is_some());
+    assert!(summary.documentation.is_some());
+    let latest = semver::Version::parse(&summary.latest_version.unwrap()).unwrap();
+    assert!(latest > semver::Version::new(1, 0, 5));
+}
+
+#[test]
+fn metadata_file() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    let text = std::fs::read_to_string(package.path.join(METADATA_FILE)).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(value["name"], "bitflags");
+    assert_eq!(value["version"], "1.0.5");
+    assert_eq!(value["method"], "crate");
+    assert_eq!(value["source"], package.source.as_str());
+    assert_eq!(value["checksum"], package.checksum.unwrap().as_str());
+    assert!(value["timestamp"].is_string());
+    cloner.set_metadata_file(false).set_force(true);
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert!(!package.path.join(METADATA_FILE).exists());
+}
+
+#[test]
+fn update() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    let dir = td.path().join("bitflags-1.0.5");
+    assert!(cloner.update(&dir, Some("~1.0")).unwrap().is_none());
+    let package = cloner.update(&dir, Some("~1.1")).unwrap().unwrap();
+    assert_eq!(package.version.as_deref(), Some("1.1.0"));
+    assert!(!dir.exists());
+    assert_downloaded(&td, "bitflags-1.1.0/src/lib.rs");
+    // A directory with another name is updated in place.
+    let dir = td.path().join("bitflags-1.1.0");
+    let flags = td.path().join("flags");
+    std::fs::rename(&dir, &flags).unwrap();
+    std::fs::remove_file(flags.join(METADATA_FILE)).unwrap();
+    let package = cloner.update(&flags, Some("~1.2")).unwrap().unwrap();
+    assert_eq!(package.path, flags.canonicalize().unwrap());
+    assert_eq!(package.version.as_deref(), Some("1.2.1"));
+    let err = cloner.update(td.path(), None).unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+}
+
+#[test]
+fn fallback_methods() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let missing = CloneMethodKind::Custom {
+        command: "cargo-clone-missing-command".to_string(),
+        args_template: "{repo}".to_string(),
+    };
+    let err = cloner
+        .clone(missing.clone(), "bitflags", None, &[])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::Vcs(_)));
+    cloner.set_fallback_methods(vec![CloneMethodKind::Crate]);
+    let package = cloner.clone(missing, "bitflags", None, &[]).unwrap();
+    assert_eq!(package.method.command(), "crate");
+    assert!(package.path.join("src/lib.rs").exists());
+}
+
+#[test]
+fn available_methods() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let methods: Vec<_> = cloner
+        .available_methods()
+        .iter()
+        .map(|m| m.command().to_string())
+        .collect();
+    assert!(methods.iter().any(|m| m == "crate"));
+    assert!(methods.iter().any(|m| m == "auto"));
+    let missing = CloneMethodKind::Custom {
+        command: "cargo-clone-missing-command".to_string(),
+        args_template: "{repo}".to_string(),
+    };
+    let err = cloner.clone(missing, "bitflags", None, &[]).unwrap_err();
+    assert!(matches!(err, CloneError::Vcs(_)));
+    assert!(err.to_string().contains("not found on PATH"), "{}", err);
+}
+
+#[test]
+fn check_repo() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let url = "https://github.com/ehuss/cargo-clone-crate-missing";
+    let err = cloner
+        .clone_url(CloneMethodKind::Git, url, &[])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::Repository(_)));
+    assert!(err.to_string().contains("private or deleted"), "{}", err);
+    assert!(!td.path().join("cargo-clone-crate-missing").exists());
+}
+
+#[test]
+fn extract_crate_file() {
+    let td = tempfile::tempdir().unwrap();
+    let cache = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_cache_dir(cache.path());
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    let file = cache.path().join("bitflags-1.0.5.crate");
+    let out = td.path().join("out");
+    std::fs::create_dir(&out).unwrap();
+    let package = Cloner::new().extract_crate_file(&file, &out).unwrap();
+    assert_eq!(package.path, out.join("bitflags-1.0.5"));
+    assert_eq!(package.version.as_deref(), Some("1.0.5"));
+    assert!(package.summary.is_none());
+    assert_downloaded(&td, "out/bitflags-1.0.5/src/lib.rs");
+    // Not a `.crate` file.
+    std::fs::write(&file, "not a crate").unwrap();
+    let err = Cloner::new().extract_crate_file(&file, &out).unwrap_err();
+    assert!(matches!(err, CloneError::Extract(_)));
+}
+
+#[test]
+fn extract_filter() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner
+        .set_extract_filter(&["src/**", "Cargo.toml"], &["src/example_*"])
+        .unwrap();
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5/Cargo.toml");
+    assert_downloaded(&td, "bitflags-1.0.5/src/lib.rs");
+    let dir = td.path().join("bitflags-1.0.5");
+    assert!(!dir.join("README.md").exists());
+    assert!(!dir.join("src/example_generated.rs").exists());
+    assert!(matches!(
+        cloner.set_extract_filter(&["["], &[]).unwrap_err(),
+        CloneError::InvalidInput(_)
+    ));
+}
+
+#[test]
+fn reproducible() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_reproducible(true);
+    let package = cloner
+        .clone(CloneMethodKind::Auto, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_eq!(package.method.command(), "crate");
+    for path in ["", "src", "src/lib.rs", "Cargo.toml"] {
+        let metadata = std::fs::metadata(package.path.join(path)).unwrap();
+        let mtime = metadata.modified().unwrap();
+        let mtime = mtime.duration_since(std::time::UNIX_EPOCH).unwrap();
+        assert_eq!(mtime.as_secs(), 315_532_800);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = if metadata.is_dir() { 0o755 } else { 0o644 };
+            assert_eq!(metadata.permissions().mode() & 0o777, mode);
+        }
+    }
+    assert!(matches!(
+        cloner
+            .clone(CloneMethodKind::Git, "bitflags", None, &[])
+            .unwrap_err(),
+        CloneError::InvalidInput(_)
+    ));
+}
+
+/// Writes a `.crate` file with raw entries of `(path, type, link, data)`,
+/// since `tar::Builder` rejects unsafe paths.
+fn write_crate(path: &Path, entries: &[(&str, tar::EntryType, &str, &str)]) {
+    let file = std::fs::File::create(path).unwrap();
+    let gz = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut builder = tar::Builder::new(gz);
+    for (path, kind, link, data) in entries {
+        let mut header = tar::Header::new_gnu();
+        let old = header.as_old_mut();
+        old.name[..path.len()].copy_from_slice(path.as_bytes());
+        old.linkname[..link.len()].copy_from_slice(link.as_bytes());
+        header.set_entry_type(*kind);
+        header.set_mode(0o644);
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+        builder.append(&header, data.as_bytes()).unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn extract_policy() {
+    use cargo_clone::ExtractPolicy;
+    use tar::EntryType::{Link, Regular, Symlink};
+
+    let td = tempfile::tempdir().unwrap();
+    let links = td.path().join("links-0.1.0.crate");
+    write_crate(
+        &links,
+        &[
+            ("links-0.1.0/Cargo.toml", Regular, "", "[package]"),
+            ("links-0.1.0/lib.rs", Symlink, "Cargo.toml", ""),
+            ("links-0.1.0/escape", Symlink, "../../outside", ""),
+            ("links-0.1.0/hard", Link, "links-0.1.0/Cargo.toml", ""),
+        ],
+    );
+    let mut cloner = Cloner::new();
+    let dir = td.path().join("links-0.1.0");
+    cloner.extract_crate_file(&links, td.path()).unwrap();
+    assert!(dir.join("lib.rs").symlink_metadata().unwrap().is_symlink());
+    assert!(dir.join("escape").symlink_metadata().is_err());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("hard")).unwrap(),
+        "[package]"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+    cloner.set_link_policy(ExtractPolicy::Skip);
+    cloner.extract_crate_file(&links, td.path()).unwrap();
+    assert!(dir.join("Cargo.toml").exists());
+    assert!(dir.join("lib.rs").symlink_metadata().is_err());
+    assert!(!dir.join("hard").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+    cloner.set_link_policy(ExtractPolicy::Error);
+    let err = cloner.extract_crate_file(&links, td.path()).unwrap_err();
+    assert!(matches!(err, CloneError::Extract(_)));
+    assert!(!dir.exists());
+
+    let paths = td.path().join("paths-0.1.0.crate");
+    write_crate(
+        &paths,
+        &[
+            ("paths-0.1.0/Cargo.toml", Regular, "", "[package]"),
+            ("paths-0.1.0/src/../lib.rs", Regular, "", "// lib"),
+            ("/paths-0.1.0/abs", Regular, "", "abs"),
+            ("paths-0.1.0/../../evil", Regular, "", "evil"),
+        ],
+    );
+    let mut cloner = Cloner::new();
+    let dir = td.path().join("paths-0.1.0");
+    let err = cloner.extract_crate_file(&paths, td.path()).unwrap_err();
+    assert!(matches!(err, CloneError::Extract(_)));
+    assert!(!dir.exists());
+    cloner.set_path_policy(ExtractPolicy::Skip);
+    cloner.extract_crate_file(&paths, td.path()).unwrap();
+    assert!(dir.join("Cargo.toml").exists());
+    assert!(!dir.join("lib.rs").exists());
+    assert!(!dir.join("abs").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+    cloner.set_path_policy(ExtractPolicy::Sanitize);
+    cloner.extract_crate_file(&paths, td.path()).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("lib.rs")).unwrap(),
+        "// lib"
+    );
+    assert_eq!(std::fs::read_to_string(dir.join("abs")).unwrap(), "abs");
+    assert!(!dir.join("evil").exists());
+    assert!(!td.path().join("evil").exists());
+    assert!(!td.path().parent().unwrap().join("evil").exists());
+}
+
+#[test]
+fn execute_request() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let request = CloneRequest::new("bitflags")
+        .method(CloneMethodKind::Crate)
+        .version("=1.0.5")
+        .dest_name("flags")
+        .extract_filter(&["Cargo.toml"], &[]);
+    let package = cloner.execute(&request).unwrap();
+    assert_eq!(package.path, td.path().join("flags"));
+    assert_downloaded(&td, "flags/Cargo.toml");
+    assert!(!td.path().join("flags/src").exists());
+    // The options only apply to the request.
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5/src/lib.rs");
+    let request = CloneRequest::new("bitflags@=1.0.5").extract_filter(&["["], &[]);
+    assert!(matches!(
+        cloner.execute(&request).unwrap_err(),
+        CloneError::InvalidInput(_)
+    ));
+}
+
+#[test]
+fn clone_temp() {
+    let cloner = Cloner::new();
+    let request = CloneRequest::new("bitflags@=1.0.5").method(CloneMethodKind::Crate);
+    let temp = cloner.clone_temp(&request).unwrap();
+    let dir = temp.dir().to_path_buf();
+    assert_eq!(temp.path(), dir.join("bitflags-1.0.5"));
+    assert!(temp.path().join("src/lib.rs").exists());
+    drop(temp);
+    assert!(!dir.exists());
+    let package = cloner.clone_temp(&request).unwrap().keep();
+    assert!(package.path.join("src/lib.rs").exists());
+    std::fs::remove_dir_all(package.path.parent().unwrap()).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn open_command() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_open_command("test -d");
+    cloner.open(td.path()).unwrap();
+    cloner.set_open_command("test -f");
+    assert!(cloner.open(td.path()).is_err());
+}
+
+#[test]
+fn git_init() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner
+        .set_out_dir(td.path())
+        .set_git_init(true)
+        .set_git_init_tag(true);
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&package.path)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(git(&["tag"]), "v1.0.5\n");
+    assert_eq!(git(&["status", "--porcelain"]), "");
+    assert!(git(&["ls-files"]).contains("src/lib.rs"));
+}
+
+#[test]
+fn diff_versions() {
+    let cloner = Cloner::new();
+    let stat = cloner
+        .diff_versions("bitflags", "1.0.4", "1.0.5", true)
+        .unwrap();
+    assert!(stat.contains("build.rs"));
+    assert!(stat.contains("src/lib.rs"));
+    assert!(!stat.contains("README.md"));
+    let diff = cloner
+        .diff_versions("bitflags", "1.0.4", "1.0.5", false)
+        .unwrap();
+    assert!(diff.contains("+++ b/bitflags-1.0.5/build.rs"));
+}
+
+#[test]
+fn custom_method() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let method = CloneMethodKind::Custom {
+        command: "git".to_string(),
+        args_template: "clone {repo} {dest}".to_string(),
+    };
+    cloner.clone(method, "bitflags", None, &["bf"]).unwrap();
+    assert_downloaded(&td, "bf/.git");
+}
+
+#[test]
+fn pijul_channel_with_other_method() {
+    let mut cloner = Cloner::new();
+    cloner.set_pijul_channel("main");
+    let err = cloner
+        .clone(CloneMethodKind::Crate, "bitflags", None, &[])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("only works with the `pijul` method"));
+}
+
+fn write_lockfile(dir: &TempDir) -> std::path::PathBuf {
+    let path = dir.path().join("Cargo.lock");
+    std::fs::write(
+        &path,
+        r#"
+version = 3
+
+[[package]]
+name = "bitflags"
+version = "1.3.2"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "bitflags"
+version = "2.4.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "foo"
+version = "0.1.0"
+dependencies = ["bitflags 1.3.2", "bitflags 2.4.0"]
+"#,
+    )
+    .unwrap();
+    path
+}
+
+#[test]
+fn read_lockfile() {
+    let td = tempfile::tempdir().unwrap();
+    let packages = cargo_clone::read_lockfile(&write_lockfile(&td)).unwrap();
+    assert_eq!(packages.len(), 3);
+    assert_eq!(packages[1].version, "2.4.0");
+    assert!(packages[1].is_crates_io());
+    assert!(packages[2].source.is_none());
+}
+
+#[test]
+fn locked_from_errors() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_lockfile(write_lockfile(&td));
+    let err = |spec| {
+        cloner
+            .clone(CloneMethodKind::Crate, spec, None, &[])
+            .unwrap_err()
+            .to_string()
+    };
+    assert!(err("bitflags").contains("Multiple versions of `bitflags`"));
+    assert!(err("bitflags@^3").contains("not found in"));
+    assert!(err("foo").contains("is a local package"));
+}
+
+#[test]
+fn locked_from() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_lockfile(write_lockfile(&td));
+    cloner
+        .clone(CloneMethodKind::Auto, "bitflags@^1", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.3.2");
+}
+
+#[test]
+fn project_lockfile() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_dry_run(true);
+    cloner.set_project_lockfile(write_lockfile(&td));
+    let version = |spec| {
+        cloner
+            .clone(CloneMethodKind::Crate, spec, None, &[])
+            .unwrap()
+            .version
+            .unwrap()
+    };
+    // The newest of the pinned versions.
+    assert_eq!(version("bitflags"), "2.4.0");
+    // A version given replaces the lockfile.
+    assert_eq!(version("bitflags@=1.0.5"), "1.0.5");
+    // VCS methods clone the repository as usual.
+    let package = cloner
+        .clone(CloneMethodKind::Auto, "bitflags", None, &[])
+        .unwrap();
+    assert!(matches!(package.method, CloneMethodKind::Git));
+    cloner
+        .clone(CloneMethodKind::Mercurial, "bitflags", None, &[])
+        .unwrap();
+    cloner.set_tag("1.0.0");
+    cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &[])
+        .unwrap();
+}
+
+#[test]
+fn dependency_requests() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let cloner = Cloner::new();
+    let direct = cloner.dependency_requests(&manifest, false).unwrap();
+    let specs: Vec<_> = direct.iter().map(|r| r.spec()).collect();
+    assert!(specs.iter().any(|spec| spec.starts_with("semver@=")));
+    assert!(!specs.iter().any(|spec| spec.starts_with("cargo-clone@")));
+    let all = cloner.dependency_requests(&manifest, true).unwrap();
+    assert!(all.len() > direct.len());
+}
+
+#[test]
+fn read_request_list() {
+    let td = tempfile::tempdir().unwrap();
+    let path = td.path().join("crates.txt");
+    std::fs::write(&path, "# audit\nbitflags@1.0.5\n\n  serde  \n").unwrap();
+    let requests = cargo_clone::read_request_list(&path).unwrap();
+    let specs: Vec<_> = requests.iter().map(|r| r.spec()).collect();
+    assert_eq!(specs, ["bitflags@1.0.5", "serde"]);
+    std::fs::write(&path, r#"["bitflags@1.0.5", "serde"]"#).unwrap();
+    let requests = cargo_clone::read_request_list(&path).unwrap();
+    assert_eq!(requests.len(), 2);
+    std::fs::write(&path, "[1, 2]").unwrap();
+    let err = cargo_clone::read_request_list(&path).unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+}
+
+#[test]
+fn owned_crates() {
+    let cloner = Cloner::new();
+    let names = cloner.owned_crates("ehuss").unwrap();
+    assert!(names.iter().any(|name| name == "cargo-clone"));
+    let err = cloner
+        .owned_crates("github:no-such-org:no-such-team")
+        .unwrap_err();
+    assert!(matches!(err, CloneError::NotFound(_)));
+}
+
+#[test]
+fn search_crates() {
+    let names = Cloner::new().search_crates("serde", 2).unwrap();
+    assert_eq!(names.len(), 2);
+    assert!(names.iter().any(|name| name == "serde"));
+}
+
+#[test]
+fn not_found_suggestions() {
+    let err = Cloner::new().crate_info("serde-json").unwrap_err();
+    assert!(matches!(err, CloneError::NotFound(_)));
+    assert!(err.to_string().contains("Did you mean `serde_json`?"));
+}
+
+#[test]
+fn fuzzy_name() {
+    let mut cloner = Cloner::new();
+    cloner.set_dry_run(true).set_fuzzy_name(true);
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "serde-json", Some("1.0.100"), &[])
+        .unwrap();
+    assert_eq!(package.name, "serde_json");
+    assert!(package.path.ends_with("serde_json-1.0.100"));
+}
+
+#[test]
+fn add_patch() {
+    let td = tempfile::tempdir().unwrap();
+    let member = td.path().join("member");
+    std::fs::create_dir_all(member.join("src")).unwrap();
+    std::fs::create_dir(td.path().join("patches")).unwrap();
+    std::fs::write(
+        td.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"member\"]\n",
+    )
+    .unwrap();
+    std::fs::write(member.join("Cargo.toml"), "[package]\nname = \"member\"\n").unwrap();
+    let manifest = cargo_clone::find_workspace_manifest(&member.join("src")).unwrap();
+    assert_eq!(manifest, td.path().join("Cargo.toml"));
+
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path().join("patches"));
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    cloner.add_patch(&manifest, &package).unwrap();
+    let contents = std::fs::read_to_string(&manifest).unwrap();
+    assert!(contents
+        .ends_with("\n[patch.crates-io]\nbitflags = { path = \"patches/bitflags-1.0.5\" }\n"));
+    // Patching the same path again is fine, another one is not.
+    cloner.add_patch(&manifest, &package).unwrap();
+    cloner.set_out_dir(td.path());
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    let err = cloner.add_patch(&manifest, &package).unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+}
+
+#[test]
+fn vendor() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_vendor(true);
+    let package = cloner
+        .clone(CloneMethodKind::Auto, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    let contents = std::fs::read_to_string(package.path.join(".cargo-checksum.json")).unwrap();
+    let checksums: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(checksums["package"].as_str(), package.checksum.as_deref());
+    assert_eq!(checksums["files"]["src/lib.rs"].as_str().unwrap().len(), 64);
+    let err = cloner
+        .clone(CloneMethodKind::Git, "bitflags", None, &[])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+}
+
+#[test]
+fn keep_archive() {
+    use sha2::{Digest, Sha256};
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_keep_archive(true);
+    let package = cloner
+        .clone(CloneMethodKind::Auto, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert!(matches!(package.method, CloneMethodKind::Crate));
+    assert!(package.path.join("src/lib.rs").exists());
+    let archive = package.archive.unwrap();
+    assert_eq!(archive, td.path().join("bitflags-1.0.5.crate"));
+    let checksum = format!("{:x}", Sha256::digest(std::fs::read(&archive).unwrap()));
+    assert_eq!(Some(checksum), package.checksum);
+
+    cloner.set_keep_archive(false).set_extract(false);
+    let package = cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.4", None, &[])
+        .unwrap();
+    assert_eq!(package.path, td.path().join("bitflags-1.0.4.crate"));
+    assert_eq!(package.archive.as_ref(), Some(&package.path));
+    assert!(package.path.is_file());
+    assert!(!td.path().join("bitflags-1.0.4").exists());
+}
+
+#[test]
+fn extract_limits() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let clone =
+        |cloner: &Cloner| cloner.clone(CloneMethodKind::Crate, "bitflags@=0.1.0", None, &[]);
+    cloner.set_max_extract_size(Some(10_000));
+    assert!(matches!(
+        clone(&cloner).unwrap_err(),
+        CloneError::Extract(_)
+    ));
+    cloner
+        .set_max_extract_size(None)
+        .set_max_file_size(Some(10_000));
+    assert!(matches!(
+        clone(&cloner).unwrap_err(),
+        CloneError::Extract(_)
+    ));
+    cloner.set_max_file_size(None).set_max_entries(Some(3));
+    assert!(matches!(
+        clone(&cloner).unwrap_err(),
+        CloneError::Extract(_)
+    ));
+    assert_eq!(std::fs::read_dir(td.path()).unwrap().count(), 0);
+    cloner.set_max_entries(None);
+    clone(&cloner).unwrap();
+    assert_downloaded(&td, "bitflags-0.1.0");
+}
+
+#[test]
+fn list_crate_files() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let listing = cloner.list_crate_files("bitflags@0.1.0", None).unwrap();
+    assert_eq!(listing.version, "0.1.0");
+    let lib = listing
+        .files
+        .iter()
+        .find(|f| f.path == Path::new("src/lib.rs"))
+        .unwrap();
+    assert_eq!(lib.size, 14717);
+    assert!(listing
+        .files
+        .iter()
+        .any(|f| f.path == Path::new("Cargo.toml")));
+    assert_eq!(std::fs::read_dir(td.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn read_crate_file() {
+    let cloner = Cloner::new();
+    let contents = cloner
+        .read_crate_file("bitflags@0.1.0", None, Path::new(".gitignore"))
+        .unwrap();
+    assert_eq!(contents, b"/target\n/Cargo.lock\n");
+    let err = cloner
+        .read_crate_file("bitflags@0.1.0", None, Path::new("build.rs"))
+        .unwrap_err();
+    assert!(matches!(err, CloneError::NotFound(_)));
+}
+
+#[test]
+fn lockfile_requests() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let requests = cloner.lockfile_requests(&write_lockfile(&td)).unwrap();
+    let specs: Vec<_> = requests.iter().map(|r| r.spec()).collect();
+    assert_eq!(specs, ["bitflags@=1.3.2", "bitflags@=2.4.0"]);
+    let results = cloner.clone_many(&requests);
+    assert!(results.iter().all(Result::is_ok));
+    assert_downloaded(&td, "bitflags-1.3.2");
+    assert_downloaded(&td, "bitflags-2.4.0");
+}
+
+#[test]
+fn error_kinds() {
+    let cloner = Cloner::new();
+    let err = |spec, version| {
+        cloner
+            .clone(CloneMethodKind::Crate, spec, version, &[])
+            .unwrap_err()
+    };
+    assert!(matches!(
+        err("foo:1.2.3", Some("1.2.3")),
+        CloneError::InvalidInput(_)
+    ));
+    assert!(matches!(err("foo", Some("abc")), CloneError::Version(_)));
+    assert!(matches!(err("test", None), CloneError::NotFound(_)));
+}
+
+#[test]
+fn sparse_registry() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_registry_url("sparse+https://index.crates.io/");
+    cloner
+        .clone(CloneMethodKind::Auto, "bitflags", Some("=1.0.5"), &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5/Cargo.toml");
+    // The index config is only fetched for the first package.
+    cloner
+        .clone(CloneMethodKind::Crate, "semver", Some("=1.0.0"), &[])
+        .unwrap();
+    assert_downloaded(&td, "semver-1.0.0/Cargo.toml");
+    let err = cloner
+        .clone(CloneMethodKind::Crate, "test", None, &[])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::NotFound(_)));
+}
+
+#[test]
+fn registry_name() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    let err = cloner.set_registry_name("no-such-registry").unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+
+    std::env::set_var(
+        "CARGO_REGISTRIES_CLONE_TEST_SPARSE_INDEX",
+        "sparse+https://index.crates.io/",
+    );
+    cloner.set_registry_name("clone-test-sparse").unwrap();
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags", Some("=1.0.5"), &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5/Cargo.toml");
+}
+
+#[test]
+fn git_index_registry() {
+    let td = tempfile::tempdir().unwrap();
+    let index = td.path().join("index");
+    std::fs::create_dir(&index).unwrap();
+    std::fs::write(
+        index.join("config.json"),
+        format!(r#"{{"api":"{}"}}"#, cargo_clone::DEFAULT_REGISTRY_URL),
+    )
+    .unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&index)
+            .args(["-c", "user.name=test", "-c", "user.email=test@localhost"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    git(&["init", "-q"]);
+    git(&["add", "config.json"]);
+    git(&["commit", "-q", "-m", "index"]);
+
+    std::env::set_var("CARGO_REGISTRIES_CLONE_TEST_GIT_INDEX", &index);
+    let cache = td.path().join("cache");
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path()).set_cache_dir(&cache);
+    cloner.set_registry_name("clone-test-git").unwrap();
+    // The index is only fetched once package information is needed.
+    assert!(!cache.join("index").exists());
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.5", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.5/Cargo.toml");
+    // Later runs reuse the clone in the cache.
+    std::fs::remove_dir_all(&index).unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path()).set_cache_dir(&cache);
+    cloner.set_registry_name("clone-test-git").unwrap();
+    cloner
+        .clone(CloneMethodKind::Crate, "bitflags@=1.0.4", None, &[])
+        .unwrap();
+    assert_downloaded(&td, "bitflags-1.0.4/Cargo.toml");
+}
+
+#[test]
+fn invalid_proxy() {
+    let mut cloner = Cloner::new();
+    cloner.set_proxy("http://[invalid");
+    let err = cloner
+        .clone(CloneMethodKind::Crate, "bitflags", None, &[])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+}
+
+#[test]
+fn custom_http_client() {
+    // Requests go through a proxy that nothing listens on.
+    let client = reqwest::blocking::Client::builder()
+        .proxy(reqwest::Proxy::all("http://127.0.0.1:9").unwrap())
+        .build()
+        .unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_http_client(client).set_retries(0);
+    let err = cloner
+        .clone(CloneMethodKind::Crate, "bitflags", None, &[])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::Http(_)));
+}
+
+#[test]
+fn invalid_root_certificate() {
+    let td = tempfile::tempdir().unwrap();
+    let path = td.path().join("ca.pem");
+    std::fs::write(&path, "not a certificate").unwrap();
+    let mut cloner = Cloner::new();
+    let err = cloner.add_root_certificate(&path).unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+}
+
+#[test]
+fn clone_url_crate() {
+    let err = Cloner::new()
+        .clone_url(
+            CloneMethodKind::Crate,
+            "https://github.com/bitflags/bitflags",
+            &[],
+        )
+        .unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+}
+
+#[test]
+fn checkout_with_crate() {
+    let mut cloner = Cloner::new();
+    cloner.set_tag("1.3.2");
+    let err = cloner
+        .clone(CloneMethodKind::Crate, "bitflags", None, &[])
+        .unwrap_err();
+    assert!(matches!(err, CloneError::InvalidInput(_)));
+}
+
+#[test]
+fn clone_many() {
+    let td = tempfile::tempdir().unwrap();
+    let mut cloner = Cloner::new();
+    cloner.set_out_dir(td.path());
+    cloner.set_jobs(2);
+    let results = cloner.clone_many(&[
+        CloneRequest::new("bitflags@=1.0.5"),
+        CloneRequest::new("test"),
+        CloneRequest::new("semver@=1.0.0"),
+    ]);
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(CloneError::NotFound(_))));
+    assert!(results[2].is_ok());
+    assert_downloaded(&td, "bitflags-1.0.5/Cargo.toml");
+    assert_downloaded(&td, "semver-1.0.0/Cargo.toml");
+}
+
+#[cfg(feature = "bin")]
+#[test]
+fn cli_package_and_dir() {
+    let td = tempfile::tempdir().unwrap();
+    let run = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_cargo-clone"))
+            .args(["clone", "--dry-run", "--method", "git"])
+            .args(args)
+            .current_dir(td.path())
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        (output.status.success(), stdout)
+    };
+    // The directory could also be another package.
+    let (success, stdout) = run(&["bitflags", "bf"]);
+    assert!(!success);
+    assert!(
+        stdout.contains("Ambiguous arguments `bitflags bf`"),
+        "{}",
+        stdout
+    );
+    let (success, stdout) = run(&["bitflags", "--", "bf"]);
+    assert!(success, "{}", stdout);
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.starts_with("Would run: git clone ") && line.ends_with(" bf")),
+        "{}",
+        stdout
+    );
+}